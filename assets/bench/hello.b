@@ -0,0 +1,7 @@
+Hello World
+Prints the greeting with the values built by short multiplication loops
+
++++++++++[->++++++++<]>.<+++++++[->++++<]>+.+++++++..+++.<++++++
++++[->--------<]>-------.<+++++++++++++[->++++<]>+++.<++++++[->+
++++<]>.+++.------.--------.<++++++++[->--------<]>---.<+++++[->-
+---<]>---.
@@ -0,0 +1,67 @@
+Mandelbrot set sketch
+Iterates z squared plus c up to 12 times for each of 31 by 13 points
+Fixed point numbers with the scale of 12 are kept offset by 72
+and split into the sign and the magnitude for the products
+Needs 16 bit cells
+
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+++++++++++++<<<<<<<<<<<
+<<<<<<<<<<<<<<<<<<<<<<<<<++++++++[-<<<+++++++>>>]<<<++++>>>>>>>>
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+<<<<++++++[->>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+++++<<<<<<<<<<<<
+<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+<<<<
+<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<++++++++[-<<<<++++++>>>>]<<<<>>>>
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+<<<<<<<+++++++++[-<<++++++++>>]<<>>+++++++++[-<++++++++>]<>>>>>>
+>>>>>>>>>>>>>>>>>>>>>>++++++++++++>>>+[<<<<<<<<<<<<<<<<<<<<<<<<<
+<<<<<<<[->>>>+<<<<]>>+++++++++[->++++++++<]>[->>+<[->-]>[->>+<<>
+]<<<]>[->>>>>>>+<<<<<<<]>>>[->>>>+<<<+<]>[>+<[-]]<<<<<<<[->>>+<<
+<]>+++++++++[->++++++++<]>[->>+<[->-]>[->>+<<>]<<<]>[->>>>>>>>+<
+<<<<<<<]>>>[->>>>>+<<<<+<]>[>>+<<[-]]>>>+>+>++<<[->>->+<[>-]>[-<
+++>>>+<<>]<<<<]>>[-]++<[->->+<[>-]>[-<++>>>>+<<<>]<<<]>[-]>>>[-<
+<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>+<<<]>>>[-<<<+>>>]<<<<<<<<<<<<<<<
+<<[>>>>>>>>>>>>>>[->>+>+<<<]>>>[-<<<+>>>]<<<<<<<<<<<<<<<<<-]>>>>
+>>>>>>>>>>>>+<<<<<+++>>>>>[-<<<<<->+<[>-]>[-<+++>>>>>>>+<<<<<<>]
+<<>>>>>]<<<<<[-]>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>+<<]>>[-<
+<+>>]<<<<<<<<<<<<<<<<<[>>>>>>>>>>>>>>>[->+>+<<]>>[-<<+>>]<<<<<<<
+<<<<<<<<<<-]>>>>>>>>>>>>>>>>+<<<<<+++>>>>>[-<<<<<->+<[>-]>[-<+++
+>>>>>>>>+<<<<<<<>]<<>>>>>]<<<<<[-]>>>[-<<<<<<<<<<<<<<+>>>>>>>>>>
+>>>>>>>+<<<]>>>[-<<<+>>>]<<<<<<<<<<<<<<<<<[>>>>>>>>>>>>>>>[->+>+
+<<]>>[-<<+>>]<<<<<<<<<<<<<<<<<-]>>>>>>>>>>>>>>>>[->++<]>+<<<<<<+
+++>>>>>>[-<<<<<<->+<[>-]>[-<+++>>>>>>>>>+<<<<<<<<>]<<>>>>>>]<<<<
+<<[-]>>>[-]>[-]>>>[->>>+<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>
+]<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<]>>>>>
+>>>>>>>>>>>>>>[->>+<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>]<<<
+<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<<<]>>>>>>
+>>>>>>>>>>>>>>>>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[
+->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]
+>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->
+]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>
++<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[-
+>-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>
+[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]
+<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+
+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->
+-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[
+->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<
+<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[[-]>>>>+<<<<>-]>[->]<<>>>>>+<
+[>>>>>[-]<<<<[-]<[-]]>[<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>+>>+<<<
+<]>>>>[-<<<<+>>>>]>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<+>>>>>
+>>>>>>>>>>>>>>>]>[-<<<<<<<<<<<<<<<<<<<<<->>>>>>>>>>>>>>>>>>>>>]<
+<<<<<<<<<<<<<<<<<<<<<[->>+>+<<<]>>>[-<<<+>>>]>>>>>>>[->>>>>>>>>>
+>>>>>>>+<<<<<<<<<<<<<<<<<]>[>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<
+<<<<<<+>>>>>>>>>>>>>>>>>>>>>>>>]+<<<<<<<<<<<<<<<<<<<<<<<<[->>>>>
+>>>>>>>>>>>>>>>>>>>-<<<<<<<<<<<<<<<<<<<<<<<<]>>>>>>>>[-]]>>>>>>>
+>>>>>>>>>[<<<<[-<<<<<<<<<<<<<<<<<<<<<->>>>>>>>>>>>>>>>>>>>>]>>>>
+[-]]<<<<[-<<<<<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>>>>>]>>>>>>>->+
+<[>-]>[->>[-]<<>]<<<[-]]<<<<<<<<[-]>[-]>[-]<<<<<<<<<<<<<[-]>[-]>
+>>>>>>>>>>>>>>>>>>>>>]>>>>++++++++++++++++++++++++++++++++++++++
+++++++++++++++++++++++++++<<<<<<<[->>>>+<<<<]>>>>>+<[->>>-------
+----------------------<<<>-]>[->]<<>+<[->>>+++++++<<<>-]>[->]<<>
++<[->>>+<<<>-]>[->]<<>+<[->>>++++++++++++++++++<<<>-]>[->]<<>+<[
+->>>--<<<>-]>[->]<<>+<[->>>-<<<>-]>[->]<<>+<[->>><<<>-]>[->]<<>+
+<[->>>--------------<<<>-]>[->]<<>+<[->>><<<>-]>[->]<<>+<[->>>++
+<<<>-]>[->]<<>+<[->>><<<>-]>[->]<<>+<[->>>--------------<<<>-]>[
+->]<<>>>.[-]<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<[-]>[-]<<<+>>>>>
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>-]<++++++++++.[-]<<<<<<<<<<<<<
+<<<<<<<<<<<<<<<<<<<<<<<<<[-]>++>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+>>>>>>-]
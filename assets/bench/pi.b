@@ -0,0 +1,42 @@
+Digits of pi
+Prints the first 20 digits with the spigot algorithm of Rabinowitz and Wagon
+Every term of the mixed radix fraction takes a block of 11 cells
+and the carry is swept from the last block to the first one
+Needs 16 bit cells
+
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>+++++++++++++++++++++
+++++++++++++++++++++++++++++++++++++++++++++++[<<<<<<<<<<+>++<<<
+<<<<<<<[->>>>>>>>>>>+>>>>+<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>[-<<<<<
+<<<<<<<<<<+>>>>>>>>>>>>>>>]<<<<+[->++>>>+<<<<]>>>>[-<<<<+>>>>]<<
+<->>>>>>>[->>>>>>>>>>>+<<<<<<<<<<<]>>>>>>>>>>>-]<<<<<<<<<<<<<<<<
+<<<<<[<<<<<<<<<<<]<<<<<<<<<<<<<<<<<<<<<<++++++++++++++++++++[>>>
+>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>[>>>>>>>>>>>]<<<<<<<<<<<[>[->>>>++
+++++++++<<<<]>>>[-<<[->>>+>+<<<<]>>>>[-<<<<+>>>>]<<]<[->>>>+<+<<
+<]>>>[-<<<+>>>]<[->>->+<[>-]>[-<<<<<[->>>>+<+<<<]>>>[-<<<+>>>]>>
+>>+<<>]<<<<]<<[-<<+>>>>>+<<<]>>>[-<<<+>>>]>[-<<<<<<->>>>>>]>>>[-
+<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<<<<<<<<<<<<<<<<<]<<<<<<
+<<<<<<<<<<<++++++++++>>>>>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<<<<
+<->+<[>-]>[-<++++++++++>>>+<<>]<<>>>>>>>>>>>>>>>>>>>>>]>>>>>>>>+
++++++++++<<<<<<<<<<<<<<<<<<<<<<<<<<<<<[->>>>>>>>>>>>>>>>>>>>>>>>
+>>>>>-<<<<<<<<<<<<<<<<<<<<<<<<<<<<<]>>>[->>+>>>+>>>>>+<<<<<<<<<<
+]>>>>>>>>>>[-<<<<<<<<<<+>>>>>>>>>>]<<<<<<<<+>>>+<<<>+<[->-]>[->]
+<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+
+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->
+-]>[->]<<>>>>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-
+]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[->]<<>+<[->-]>[-
+>]<<>+<[->-]>[->]<<<<<[->>>-->>>>>+<<<<<<<<]>>>>>>>>[-<<<<<<<<+>
+>>>>>>>]<<+<<<<<<[->>>>>>->>+<<<<<<<<]>>>>>>>>[-<<<<<<<<+>>>>>>>
+>]<<<<<[->>>->>+<<<<<]>>>>>[-<<<<<+>>>>>]<<<<<[<<<<<<<<<<<+>>>>>
+>[-]>>>>>[-]]<<<[<<<<<<<<<[->>>+>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<
+]>>>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<<<<
+<<<<<<+++++++++++++++++++++++++++++++++++++++++++++++++.[-]+++++
++++++++++++++++++++++++++++++++++++++++++++<<[>>.<<-]>>[-]<<<[-]
+>>>>>>>[-]>>[-]]>>>>>>[<<<<<<<<<<<<<[->>>>>>>>>>>>>>+>+<<<<<<<<<
+<<<<<<]>>>>>>>>>>>>>>>[-<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>]<[<<<<<<
+<<<<<<<<<<[->>>+>>>>>>>>>>>>>>+<<<<<<<<<<<<<<<<<]>>>>>>>>>>>>>>>
+>>[-<<<<<<<<<<<<<<<<<+>>>>>>>>>>>>>>>>>]<<<<<<<<<<<<<<++++++++++
+++++++++++++++++++++++++++++++++++++++.[-]++++++++++++++++++++++
++++++++++++++++++++++++++++++++++++<<[>>.<<-]>>[-]>>>>>>>>>>>>>[
+-]]<<<<<<<<<<<<<<[-]+<<[-]>>>>>>>[-<<<<<<<+>>>>>>>]>>>>>>>>[-]]<
+<<<<<<<<<<<<<<<-]>[->>>+<<<]>>>+++++++++++++++++++++++++++++++++
++++++++++++++++.[-]++++++++++.
@@ -0,0 +1,33 @@
+Sierpinski triangle
+Prints 32 rows of the Pascal triangle taken modulo 2
+The cell in row r and column c is set when the bits of c are a subset of those of r
+
+++++++++++++++++++++++++++++++++[>>+++++++++++++++++++++++++++++
+++<[->->>>>>>>>+<<<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>>>>>>>>]>>>>>>>
+>>>++++++++++++++++++++++++++++++++<<<<<<<<<<<<<<<<<<[>>>>>>>>>>
+>>>>>>>>.<<<<<<<<<<<<<<<<<<-]<[->>>+>>>>>>+<<<<<<<<<]>>>>>>>>>[-
+<<<<<<<<<+>>>>>>>>>]<<<<<<+[<[->>+>>>>>+<<<<<<<]>>>>>>>[-<<<<<<<
++>>>>>>>]<<<<<<<<<[->>>>>+>>>>+<<<<<<<<<]>>>>>>>>>[-<<<<<<<<<+>>
+>>>>>>>]>>++<<<<<<<[->>>>>>>->+<[>-]>[-<++>>>+<<>]<<<<<<<<<]>>++
+>>>>>[-<<<<<->>>>>]++<<<<<<[->>>>>>->+<[>-]>[-<++>>>>+<<<>]<<<<<
+<<<]>>++>>>>[-<<<<->>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>[-<<<<<<<<<<
++>>>>>>>>>>]<<<<<<<+<[->-<]<[>>[->>>>>>>>+<<<<<<<+<]>[-<+>]<<<[-
+]]>>[-]>>>++<<<<<<<[->>>>>>>->+<[>-]>[-<++>>>+<<>]<<<<<<<<<]>>++
+>>>>>[-<<<<<->>>>>]++<<<<<<[->>>>>>->+<[>-]>[-<++>>>>+<<<>]<<<<<
+<<<]>>++>>>>[-<<<<->>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>[-<<<<<<<<<<
++>>>>>>>>>>]<<<<<<<+<[->-<]<[>>[->>>>>>>>+<<<<<<<+<]>[-<+>]<<<[-
+]]>>[-]>>>++<<<<<<<[->>>>>>>->+<[>-]>[-<++>>>+<<>]<<<<<<<<<]>>++
+>>>>>[-<<<<<->>>>>]++<<<<<<[->>>>>>->+<[>-]>[-<++>>>>+<<<>]<<<<<
+<<<]>>++>>>>[-<<<<->>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>[-<<<<<<<<<<
++>>>>>>>>>>]<<<<<<<+<[->-<]<[>>[->>>>>>>>+<<<<<<<+<]>[-<+>]<<<[-
+]]>>[-]>>>++<<<<<<<[->>>>>>>->+<[>-]>[-<++>>>+<<>]<<<<<<<<<]>>++
+>>>>>[-<<<<<->>>>>]++<<<<<<[->>>>>>->+<[>-]>[-<++>>>>+<<<>]<<<<<
+<<<]>>++>>>>[-<<<<->>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>[-<<<<<<<<<<
++>>>>>>>>>>]<<<<<<<+<[->-<]<[>>[->>>>>>>>+<<<<<<<+<]>[-<+>]<<<[-
+]]>>[-]>>>++<<<<<<<[->>>>>>>->+<[>-]>[-<++>>>+<<>]<<<<<<<<<]>>++
+>>>>>[-<<<<<->>>>>]++<<<<<<[->>>>>>->+<[>-]>[-<++>>>>+<<<>]<<<<<
+<<<]>>++>>>>[-<<<<->>>>]>>>[-<<<<<<<<<<+>>>>>>>>>>]>[-<<<<<<<<<<
++>>>>>>>>>>]<<<<<<<+<[->-<]<[>>[->>>>>>>>+<<<<<<<+<]>[-<+>]<<<[-
+]]>>[-]<<<<[-]>[-]>>>>>>>>>>>>+<[[-]>-]>[->>++++++++++<<>]<<>>>.
+[-]++++++++++++++++++++++++++++++++.<<<<<<<<<<<<<<<<<+>-]<[-]>>>
+>>>>>>>>>>>>>>[-]++++++++++.[-]<<<<<<<<<<<<<<<<<<<+<-]
@@ -0,0 +1,43 @@
+/* C interface of the braincooker library - built with `cargo build --release --features ffi` */
+#ifndef BRAINCOOKER_H
+#define BRAINCOOKER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    BC_OK = 0,
+    BC_NULL_POINTER = 1,
+    BC_INVALID_UTF8 = 2,
+    BC_PARSE = 3,
+    BC_BUILD = 4,
+    BC_RUN = 5,
+    BC_OUTPUT = 6
+    } BcStatus;
+
+typedef struct BcProgram BcProgram;
+
+/* Callback getting the written bytes - the data is valid only during the call */
+typedef void (*BcOutput)(const uint8_t *data, size_t len, void *user);
+
+/* Parse the null-terminated source code - the program has to be released with `bc_free` */
+BcStatus bc_eval(const char *code, BcProgram **program);
+
+/* Run the program on the given input bytes - the output callback may be null */
+BcStatus bc_run(const BcProgram *program, const uint8_t *input, size_t input_len, BcOutput output, void *user);
+
+/* Release the program - null is ignored */
+void bc_free(BcProgram *program);
+
+/* Get the static description of the status */
+const char *bc_status_message(BcStatus status);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
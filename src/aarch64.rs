@@ -0,0 +1,575 @@
+use crate::{
+    codegen::*,
+    elf,
+    eval::*,
+    ir::*,
+    macho,
+    utils::*
+    };
+
+
+/* Registers with a fixed purpose - tape's address, pointer, output buffer's address, and its length */
+const TAPE: u32 = 19;
+const INDEX: u32 = 20;
+const BUFFER: u32 = 21;
+const LENGTH: u32 = 22;
+/* Scratch registers, for the cell's value, constants, products, and the index of a cell at an offset */
+const VALUE: u32 = 9;
+const CONSTANT: u32 = 10;
+const PRODUCT: u32 = 11;
+const OFFSET: u32 = 12;
+
+/* Condition codes */
+const EQ: u32 = 0;
+const NE: u32 = 1;
+const HS: u32 = 2;
+const LO: u32 = 3;
+const PL: u32 = 5;
+const HI: u32 = 8;
+
+/* Branch opcodes, without the offset */
+const B: u32 = 0x14000000;
+const BL: u32 = 0x94000000;
+
+
+/* Helper container for assembling machine code - every instruction is a single 32-bit word */
+struct Asm {
+    code: Vec<u8>,
+    options: CodegenOptions,
+    layout: Layout,
+    /* Register holding the addressed cell's index */
+    index: u32
+    }
+
+impl Asm {
+    /* Get the current offset */
+    const fn pos(&self) -> usize {
+        self.code.len()
+        }
+
+    fn emit(&mut self, words: &[u32]) {
+        for word in words {
+            self.code.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+
+    /* Get the offset to the target, in instructions */
+    const fn offset(from: usize, target: usize) -> u32 {
+        ((target as i64 - from as i64) / 4) as u32
+        }
+
+    /* Emit an unconditional branch, or a call to the target */
+    fn branch(&mut self, opcode: u32, target: usize) {
+        let offset = Self::offset(self.pos(), target);
+        self.emit(&[opcode | offset & 0x3FFFFFF]);
+        }
+
+    /* Emit a conditional branch to the target */
+    fn branch_if(&mut self, cond: u32, target: usize) {
+        let offset = Self::offset(self.pos(), target);
+        self.emit(&[0x54000000 | (offset & 0x7FFFF) << 5 | cond]);
+        }
+
+    /* Emit a branch with a placeholder offset, returns its location */
+    fn forward(&mut self, word: u32) -> usize {
+        self.emit(&[word]);
+        self.pos() - 4
+        }
+
+    /* Patch a branch, emitted earlier, to point at the target */
+    fn patch(&mut self, at: usize, target: usize) {
+        let offset = Self::offset(at, target);
+        let mut word = u32::from_le_bytes([self.code[at], self.code[at + 1], self.code[at + 2], self.code[at + 3]]);
+
+        /* Unconditional branches have a 26-bit offset, the others a 19-bit one */
+        word |= match word & 0x7C000000 == B {
+            true => offset & 0x3FFFFFF,
+            false => (offset & 0x7FFFF) << 5
+            };
+
+        self.code[at .. at + 4].copy_from_slice(&word.to_le_bytes());
+        }
+
+    /* Load a 32-bit constant into a register - `movz`, and `movk` for the upper half */
+    fn move_constant(&mut self, reg: u32, value: u32) {
+        self.emit(&[0x52800000 | (value & 0xFFFF) << 5 | reg]);
+        if value >> 16 != 0 {
+            self.emit(&[0x72A00000 | (value >> 16) << 5 | reg]);
+            }
+        }
+
+    /* Load an address in the zero-initialised memory - `adrp` for its page, and `add` for the rest */
+    fn address_bss(&mut self, reg: u32, offset: u64) {
+        let target = self.layout.bss_address + offset;
+        let here = self.layout.code_address + self.pos() as u64;
+        let pages = ((target >> 12) as i64 - (here >> 12) as i64) as u32;
+
+        self.emit(&[
+            0x90000000 | (pages & 0b11) << 29 | (pages >> 2 & 0x7FFFF) << 5 | reg,
+            0x91000000 | (target as u32 & 0xFFF) << 10 | reg << 5 | reg
+            ]);
+        }
+
+    /* Emit a system call, with its arguments already in place */
+    fn syscall(&mut self, call: Syscall) {
+        match self.layout.platform {
+            /* `mov x8, number`, and `svc #0` */
+            Platform::Linux => {
+                let number = match call {
+                    Syscall::Read => 63,
+                    Syscall::Write => 64,
+                    Syscall::Exit => 93
+                    };
+                self.emit(&[0xD2800008 | number << 5, 0xD4000001]);
+                },
+            /* `mov x16, number`, and `svc #0x80` */
+            Platform::MacOs =>
+                self.emit(&[0xD2800010 | call.bsd_number() << 5, 0xD4001001])
+            }
+        }
+
+    /* Add a value, already truncated to the given width, to a 32-bit register */
+    fn add_constant(&mut self, reg: u32, value: u32, bits: u32) {
+        let negated = match bits {
+            u32::BITS => value.wrapping_neg(),
+            _ => (1 << bits) - value
+            };
+
+        match (value < 0x1000, negated < 0x1000) {
+            /* `add`, or `sub` with an immediate */
+            (true, _) => self.emit(&[0x11000000 | value << 10 | reg << 5 | reg]),
+            (false, true) => self.emit(&[0x51000000 | negated << 10 | reg << 5 | reg]),
+            /* `add` with a constant loaded into a register */
+            (false, false) => {
+                self.move_constant(CONSTANT, value);
+                self.emit(&[0x0B000000 | CONSTANT << 16 | reg << 5 | reg]);
+                }
+            }
+        }
+
+    /* Emit the operation on the cell at an offset from the pointer - its index is computed into w12 */
+    fn at(&mut self, offset: i64, operation: impl FnOnce(&mut Self)) {
+        if offset != 0 {
+            self.emit(&[0x2A0003E0 | INDEX << 16 | OFFSET]);    /* mov w12, w20 */
+            self.advance(OFFSET, offset);
+            self.index = OFFSET;
+            }
+
+        operation(self);
+        self.index = INDEX;
+        }
+
+    /* Load the addressed cell into w9, zero-extended - `[x19 + x20 * size]` */
+    fn load_cell(&mut self) {
+        let opcode = match self.options.cell_bytes() {
+            1 => 0x38606800,
+            2 => 0x78607800,
+            _ => 0xB8607800
+            };
+        self.emit(&[opcode | self.index << 16 | TAPE << 5 | VALUE]);
+        }
+
+    /* Store w9 into the addressed cell */
+    fn store_cell(&mut self) {
+        let opcode = match self.options.cell_bytes() {
+            1 => 0x38206800,
+            2 => 0x78207800,
+            _ => 0xB8207800
+            };
+        self.emit(&[opcode | self.index << 16 | TAPE << 5 | VALUE]);
+        }
+
+    /* Set the current cell to a value, truncated to the cell's width */
+    fn set_cell(&mut self, value: u64) {
+        self.move_constant(VALUE, (value & self.options.cell_mask()) as u32);
+        self.store_cell();
+        }
+
+    /* Add the current cell times a factor to the cell at an offset - the pointer is moved there, and back */
+    fn mul_add(&mut self, offset: i64, factor: i16) {
+        /* Bits are flipped by odd factors only, and the cell is the product itself */
+        let (skip, combine, factor) = match self.options.cell_size {
+            CellSize::U1 => (factor % 2 == 0, 0x4A000000, 1),   /* eor */
+            _ => (factor == 0, 0x0B000000, factor)              /* add */
+            };
+        if skip {
+            return;
+            }
+
+        self.load_cell();
+        match factor {
+            1 => self.emit(&[0x2A0003E0 | VALUE << 16 | PRODUCT]),    /* mov w11, w9 */
+            _ => {
+                self.move_constant(CONSTANT, i32::from(factor) as u32);
+                self.emit(&[0x1B007C00 | CONSTANT << 16 | VALUE << 5 | PRODUCT]);  /* mul w11, w9, w10 */
+                }
+            }
+        self.move_pointer(offset);
+        self.load_cell();
+        self.emit(&[combine | PRODUCT << 16 | VALUE << 5 | VALUE]);
+        self.store_cell();
+        self.move_pointer(-offset);
+        }
+
+    /* Add a value to the current cell - wrapping, as it's done in the cell's width */
+    fn add_cell(&mut self, value: i64) {
+        let (value, bits) = match self.options.cell_size {
+            /* Adding an odd value to a single bit flips it */
+            CellSize::U1 => {
+                if value % 2 != 0 {
+                    self.load_cell();
+                    self.emit(&[0x52000129]);       /* eor w9, w9, #1 */
+                    self.store_cell();
+                    }
+                return;
+                },
+            CellSize::U8 => (value as u8 as u32, u8::BITS),
+            CellSize::U16 => (value as u16 as u32, u16::BITS),
+            CellSize::U32 => (value as u32, u32::BITS),
+            /* 64-bit, arbitrary-precision, and signed cells were rejected while validating */
+            CellSize::U64 | CellSize::Big | CellSize::I8 | CellSize::I16 | CellSize::I32 => return
+            };
+
+        if value != 0 {
+            self.load_cell();
+            self.add_constant(VALUE, value, bits);
+            self.store_cell();
+            }
+        }
+
+    /* Move the pointer - wrapping, as it's done in the pointer's width */
+    fn move_pointer(&mut self, value: i64) {
+        self.advance(INDEX, value);
+        }
+
+    /* Add a distance to an index register - wrapping, as it's done in the pointer's width */
+    fn advance(&mut self, reg: u32, value: i64) {
+        match self.options.pointer_size {
+            DataSize::U8 => if value as u8 != 0 {
+                self.add_constant(reg, value as u8 as u32, u8::BITS);
+                self.emit(&[0x53001C00 | reg << 5 | reg]);     /* uxtb */
+                },
+            DataSize::U16 => if value as u16 != 0 {
+                self.add_constant(reg, value as u16 as u32, u16::BITS);
+                self.emit(&[0x53003C00 | reg << 5 | reg]);     /* uxth */
+                },
+            DataSize::U32 => if value as u32 != 0 {
+                self.add_constant(reg, value as u32, u32::BITS);
+                },
+            /* 64-bit pointers were rejected while validating */
+            DataSize::U64 => ()
+            }
+        }
+    }
+
+
+/* Addresses of the runtime routines */
+struct Runtime {
+    flush: usize,
+    put_byte: usize,
+    get_byte: usize,
+    put_value: usize
+    }
+
+/* Function for emitting the runtime routines - x22 holds the buffered output's length, and x21 the buffer's address */
+fn emit_runtime(asm: &mut Asm) -> Runtime {
+    /* Write out the buffered output - `write(1, x21, x22)` */
+    let flush = asm.pos();
+    asm.emit(&[
+        0xB40000F6,                     /* cbz x22, .ret */
+        0xD2800020,                     /* mov x0, #1 */
+        0xAA1503E1,                     /* mov x1, x21 */
+        0xAA1603E2                      /* mov x2, x22 */
+        ]);
+    asm.syscall(Syscall::Write);
+    asm.emit(&[
+        0xD2800016,                     /* mov x22, #0 */
+        0xD65F03C0                      /* .ret: ret */
+        ]);
+
+    /* Buffer the byte in w0, and flush once the buffer gets full - the buffer's size is a multiple of 4 KiB */
+    let put_byte = asm.pos();
+    asm.emit(&[
+        0x38206800 | LENGTH << 16 | BUFFER << 5,            /* strb w0, [x21, x22] */
+        0x91000400 | LENGTH << 5 | LENGTH,                  /* add x22, x22, #1 */
+        0xF140001F | (BUFFER_SIZE as u32 >> 12) << 10 | LENGTH << 5     /* cmp x22, BUFFER_SIZE */
+        ]);
+    asm.branch_if(EQ, flush);           /* b.eq flush */
+    asm.emit(&[0xD65F03C0]);            /* ret */
+
+    /* Exit with a failure, keeping the output written so far - `exit(1)` */
+    let exit_failure = asm.pos();
+    asm.branch(BL, flush);
+    asm.emit(&[0xD2800020]);            /* mov x0, #1 */
+    asm.syscall(Syscall::Exit);
+
+    /* Read a single byte into w0 - `read(0, sp, 1)`, the end of input is a failure */
+    let get_byte = asm.pos();
+    asm.emit(&[0xA9BF7BFD]);            /* stp x29, x30, [sp, #-16]! */
+    asm.branch(BL, flush);
+    asm.emit(&[
+        0xD10043FF,                     /* sub sp, sp, #16 */
+        0xD2800000,                     /* mov x0, #0 */
+        0x910003E1,                     /* mov x1, sp */
+        0xD2800022                      /* mov x2, #1 */
+        ]);
+    asm.syscall(Syscall::Read);
+    asm.emit(&[0xF100041F]);            /* cmp x0, #1 */
+    asm.branch_if(NE, exit_failure);    /* b.ne exit_failure */
+    asm.emit(&[
+        0x394003E0,                     /* ldrb w0, [sp] */
+        0x910043FF,                     /* add sp, sp, #16 */
+        0xA8C17BFD,                     /* ldp x29, x30, [sp], #16 */
+        0xD65F03C0                      /* ret */
+        ]);
+
+    /* Write the value in w0, according to the display mode */
+    let put_value = asm.pos();
+    match asm.options.display_mode {
+        DisplayMode::ASCII => {
+            /* Printable characters are written as they are - tab, line feed, and graphic characters */
+            asm.emit(&[0x7100001F | (b'~' as u32) << 10]);    /* cmp w0, '~' */
+            let above_tilde = asm.forward(0x54000000 | HI);     /* b.hi .hex */
+            asm.emit(&[0x7100001F | (b' ' as u32) << 10]);    /* cmp w0, ' ' */
+            let graphic = asm.forward(0x54000000 | HS);         /* b.hs .byte */
+            asm.emit(&[0x7100001F | (b'\t' as u32) << 10]);   /* cmp w0, '\t' */
+            let below_tab = asm.forward(0x54000000 | LO);       /* b.lo .hex */
+            asm.emit(&[0x7100001F | (b'\n' as u32) << 10]);   /* cmp w0, '\n' */
+            let above_feed = asm.forward(0x54000000 | HI);      /* b.hi .hex */
+
+            let byte = asm.pos();
+            asm.patch(graphic, byte);
+            asm.branch(B, put_byte);            /* .byte: b put_byte */
+
+            /* Fallback - `0x` prefixed, zero padded, uppercase hexadecimal */
+            let hex = asm.pos();
+            for at in [above_tilde, below_tab, above_feed] {
+                asm.patch(at, hex);
+                }
+
+            asm.emit(&[
+                0xA9BF7BFD,                     /* .hex: stp x29, x30, [sp, #-16]! */
+                0x2A0003EA,                     /* mov w10, w0 */
+                0x52800000 | (b'0' as u32) << 5 /* mov w0, '0' */
+                ]);
+            asm.branch(BL, put_byte);
+            asm.emit(&[0x52800000 | (b'x' as u32) << 5]);   /* mov w0, 'x' */
+            asm.branch(BL, put_byte);
+
+            /* Shift of the most significant digit */
+            let shift = (asm.options.cell_bytes() * 2 - 1) * 4;
+            asm.move_constant(11, shift as u32);    /* mov w11, shift */
+
+            let digit = asm.pos();
+            asm.emit(&[
+                0x1ACB2540,                     /* .digit: lsr w0, w10, w11 */
+                0x12000C00,                     /* and w0, w0, #15 */
+                0x7100281F,                     /* cmp w0, #10 */
+                0x54000043,                     /* b.lo .decimal */
+                0x11001C00,                     /* add w0, w0, 'A' - '0' - 10 */
+                0x11000000 | (b'0' as u32) << 10    /* .decimal: add w0, w0, '0' */
+                ]);
+            asm.branch(BL, put_byte);
+            asm.emit(&[0x7100116B]);            /* subs w11, w11, #4 */
+            asm.branch_if(PL, digit);           /* b.pl .digit */
+            asm.emit(&[
+                0xA8C17BFD,                     /* ldp x29, x30, [sp], #16 */
+                0xD65F03C0                      /* ret */
+                ]);
+            },
+        DisplayMode::Numeric | DisplayMode::Hex | DisplayMode::Bin | DisplayMode::Oct => {
+            /* Push the digits in the display mode's base from the least significant, then pop them in order */
+            let base = asm.options.display_mode.radix();
+            asm.emit(&[
+                0xA9BF7BFD,                     /* stp x29, x30, [sp, #-16]! */
+                0x2A0003EA,                     /* mov w10, w0 */
+                0x5280000C | base << 5,         /* mov w12, base */
+                0x5280000D                      /* mov w13, #0 */
+                ]);
+
+            let divide = asm.pos();
+            asm.emit(&[
+                0x1ACC094B,                     /* .divide: udiv w11, w10, w12 */
+                0x1B0CA96E                      /* msub w14, w11, w12, w10 */
+                ]);
+            if base > 10 {
+                asm.emit(&[
+                    0x710029DF,                 /* cmp w14, #10 */
+                    0x54000043,                 /* b.lo .decimal */
+                    0x11001DCE                  /* add w14, w14, 'A' - '0' - 10 */
+                    ]);
+                }
+            asm.emit(&[
+                0x1100C1CE,                     /* .decimal: add w14, w14, '0' */
+                0xF81F0FEE,                     /* str x14, [sp, #-16]! */
+                0x110005AD,                     /* add w13, w13, #1 */
+                0x2A0B03EA                      /* mov w10, w11 */
+                ]);
+            let offset = Asm::offset(asm.pos(), divide);
+            asm.emit(&[0x35000000 | (offset & 0x7FFFF) << 5 | 10]);   /* cbnz w10, .divide */
+
+            let digit = asm.pos();
+            asm.emit(&[0xF84107E0]);            /* .digit: ldr x0, [sp], #16 */
+            asm.branch(BL, put_byte);
+            asm.emit(&[0x710005AD]);            /* subs w13, w13, #1 */
+            asm.branch_if(NE, digit);           /* b.ne .digit */
+            asm.emit(&[
+                0xA8C17BFD,                     /* ldp x29, x30, [sp], #16 */
+                0xD65F03C0                      /* ret */
+                ]);
+            },
+        DisplayMode::Raw =>
+            /* The low byte is written as it is */
+            asm.branch(B, put_byte),            /* b put_byte */
+        /* Unicode display was rejected while validating */
+        DisplayMode::Unicode => ()
+        }
+
+    Runtime { flush, put_byte, get_byte, put_value }
+    }
+
+/* Function for compiling the instructions into a static AArch64 Linux executable */
+pub fn compile_aarch64(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    let (code, entry) = generate(instr, options, elf::LAYOUT)?;
+
+    Ok(elf::executable(elf::MACHINE_AARCH64, &code, entry as u64, options.bss_size()))
+    }
+
+/* Function for compiling the instructions into an arm64 macOS executable, signed ad-hoc */
+pub fn compile_aarch64_macho(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    let (code, entry) = generate(instr, options, macho::LAYOUT)?;
+
+    Ok(macho::executable(macho::CPU_ARM64, &code, entry as u64, options.bss_size()))
+    }
+
+/* Function for generating the machine code, placed according to the layout - returns the code, and the entry point's offset */
+fn generate(instr: &InstructionSet, options: CodegenOptions, layout: Layout) -> Result<(Vec<u8>, usize), CompileError> {
+    options.validate_native()?;
+
+    let ir = lower_checked(instr, options.opt_level)?;
+    let mut asm = Asm {
+        code: Vec::with_capacity(256 + 16 * ir.len()),
+        options,
+        layout,
+        index: INDEX
+        };
+
+    let runtime = emit_runtime(&mut asm);
+
+    /* Entry point - the tape comes right after the output buffer */
+    let entry = asm.pos();
+    asm.address_bss(TAPE, BUFFER_SIZE);
+    asm.address_bss(BUFFER, 0);
+    asm.emit(&[
+        0x52800014,                     /* mov w20, #0 */
+        0xD2800016                      /* mov x22, #0 */
+        ]);
+
+    /* Locations of the open loops' forward branches */
+    let mut loop_stack = Vec::new();
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) =>
+                asm.add_cell(value.into()),
+            IrOp::Move(value) =>
+                asm.move_pointer(value as i64),
+            IrOp::Set(value) =>
+                asm.set_cell(value),
+            IrOp::MulAdd(offset, factor) =>
+                asm.mul_add(offset as i64, factor),
+            IrOp::AddAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.add_cell(value.into())),
+            IrOp::SetAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.set_cell(value)),
+            IrOp::Output => {
+                asm.load_cell();
+                asm.emit(&[0x2A0903E0]);    /* mov w0, w9 */
+                asm.branch(BL, runtime.put_value);
+                },
+            IrOp::Input => {
+                asm.branch(BL, runtime.get_byte);
+                /* Only the lowest bit is kept for 1-bit cells */
+                match asm.options.cell_size {
+                    CellSize::U1 => asm.emit(&[0x12000009]),   /* and w9, w0, #1 */
+                    _ => asm.emit(&[0x2A0003E9])                /* mov w9, w0 */
+                    };
+                asm.store_cell();
+                },
+            /* Branch past the loop's end, when the cell is zero - `b` has a longer range than `cbz` */
+            IrOp::LoopStart(_) => {
+                asm.load_cell();
+                asm.emit(&[0x35000049]);    /* cbnz w9, +8 */
+                loop_stack.push(asm.forward(B));
+                },
+            /* Branch back to the loop's body, when the cell is non-zero */
+            IrOp::LoopEnd(_) => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some(open) = loop_stack.pop() else {
+                    continue;
+                    };
+
+                asm.load_cell();
+                asm.emit(&[0x34000049]);    /* cbz w9, +8 */
+                asm.branch(B, open + 4);
+
+                let end = asm.pos();
+                asm.patch(open, end);
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run, then `exit(0)` - raw output stays byte-exact */
+    if options.display_mode != DisplayMode::Raw {
+        asm.emit(&[0x52800000 | (b'\n' as u32) << 5]); /* mov w0, '\n' */
+        asm.branch(BL, runtime.put_byte);
+        }
+    asm.branch(BL, runtime.flush);
+    asm.emit(&[0xD2800000]);            /* mov x0, #0 */
+    asm.syscall(Syscall::Exit);
+
+    Ok((asm.code, entry))
+    }
+
+#[cfg(test)]
+mod test {
+    use crate::aarch64::*;
+
+    #[test]
+    fn compile_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let binary = compile_aarch64(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&binary[.. 4], b"\x7FELF");
+        assert_eq!(u16::from_le_bytes([binary[18], binary[19]]), elf::MACHINE_AARCH64);
+        }
+
+    #[test]
+    fn compile_macho_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let binary = compile_aarch64_macho(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&binary[.. 4], &0xFEEDFACF_u32.to_le_bytes());
+        assert_eq!(u32::from_le_bytes([binary[4], binary[5], binary[6], binary[7]]), macho::CPU_ARM64.0);
+        }
+
+    #[test]
+    fn compile_err_ascii_bit_cells() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            cell_size: CellSize::U1,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_aarch64(&instr, options), Err(CompileError::AsciiBitCells));
+        }
+    }
\ No newline at end of file
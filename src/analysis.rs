@@ -0,0 +1,350 @@
+use {
+    std::collections::BTreeMap,
+    core::fmt::{
+        Display,
+        Formatter,
+        Result as FmtResult
+        },
+    crate::eval::*
+    };
+
+
+/* Net pointer movement of a loop's single iteration */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Balance {
+    /* Pointer ends where it started */
+    Balanced,
+    /* Pointer moves by the number of cells */
+    Unbalanced(isize),
+    /* Movement depends on the number of iterations of an unbalanced inner loop */
+    Unknown
+    }
+
+/* Analysis of a single loop - locations are instruction indices */
+#[derive(Clone, PartialEq, Debug)]
+pub struct LoopInfo {
+    pub start: usize,
+    pub end: usize,
+    pub balance: Balance,
+    /* Net changes of the cells by their offsets from the iteration's start, without the unchanged ones - none, when they depend on the inner loops, or the input */
+    pub deltas: Option<Vec<(isize, i64)>>
+    }
+
+impl LoopInfo {
+    /* Get whether the pointer ends every iteration where it started */
+    #[inline]
+    pub const fn is_balanced(&self) -> bool {
+        matches!(self.balance, Balance::Balanced)
+        }
+    }
+
+/* Every instruction, in the order they are counted in */
+const INSTRUCTIONS: [Instruction; 18] = [
+    Instruction::Right,
+    Instruction::Left,
+    Instruction::Increment,
+    Instruction::Decrement,
+    Instruction::LoopOpen,
+    Instruction::LoopClose,
+    Instruction::Output,
+    Instruction::Input,
+    Instruction::Random,
+    Instruction::Dump,
+    Instruction::ProcStart,
+    Instruction::ProcEnd,
+    Instruction::ProcCall,
+    Instruction::Breakpoint,
+    Instruction::ShowPointer,
+    Instruction::ShowTape,
+    Instruction::TapeUp,
+    Instruction::TapeDown
+    ];
+
+/* Static statistics of the instructions, gathered without running them */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stats {
+    /* Number of every instruction */
+    pub counts: Vec<(Instruction, usize)>,
+    pub loops: usize,
+    pub max_depth: usize,
+    /* Loops, which move the pointer by a known number of cells, or an unknown one, are both unbalanced */
+    pub balanced: usize,
+    pub unbalanced: usize,
+    /* Farthest cells the pointer reaches, to the left, and to the right of the starting one - none, when an unbalanced loop, or a procedure call makes it unknown */
+    pub excursion: Option<(isize, isize)>
+    }
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        writeln!(f, "Instructions:")?;
+        for &(inst, count) in &self.counts {
+            writeln!(f, "  '{}'  {count}", inst.symbol())?;
+            }
+
+        writeln!(f, "Loops: {} ({} balanced, {} unbalanced), nested at most {} deep", self.loops, self.balanced, self.unbalanced, self.max_depth)?;
+
+        match self.excursion {
+            Some((left, right)) => writeln!(f, "Pointer stays between cells {left}, and {right}"),
+            None => writeln!(f, "Pointer's range is unknown, as it depends on the unbalanced loops, or the procedure calls")
+            }
+        }
+    }
+
+impl Stats {
+    /* Get the statistics as a JSON object */
+    pub fn to_json(&self) -> String {
+        let counts = self.counts.iter()
+            .map(|&(inst, count)| format!("\"{}\":{count}", inst.symbol()))
+            .collect::<Vec<_>>()
+            .join(",");
+        let excursion = self.excursion
+            .map_or_else(|| "null".to_owned(), |(left, right)| format!("[{left},{right}]"));
+
+        format!("{{\"counts\":{{{counts}}},\"loops\":{},\"max_depth\":{},\"balanced\":{},\"unbalanced\":{},\"excursion\":{excursion}}}", self.loops, self.max_depth, self.balanced, self.unbalanced)
+        }
+    }
+
+/* Open loop's state during the analysis */
+struct Frame {
+    start: usize,
+    /* Offset from the iteration's start - none, once it can't be known */
+    offset: Option<isize>,
+    changes: BTreeMap<isize, i64>,
+    /* Whether the changes are the whole effect on the cells */
+    exact: bool
+    }
+
+impl Frame {
+    /* Constructor function - at the loop's start */
+    const fn new(start: usize) -> Self {
+        Self {
+            start,
+            offset: Some(0),
+            changes: BTreeMap::new(),
+            exact: true
+            }
+        }
+
+    /* Follow the instruction of the loop's own body */
+    fn step(&mut self, inst: Instruction) {
+        match inst {
+            Instruction::Right => self.shift(1),
+            Instruction::Left => self.shift(-1),
+            Instruction::Increment => self.add(1),
+            Instruction::Decrement => self.add(-1),
+            /* Input, and random values overwrite the cell */
+            Instruction::Input | Instruction::Random =>
+                self.exact = false,
+            /* Called procedure can do anything, and the other tape has its own pointer */
+            Instruction::ProcCall | Instruction::TapeUp | Instruction::TapeDown => {
+                self.offset = None;
+                self.exact = false;
+                },
+            _ => ()
+            }
+        }
+
+    fn shift(&mut self, step: isize) {
+        if let Some(offset) = &mut self.offset {
+            *offset += step;
+            }
+        }
+
+    fn add(&mut self, step: i64) {
+        match self.offset {
+            Some(offset) =>
+                *self.changes.entry(offset).or_default() += step,
+            None =>
+                self.exact = false
+            }
+        }
+    }
+
+impl InstructionSet {
+    /* Function for classifying every loop by its pointer movement, with its cells' net changes - ordered by the loops' starts */
+    pub fn analyze_loops(&self) -> Vec<LoopInfo> {
+        let mut output = Vec::new();
+        let mut loop_stack: Vec<Frame> = Vec::new();
+
+        for i in 0 .. self.len() {
+            match self[i] {
+                Instruction::LoopOpen =>
+                    loop_stack.push(Frame::new(i)),
+                Instruction::LoopClose => {
+                    /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                    let Some(Frame { start, offset, changes, exact }) = loop_stack.pop() else {
+                        continue;
+                        };
+
+                    let balance = match offset {
+                        Some(0) => Balance::Balanced,
+                        Some(drift) => Balance::Unbalanced(drift),
+                        None => Balance::Unknown
+                        };
+
+                    /* Inner loops run an unknown number of times, and only balanced ones keep the outer movement known */
+                    if let Some(outer) = loop_stack.last_mut() {
+                        outer.exact = false;
+                        if balance != Balance::Balanced {
+                            outer.offset = None;
+                            }
+                        }
+
+                    let deltas = exact.then(|| changes.into_iter()
+                        .filter(|&(_, change)| change != 0)
+                        .collect());
+
+                    output.push(LoopInfo { start, end: i, balance, deltas });
+                    },
+                inst => if let Some(frame) = loop_stack.last_mut() {
+                    frame.step(inst);
+                    }
+                }
+            }
+
+        /* Inner loops end first */
+        output.sort_by_key(|info| info.start);
+
+        output
+        }
+
+    /* Function for gathering the static statistics - the pointer's range is followed through the balanced loops' bodies, as they end where they started */
+    pub fn stats(&self) -> Stats {
+        let loops = self.analyze_loops();
+
+        let counts = INSTRUCTIONS.iter()
+            .map(|&inst| (inst, self.iter().filter(|&&other| other == inst).count()))
+            .collect();
+
+        let mut depth: usize = 0;
+        let mut max_depth = 0;
+        let mut offset: isize = 0;
+        let mut excursion = Some((0, 0));
+
+        for (i, &inst) in self.iter().enumerate() {
+            match inst {
+                Instruction::LoopOpen => {
+                    depth += 1;
+                    max_depth = max_depth.max(depth);
+
+                    /* Loops are ordered by their starts */
+                    let balanced = loops.binary_search_by_key(&i, |info| info.start)
+                        .is_ok_and(|index| loops[index].is_balanced());
+                    if ! balanced {
+                        excursion = None;
+                        }
+                    },
+                Instruction::LoopClose =>
+                    depth -= 1,
+                Instruction::Right =>
+                    offset += 1,
+                Instruction::Left =>
+                    offset -= 1,
+                Instruction::ProcCall | Instruction::TapeUp | Instruction::TapeDown =>
+                    excursion = None,
+                _ => ()
+                }
+
+            if let Some((left, right)) = &mut excursion {
+                *left = offset.min(*left);
+                *right = offset.max(*right);
+                }
+            }
+
+        let balanced = loops.iter()
+            .filter(|info| info.is_balanced())
+            .count();
+
+        Stats {
+            counts,
+            loops: loops.len(),
+            max_depth,
+            balanced,
+            unbalanced: loops.len() - balanced,
+            excursion
+            }
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        analysis::*,
+        utils::Dialect
+        };
+
+    #[test]
+    fn analyze_balanced() {
+        let instr = eval_instr("++[->+++<]")
+            .expect("Unreachable");
+
+        assert_eq!(instr.analyze_loops(), vec![
+            LoopInfo { start: 2, end: 9, balance: Balance::Balanced, deltas: Some(vec![(0, -1), (1, 3)]) }
+            ]);
+        }
+
+    #[test]
+    fn analyze_unbalanced() {
+        let instr = eval_instr("+[>+-]")
+            .expect("Unreachable");
+
+        assert_eq!(instr.analyze_loops(), vec![
+            LoopInfo { start: 1, end: 5, balance: Balance::Unbalanced(1), deltas: Some(vec![]) }
+            ]);
+        }
+
+    #[test]
+    fn analyze_nested() {
+        let instr = eval_instr("[<[<<]>[-]]")
+            .expect("Unreachable");
+        let loops = instr.analyze_loops();
+
+        assert_eq!(loops.iter().map(|info| info.balance).collect::<Vec<_>>(), vec![
+            Balance::Unknown,
+            Balance::Unbalanced(-2),
+            Balance::Balanced
+            ]);
+        assert_eq!(loops[0].deltas, None);
+        assert!(loops[2].is_balanced());
+        }
+
+    #[test]
+    fn stats_basic() {
+        let instr = eval_instr("++[->+[-<<]<]>>[>]")
+            .expect("Unreachable");
+        let stats = instr.stats();
+
+        assert_eq!(stats.counts[2], (Instruction::Increment, 3));
+        assert_eq!((stats.loops, stats.max_depth, stats.balanced, stats.unbalanced), (3, 2, 0, 3));
+        assert_eq!(stats.excursion, None);
+
+        let stats = eval_instr("<<[->>>+<<<]>")
+            .expect("Unreachable")
+            .stats();
+
+        assert_eq!(stats.excursion, Some((-2, 1)));
+        assert_eq!(stats.to_json(), r##"{"counts":{">":4,"<":5,"+":1,"-":1,"[":1,"]":1,".":0,",":0,"?":0,"#":0,"(":0,")":0,":":0,"!":0,"@":0,"$":0,"^":0,"v":0},"loops":1,"max_depth":1,"balanced":1,"unbalanced":0,"excursion":[-2,1]}"##);
+        }
+
+    #[test]
+    fn analyze_input() {
+        let instr = eval_instr("+[,>+<]")
+            .expect("Unreachable");
+        let loops = instr.analyze_loops();
+
+        assert!(loops[0].is_balanced());
+        assert_eq!(loops[0].deltas, None);
+        }
+
+    #[test]
+    fn analyze_procedures() {
+        let instr = eval_instr_with("(>)+[:-]", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        assert_eq!(instr.analyze_loops(), vec![
+            LoopInfo { start: 4, end: 7, balance: Balance::Unknown, deltas: None }
+            ]);
+        assert_eq!(instr.stats().excursion, None);
+        }
+    }
\ No newline at end of file
@@ -1,7 +1,34 @@
 use {
     clap::*,
-    std::path::PathBuf,
-    braincooker::DisplayMode
+    std::{
+        fs::{
+            read,
+            read_to_string
+            },
+        path::PathBuf,
+        time::Duration
+        },
+    braincooker::{
+        eval_instr_strict,
+        eval_ook,
+        eval_tokens,
+        CellOverflow,
+        CellSize,
+        CommentMode,
+        DataSize,
+        Dialect,
+        DisplayMode,
+        EofMode,
+        EvalError,
+        InstructionSet,
+        Lang,
+        OptLevel,
+        PointerOverflow,
+        SourceMap,
+        TapeKind,
+        TokenMap,
+        TokenMapError
+        }
     };
 
 
@@ -12,20 +39,48 @@ pub enum LoopPrune {
     All
     }
 
-/* Pointer, and cell size */
-#[derive(Clone, Copy, ValueEnum)]
-pub enum DataSize {
-    U8,
-    U16,
-    U32
+/* Target architectures of the compiler */
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Arch {
+    #[value(name = "x86_64", alias = "x86-64", alias = "amd64")]
+    X86_64,
+    #[value(alias = "aarch64")]
+    Arm64,
+    #[value(name = "riscv64")]
+    RiscV64
     }
 
-// #[derive(Clone, Copy, PartialEq)]
-// pub enum Arch {
-//     X86_64,
-//     Arm64,
-//     RiscV64,
-//     }
+/* Output formats of the compiler */
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Emit {
+    /* Native executable, for the target architecture */
+    Binary,
+    /* Native macOS executable, for the target architecture */
+    #[value(name = "macho")]
+    MachO,
+    /* C source code */
+    C,
+    /* Rust source code */
+    Rust,
+    /* WebAssembly module */
+    Wasm,
+    /* Optimised bytecode, run by the interpreter */
+    #[value(alias = "bfc")]
+    Bytecode
+    }
+
+/* Programs of the built-in benchmark corpus */
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Benchmark {
+    /* Greeting, done in a moment */
+    Hello,
+    /* Pascal's triangle modulo 2 */
+    Sierpinski,
+    /* Fixed point sketch of the Mandelbrot set, on 16-bit cells */
+    Mandelbrot,
+    /* Digits of pi from a spigot, on 16-bit cells */
+    Pi
+    }
 
 
 #[derive(Parser)]
@@ -41,19 +96,105 @@ pub struct Args {
 pub struct Inputs {
     /// Raw source code
     pub input: Option<String>,
-    /// Path to a file with source code
+    /// Path to a file with source code - the interpreter also runs `*.bfc` bytecode, with the sizes it was compiled for
     #[clap(short, long)]
     pub input_file: Option<PathBuf>,
     }
 
+#[derive(Args, Hash)]
+pub struct Formatting {
+    /// Written between the successive values of the numeric display modes - space, newline, comma, or a literal (accepts escapes)
+    #[clap(long, value_parser = parse_separator)]
+    pub numeric_separator: Option<ByteString>,
+    /// Written before every value of the numeric display modes (accepts escapes)
+    #[clap(long, value_parser = parse_escaped)]
+    pub numeric_prefix: Option<ByteString>,
+    /// Written after every value of the numeric display modes (accepts escapes)
+    #[clap(long, value_parser = parse_escaped)]
+    pub numeric_suffix: Option<ByteString>
+    }
+
+#[derive(Args)]
+pub struct Bounds {
+    /// Number of cells the pointer wraps at, like 30000 of the classic implementation [default: the whole range of the pointer]
+    #[clap(long, conflicts_with = "matrix")]
+    pub tape_size: Option<usize>,
+    /// Cell the pointer starts at, for programs launched in the middle of their memory - negative ones are only on the infinite tape
+    #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+    pub start_cell: isize,
+    /// Value every cell starts with, instead of zero
+    #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+    pub fill: i64,
+    /// Behaviour of the pointer moved past either end of the tape
+    #[clap(long, value_enum, default_value_t = PointerOverflow::Wrap)]
+    pub pointer_overflow: PointerOverflow,
+    /// Behaviour of the cell taken past its bounds
+    #[clap(long, value_enum, default_value_t = CellOverflow::Wrap)]
+    pub cell_overflow: CellOverflow,
+    /// Number of tapes switched between by the multi-tape dialect - runs on several ones can't be snapshotted, nor written into a tape image [default: 2 with the multi-tape dialect, 1 otherwise]
+    #[clap(long, conflicts_with_all = ["warm_start", "checkpoint_every", "restore", "tape_out"])]
+    pub tapes: Option<usize>
+    }
+
 #[derive(Args)]
 pub struct Settings {
     /// Whether to show progress informations
     #[clap(short = 'D', long, action)]
     pub debug_display: bool,
+    /// Whether to hide every log line, so only the program's output, and the final error get printed
+    #[clap(short, long, action, conflicts_with = "debug_display")]
+    pub quiet: bool,
     /// Whether to prune comment loops
     #[clap(short, long, value_enum)]
-    pub loop_prune: Option<LoopPrune>
+    pub loop_prune: Option<LoopPrune>,
+    /// Language of the source code
+    #[clap(long, value_enum, default_value_t = Lang::Brainfuck)]
+    pub lang: Lang,
+    /// Path to a file giving the instructions other tokens - one per line, with the instruction's symbol, and its token after the whitespace
+    #[clap(long, value_parser = parse_token_map, conflicts_with = "lang")]
+    pub token_map: Option<TokenMap>,
+    /// Language extensions to enable
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub dialect: Vec<Dialect>,
+    /// Whether every character outside the instructions is an error, instead of a comment - the REPL stays lenient
+    #[clap(long, action)]
+    pub strict: bool,
+    /// Whether the strict parsing still allows the whitespace
+    #[clap(long, action, requires = "strict")]
+    pub allow_whitespace: bool
+    }
+
+impl Settings {
+    /* Get the treatment of the characters outside the instructions */
+    pub const fn comment_mode(&self) -> CommentMode {
+        match (self.strict, self.allow_whitespace) {
+            (false, _) => CommentMode::Any,
+            (true, true) => CommentMode::Whitespace,
+            (true, false) => CommentMode::Forbidden
+            }
+        }
+
+    /* Function for evaluating the source code in the settings' language, or the token map - the extensions are only in the Brainfuck */
+    pub fn eval(&self, instr_str: &str) -> Result<(InstructionSet, SourceMap), EvalError> {
+        match (&self.token_map, self.lang) {
+            (Some(map), _) => eval_tokens(instr_str, map, self.comment_mode()),
+            (None, Lang::Brainfuck) => eval_instr_strict(instr_str, &self.dialect, self.comment_mode()),
+            (None, Lang::Ook) => eval_ook(instr_str, self.comment_mode())
+            }
+        }
+
+    /* Get the number of tapes - two by default, with the multi-tape dialect */
+    pub fn tape_count(&self, count: Option<usize>) -> usize {
+        count.unwrap_or(match self.dialect.contains(&Dialect::MultiTape) {
+            true => 2,
+            false => 1
+            })
+        }
+
+    /* Whether the source code is the plain Brainfuck */
+    pub const fn is_brainfuck(&self) -> bool {
+        self.token_map.is_none() && matches!(self.lang, Lang::Brainfuck)
+        }
     }
 
 #[derive(Subcommand)]
@@ -70,11 +211,330 @@ pub enum CMD {
         #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
         pointer_size: DataSize,
         /// Cell size
-        #[clap(short, long, value_enum, default_value_t = DataSize::U8)]
-        cell_size: DataSize,
-        /// Way of displaying value of a cell
-        #[clap(short, long, value_enum, default_value_t = DisplayMode::ASCII)]
-        display_mode: DisplayMode
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Size, starting state, and bounds of the tape
+        #[clap(flatten)]
+        bounds: Box<Bounds>,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Formatting of the numeric values
+        #[clap(flatten)]
+        formatting: Box<Formatting>,
+        /// Seed for the random number extension
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Optimisation level - from executing every instruction on its own, to rewriting the loops
+        #[clap(short = 'O', long, value_enum, default_value_t = OptLevel::O3)]
+        opt_level: OptLevel,
+        /// Literal used as the whole input, read byte by byte (accepts escapes, like \n, or \x41)
+        #[clap(long, value_parser = parse_escaped)]
+        stdin: Option<ByteString>,
+        /// File used as the whole input, read byte by byte
+        #[clap(long, value_parser = parse_file, conflicts_with = "stdin")]
+        input_data: Option<ByteString>,
+        /// Behaviour of the input instruction at the end of the input
+        #[clap(long, value_enum, default_value_t = EofMode::Error)]
+        eof: EofMode,
+        /// Abort once the program writes more bytes than this (accepts K, M, G suffixes)
+        #[clap(long, value_parser = parse_byte_size)]
+        max_output: Option<u64>,
+        /// Abort once the program executes more instructions than this - runaway programs never halt otherwise
+        #[clap(long)]
+        max_steps: Option<u64>,
+        /// Stop the run after the given time, and report where it stopped (accepts ms, s, m, h suffixes - seconds by default)
+        #[clap(long, value_parser = parse_duration, conflicts_with = "matrix")]
+        timeout: Option<Duration>,
+        /// Run under every pointer, and cell size combination with the same input, and compare the outputs (u32 pointers are skipped, as their tapes take gigabytes)
+        #[clap(long, action, conflicts_with_all = ["pointer_size", "cell_size"])]
+        matrix: bool,
+        /// Cache the state before the first input, and resume from it on later runs with the same program, and settings
+        #[clap(long, action)]
+        warm_start: bool,
+        /// Save a checkpoint of the run every given number of executed instructions, which can be restored after a restart
+        #[clap(long, value_parser = value_parser!(u64).range(1 ..), conflicts_with = "matrix")]
+        checkpoint_every: Option<u64>,
+        /// File the checkpoints are saved into - overwritten with every one
+        #[clap(long, default_value = "braincooker.ckpt", requires = "checkpoint_every")]
+        checkpoint_file: PathBuf,
+        /// Continue the run from a checkpoint, made for the same program
+        #[clap(long, conflicts_with_all = ["matrix", "warm_start"])]
+        restore: Option<PathBuf>,
+        /// Count the executions of every instruction, and report the hottest loops, and instructions after the run
+        #[clap(long, action, conflicts_with_all = ["matrix", "warm_start"])]
+        profile: bool,
+        /// Print every cell differing from the fill value, with its location after the run - the cells of several tapes also get their tape's index
+        #[clap(long, action, conflicts_with = "matrix")]
+        dump_tape: bool,
+        /// Fill the tape with cells from a file, from the first one on - as little-endian bytes of the cell's width (1-bit cells take a byte each, and the arbitrary-precision ones a decimal line each)
+        #[clap(long, conflicts_with = "matrix")]
+        tape_in: Option<PathBuf>,
+        /// Write every cell of the final tape into a file, as little-endian bytes of the cell's width (1-bit cells take a byte each, and the arbitrary-precision ones a decimal line each)
+        #[clap(long, conflicts_with = "matrix")]
+        tape_out: Option<PathBuf>,
+        /// Flush every output right away, so the prompts of interactive programs show up - it's otherwise buffered until an input, or the end
+        #[clap(long, action)]
+        flush_every_output: bool,
+        /// Write the program's output into a file, instead of the terminal - log lines never go there
+        #[clap(short, long, conflicts_with_all = ["matrix", "expect_output"])]
+        output_file: Option<PathBuf>,
+        /// Compare the output with a golden file, and fail with a diff on a mismatch
+        #[clap(long, conflicts_with = "matrix")]
+        expect_output: Option<PathBuf>,
+        /// Overwrite the golden file with the output, instead of comparing them
+        #[clap(long, action, requires = "expect_output")]
+        update_expected: bool
+        },
+    /// Step through Brainfuck code one instruction at a time, with breakpoints, and tape inspection
+    Debug {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Seed for the random number extension
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Literal used as the whole input, read byte by byte (accepts escapes, like \n, or \x41) - otherwise the program shares the terminal with the commands
+        #[clap(long, value_parser = parse_escaped)]
+        stdin: Option<ByteString>
+        },
+    /// Step through Brainfuck code in a visual debugger, showing the tape live
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Seed for the random number extension
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Literal used as the whole input, read byte by byte (accepts escapes, like \n, or \x41) - otherwise the input is empty
+        #[clap(long, value_parser = parse_escaped)]
+        stdin: Option<ByteString>
+        },
+    /// Run Brainfuck code line by line, keeping the tape between the lines
+    Repl {
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Seed for the random number extension
+        #[clap(long)]
+        seed: Option<u64>
+        },
+    /// Check Brainfuck code for suspicious constructs
+    Check {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings
+        },
+    /// Show static statistics of Brainfuck code, without running it
+    Analyze {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Print the statistics as JSON
+        #[clap(long)]
+        json: bool
+        },
+    /// Compare two Brainfuck programs by their structure
+    Diff {
+        /// Path to a file with the old source code
+        old: PathBuf,
+        /// Path to a file with the new source code
+        new: PathBuf,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings
+        },
+    /// Run Brainfuck programs at once, with the output of each one connected to the input of the next
+    Pipe {
+        /// Paths to the files with the programs, in order
+        #[clap(required = true, num_args = 1 ..)]
+        programs: Vec<PathBuf>,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Way of displaying value of a cell - raw keeps the streams byte-exact
+        #[clap(short, long, value_enum, default_value_t = DisplayMode::Raw)]
+        display_mode: DisplayMode,
+        /// Behaviour of the input instruction at the end of the input
+        #[clap(long, value_enum, default_value_t = EofMode::Zero)]
+        eof: EofMode
+        },
+    /// Run every `*.bf` program in a directory with its `*.in` input, and compare the output with its `*.out` file
+    Test {
+        /// Directory with the programs
+        dir: PathBuf,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Fail a test, once it executes more instructions than this - a program, which never halts can't hang the whole run
+        #[clap(long, default_value_t = 100_000_000)]
+        max_steps: u64,
+        /// Fail a test, once it runs longer than this (accepts ms, s, m, h suffixes - seconds by default)
+        #[clap(long, value_parser = parse_duration, default_value = "10s")]
+        timeout: Duration
+        },
+    /// Run the built-in programs under the tape layouts, and optimisation levels, and compare their speed
+    Bench {
+        /// Programs to run [default: all of them]
+        #[clap(value_enum)]
+        programs: Vec<Benchmark>,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Tape layouts to compare [default: all of them]
+        #[clap(short, long, value_enum, value_delimiter = ',')]
+        tape: Vec<TapeKind>,
+        /// Optimisation levels to compare [default: all of them]
+        #[clap(short = 'O', long, value_enum, value_delimiter = ',')]
+        opt_level: Vec<OptLevel>,
+        /// Number of runs of every configuration - the fastest one is reported
+        #[clap(short, long, default_value_t = 1)]
+        runs: u32
+        },
+    /// Pack Brainfuck code, and the settings it has to be run with into a self-contained bundle
+    Pack {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Tape layout
+        #[clap(short, long, value_enum, default_value_t = TapeKind::Fixed)]
+        tape: TapeKind,
+        /// Number of cells the pointer wraps at, like 30000 of the classic implementation [default: the whole range of the pointer]
+        #[clap(long)]
+        tape_size: Option<usize>,
+        /// Cell the pointer starts at - negative ones are only on the infinite tape
+        #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+        start_cell: isize,
+        /// Value every cell starts with, instead of zero
+        #[clap(long, default_value_t = 0, allow_negative_numbers = true)]
+        fill: i64,
+        /// Behaviour of the pointer moved past either end of the tape
+        #[clap(long, value_enum, default_value_t = PointerOverflow::Wrap)]
+        pointer_overflow: PointerOverflow,
+        /// Behaviour of the cell taken past its bounds
+        #[clap(long, value_enum, default_value_t = CellOverflow::Wrap)]
+        cell_overflow: CellOverflow,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Seed for the random number extension
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Literal embedded as the whole input, read byte by byte (accepts escapes, like \n, or \x41)
+        #[clap(long, value_parser = parse_escaped)]
+        stdin: Option<ByteString>,
+        /// Behaviour of the input instruction at the end of the input
+        #[clap(long, value_enum, default_value_t = EofMode::Error)]
+        eof: EofMode,
+        /// Abort once the program writes more bytes than this (accepts K, M, G suffixes)
+        #[clap(long, value_parser = parse_byte_size)]
+        max_output: Option<u64>,
+        /// Abort once the program executes more instructions than this
+        #[clap(long)]
+        max_steps: Option<u64>,
+        /// Free-form information, as key=value pairs
+        #[clap(long, value_parser = parse_key_value)]
+        meta: Vec<(String, String)>,
+        /// Output file path
+        #[clap(short, long)]
+        output_file: PathBuf
+        },
+    /// Run a bundle with the settings it was packed with
+    Run {
+        /// Path to the bundle
+        bundle: PathBuf,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings
+        },
+    /// Compile Brainfuck code into native code in memory, and run it right away
+    #[cfg(feature = "jit")]
+    Jit {
+        /// Possible input sources
+        #[clap(flatten)]
+        inputs: Inputs,
+        /// General settings
+        #[clap(flatten)]
+        settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>
         },
     /// Compile Brainfuck code into executable file
     Comp {
@@ -84,6 +544,27 @@ pub enum CMD {
         /// General settings
         #[clap(flatten)]
         settings: Settings,
+        /// Pointer size, number of cells
+        #[clap(short, long, value_enum, default_value_t = DataSize::U16)]
+        pointer_size: DataSize,
+        /// Cell size
+        #[clap(short, long, value_enum, default_value_t = CellSize::U8)]
+        cell_size: CellSize,
+        /// Way of displaying value of a cell [default: ascii, or numeric for 1-bit cells]
+        #[clap(short, long, value_enum)]
+        display_mode: Option<DisplayMode>,
+        /// Optimisation level - from compiling every instruction on its own, to rewriting the loops
+        #[clap(short = 'O', long, value_enum, default_value_t = OptLevel::O3)]
+        opt_level: OptLevel,
+        /// Target architecture - chosen explicitly, regardless of the host
+        #[clap(short, long, visible_alias = "target", value_enum, default_value_t = Arch::X86_64)]
+        arch: Arch,
+        /// Output format
+        #[clap(short, long, value_enum, default_value_t = Emit::Binary)]
+        emit: Emit,
+        /// Whether the Rust output should stop on overflows, instead of wrapping around
+        #[clap(long, action)]
+        checked: bool,
         /// Output file path
         #[clap(short, long)]
         output_file: PathBuf
@@ -93,16 +574,256 @@ pub enum CMD {
 
 impl CMD {
     /* Getters */
-    pub const fn get_inputs(&self) -> &Inputs {
+    pub const fn get_inputs(&self) -> Option<&Inputs> {
         match self {
-            CMD::Interp { inputs, .. } => inputs,
-            CMD::Comp { inputs, .. } => inputs
+            CMD::Interp { inputs, .. } => Some(inputs),
+            CMD::Debug { inputs, .. } => Some(inputs),
+            #[cfg(feature = "tui")]
+            CMD::Tui { inputs, .. } => Some(inputs),
+            CMD::Check { inputs, .. } => Some(inputs),
+            CMD::Analyze { inputs, .. } => Some(inputs),
+            CMD::Pack { inputs, .. } => Some(inputs),
+            CMD::Diff { .. } | CMD::Pipe { .. } | CMD::Test { .. } | CMD::Bench { .. } | CMD::Repl { .. } | CMD::Run { .. } => None,
+            #[cfg(feature = "jit")]
+            CMD::Jit { inputs, .. } => Some(inputs),
+            CMD::Comp { inputs, .. } => Some(inputs)
             }
         }
     pub const fn get_settings(&self) -> &Settings {
         match self {
             CMD::Interp { settings, .. } => settings,
+            CMD::Debug { settings, .. } => settings,
+            #[cfg(feature = "tui")]
+            CMD::Tui { settings, .. } => settings,
+            CMD::Check { settings, .. } => settings,
+            CMD::Analyze { settings, .. } => settings,
+            CMD::Diff { settings, .. } => settings,
+            CMD::Pipe { settings, .. } => settings,
+            CMD::Test { settings, .. } => settings,
+            CMD::Bench { settings, .. } => settings,
+            CMD::Repl { settings, .. } => settings,
+            CMD::Pack { settings, .. } => settings,
+            CMD::Run { settings, .. } => settings,
+            #[cfg(feature = "jit")]
+            CMD::Jit { settings, .. } => settings,
             CMD::Comp { settings, .. } => settings
             }
         }
+    }
+
+
+/* Function for getting the command-line name of a value */
+pub fn value_name<T>(value: &T) -> String
+where T: ValueEnum {
+    value.to_possible_value()
+        .map(|value| value.get_name().to_owned())
+        .unwrap_or_default()
+    }
+
+
+/* Owned bytes of a parsed literal */
+#[derive(Clone, Hash)]
+pub struct ByteString (
+    pub Vec<u8>
+    );
+
+/* Function for parsing a literal with escape sequences into bytes */
+pub fn parse_escaped(value: &str) -> Result<ByteString, String> {
+    let mut output = Vec::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(chr) = chars.next() {
+        /* Copy regular characters as UTF-8 */
+        if chr != '\\' {
+            let mut buf = [0; 4];
+            output.extend_from_slice(chr.encode_utf8(&mut buf).as_bytes());
+            continue;
+            }
+
+        let byte = match chars.next() {
+            Some('n') => b'\n',
+            Some('t') => b'\t',
+            Some('r') => b'\r',
+            Some('0') => b'\0',
+            Some('\\') => b'\\',
+            Some('\'') => b'\'',
+            Some('"') => b'"',
+            /* Raw byte, as two hex digits */
+            Some('x') => {
+                let digits: String = chars.by_ref()
+                    .take(2)
+                    .collect();
+
+                match digits.len() == 2 && digits.chars().all(|chr| chr.is_ascii_hexdigit()) {
+                    /* Unsafe note - unwrap is safe, because both digits were checked */
+                    true => unsafe {
+                        u8::from_str_radix(&digits, 16)
+                            .unwrap_unchecked()
+                        },
+                    false => return Err(format!("Invalid hex escape: \\x{digits}"))
+                    }
+                },
+            Some(other) =>
+                return Err(format!("Unknown escape: \\{other}")),
+            None =>
+                return Err("Trailing backslash".to_owned())
+            };
+
+        output.push(byte);
+        }
+
+    Ok(ByteString(output))
+    }
+
+/* Function for parsing a separator - the name of a common one, or a literal with escape sequences */
+pub fn parse_separator(value: &str) -> Result<ByteString, String> {
+    match value {
+        "space" => Ok(ByteString(b" ".to_vec())),
+        "newline" => Ok(ByteString(b"\n".to_vec())),
+        "comma" => Ok(ByteString(b",".to_vec())),
+        _ => parse_escaped(value)
+        }
+    }
+
+/* Function for reading the whole file at the path into bytes */
+pub fn parse_file(value: &str) -> Result<ByteString, String> {
+    read(value)
+        .map(ByteString)
+        .map_err(|err| format!("Couldn't read {value}: {err}"))
+    }
+
+/* Function for reading, and parsing the token map file */
+pub fn parse_token_map(value: &str) -> Result<TokenMap, String> {
+    read_to_string(value)
+        .map_err(|err| format!("Couldn't read {value}: {err}"))?
+        .parse()
+        .map_err(|err: TokenMapError| err.to_string())
+    }
+
+/* Function for parsing a number of bytes, with an optional binary multiple suffix */
+pub fn parse_byte_size(value: &str) -> Result<u64, String> {
+    /* Split off the suffix, if present */
+    let (number, multiplier) = match value.as_bytes().last() {
+        Some(b'K' | b'k') => (&value[.. value.len() - 1], 1 << 10),
+        Some(b'M' | b'm') => (&value[.. value.len() - 1], 1 << 20),
+        Some(b'G' | b'g') => (&value[.. value.len() - 1], 1 << 30),
+        _ => (value, 1)
+        };
+
+    number.parse::<u64>()
+        .map_err(|err| err.to_string())?
+        .checked_mul(multiplier)
+        .ok_or_else(|| "Size is too large".to_owned())
+    }
+
+/* Function for parsing a duration, with an optional unit suffix */
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    /* Split off the suffix, if present - milliseconds go first, as they end with seconds' suffix */
+    let (number, multiplier) = match value {
+        _ if value.ends_with("ms") => (&value[.. value.len() - 2], 1),
+        _ if value.ends_with('s') => (&value[.. value.len() - 1], 1000),
+        _ if value.ends_with('m') => (&value[.. value.len() - 1], 60 * 1000),
+        _ if value.ends_with('h') => (&value[.. value.len() - 1], 60 * 60 * 1000),
+        _ => (value, 1000)
+        };
+
+    number.parse::<u64>()
+        .map_err(|err| err.to_string())?
+        .checked_mul(multiplier)
+        .map(Duration::from_millis)
+        .ok_or_else(|| "Duration is too long".to_owned())
+    }
+
+/* Function for parsing a `key=value` pair */
+pub fn parse_key_value(value: &str) -> Result<(String, String), String> {
+    value.split_once('=')
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .ok_or_else(|| "Expected a key=value pair".to_owned())
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::args::*;
+
+    fn escaped(value: &str) -> Result<Vec<u8>, String> {
+        parse_escaped(value)
+            .map(|ByteString(bytes)| bytes)
+        }
+
+    #[test]
+    fn escaped_plain() {
+        assert_eq!(escaped(""), Ok(vec![]));
+        assert_eq!(escaped("hello"), Ok(b"hello".to_vec()));
+        assert_eq!(escaped("zażółć"), Ok("zażółć".as_bytes().to_vec()));
+        }
+
+    #[test]
+    fn escaped_sequences() {
+        assert_eq!(escaped("hello\\n"), Ok(b"hello\n".to_vec()));
+        assert_eq!(escaped("\\t\\r\\0"), Ok(b"\t\r\0".to_vec()));
+        assert_eq!(escaped("\\\\\\'\\\""), Ok(b"\\'\"".to_vec()));
+        assert_eq!(escaped("\\x41\\xff"), Ok(vec![b'A', 0xFF]));
+        }
+
+    #[test]
+    fn escaped_incorrect() {
+        assert!(escaped("\\").is_err());
+        assert!(escaped("\\q").is_err());
+        assert!(escaped("\\x4").is_err());
+        assert!(escaped("\\xzz").is_err());
+        assert!(escaped("\\x+1").is_err());
+        }
+
+    #[test]
+    fn separator_names() {
+        let separator = |value| parse_separator(value)
+            .map(|ByteString(bytes)| bytes);
+
+        assert_eq!(separator("space"), Ok(b" ".to_vec()));
+        assert_eq!(separator("newline"), Ok(b"\n".to_vec()));
+        assert_eq!(separator("comma"), Ok(b",".to_vec()));
+        assert_eq!(separator(", "), Ok(b", ".to_vec()));
+        assert_eq!(separator("\\t"), Ok(b"\t".to_vec()));
+        }
+
+    #[test]
+    fn byte_size_plain() {
+        assert_eq!(parse_byte_size("0"), Ok(0));
+        assert_eq!(parse_byte_size("1000"), Ok(1000));
+        }
+
+    #[test]
+    fn byte_size_suffixes() {
+        assert_eq!(parse_byte_size("1K"), Ok(1024));
+        assert_eq!(parse_byte_size("10M"), Ok(10 * 1024 * 1024));
+        assert_eq!(parse_byte_size("2g"), Ok(2 * 1024 * 1024 * 1024));
+        }
+
+    #[test]
+    fn duration_suffixes() {
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("3s"), Ok(Duration::from_secs(3)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        }
+
+    #[test]
+    fn duration_incorrect() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("1.5s").is_err());
+        assert!(parse_duration("10sec").is_err());
+        }
+
+    #[test]
+    fn byte_size_incorrect() {
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("M").is_err());
+        assert!(parse_byte_size("-1K").is_err());
+        assert!(parse_byte_size("10MB").is_err());
+        assert!(parse_byte_size("99999999999G").is_err());
+        }
     }
\ No newline at end of file
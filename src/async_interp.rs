@@ -0,0 +1,127 @@
+use {
+    tokio::io::{
+        AsyncBufRead,
+        AsyncBufReadExt,
+        AsyncWrite,
+        AsyncWriteExt
+        },
+    crate::{
+        eval::InstructionSet,
+        interp::*,
+        tape::{
+            TapeCell,
+            TapePointer
+            },
+        utils::{
+            SharedBuffer,
+            SharedFeed
+            }
+        }
+    };
+
+
+/* Interpreter reading, and writing through the asynchronous I/O - the instructions in between the inputs run synchronously, and the output is written out before every input, and at the end */
+pub struct AsyncInterpreter<R, W> {
+    interp: Box<dyn InterpRun>,
+    feed: SharedFeed,
+    sink: SharedBuffer,
+    input: R,
+    output: W
+    }
+
+impl<R, W> AsyncInterpreter<R, W>
+where R: AsyncBufRead + Unpin, W: AsyncWrite + Unpin {
+    /* Constructor function, building the Interpreter with the builder's settings - its own input, and output are replaced */
+    pub fn new<T, U>(builder: InterpreterBuilder, input: R, output: W) -> Result<Self, BuildError>
+    where T: TapePointer + 'static, U: TapeCell + 'static {
+        let feed = SharedFeed::default();
+        let sink = SharedBuffer::default();
+
+        let interp = builder
+            .input(Box::new(feed.clone()))
+            .output(Box::new(sink.clone()))
+            .try_build::<T, U>()?;
+
+        Ok(Self {
+            interp: Box::new(interp),
+            feed,
+            sink,
+            input,
+            output
+            })
+        }
+
+    /* Getter of the Interpreter, for reading the tape after the run */
+    pub fn interp(&mut self) -> &mut dyn InterpRun {
+        &mut *self.interp
+        }
+
+    /* Run the source code's instructions - every input waits for a whole line, which the following inputs take from, until it runs out */
+    pub async fn run(&mut self, instr: &InstructionSet) -> Result<(), RunError> {
+        let ir = self.interp.prepare(instr);
+
+        let mut op_ptr = 0;
+        let mut count = 0;
+
+        loop {
+            op_ptr = self.interp.run_until_input(&ir, op_ptr, &mut count)?;
+
+            /* Write out everything written so far */
+            self.output.write_all(&self.sink.take()).await?;
+            self.output.flush().await?;
+
+            if op_ptr >= ir.len() {
+                return Ok(());
+                }
+
+            /* Wait for the next line, only when the last one was used up - nothing read means the end of input */
+            if self.feed.is_empty() {
+                let mut line = Vec::new();
+                self.input.read_until(b'\n', &mut line).await?;
+                self.feed.extend(&line);
+                }
+
+            op_ptr = self.interp.step(&ir, op_ptr, &mut count)?;
+            }
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use {
+        tokio::runtime::Builder,
+        crate::{
+            async_interp::*,
+            eval::eval_instr,
+            utils::{
+                DisplayMode,
+                EofMode
+                }
+            }
+        };
+
+    #[test]
+    fn async_run() {
+        let instr = eval_instr("++.,[+.,]")
+            .expect("Unreachable");
+
+        let builder = Interpreter::builder()
+            .display_mode(DisplayMode::Raw)
+            .byte_input(true)
+            .eof(EofMode::Zero);
+        let mut output = Vec::new();
+        let mut interp = AsyncInterpreter::new::<u8, u8>(builder, &b"HA\nL"[..], &mut output)
+            .expect("Unreachable");
+
+        Builder::new_current_thread()
+            .build()
+            .expect("Unreachable")
+            .block_on(interp.run(&instr))
+            .expect("Unreachable");
+
+        assert_eq!(interp.interp().cells(), Vec::new());
+        drop(interp);
+        assert_eq!(output, b"\x02IB\x0bM");
+        }
+    }
@@ -0,0 +1,149 @@
+use {
+    anyhow::{
+        bail,
+        Result as DynResult
+        },
+    clap::ValueEnum,
+    log::info,
+    std::{
+        io::empty,
+        time::Duration
+        },
+    crate::{
+        args::*,
+        construct_interp
+        },
+    braincooker::*
+    };
+
+
+impl Benchmark {
+    /* Get the source code - the programs are plain Brainfuck, without any input */
+    pub const fn source(self) -> &'static str {
+        match self {
+            Benchmark::Hello => include_str!("../assets/bench/hello.b"),
+            Benchmark::Sierpinski => include_str!("../assets/bench/sierpinski.b"),
+            Benchmark::Mandelbrot => include_str!("../assets/bench/mandelbrot.b"),
+            Benchmark::Pi => include_str!("../assets/bench/pi.b")
+            }
+        }
+
+    /* Get the cell size the program was written for */
+    pub const fn cell_size(self) -> CellSize {
+        match self {
+            Benchmark::Hello | Benchmark::Sierpinski => CellSize::U8,
+            Benchmark::Mandelbrot | Benchmark::Pi => CellSize::U16
+            }
+        }
+    }
+
+
+/* Function for running the programs under every tape layout, and optimisation level, and reporting the fastest of the runs - empty lists stand for every value */
+pub fn run_bench(programs: &[Benchmark], tapes: &[TapeKind], levels: &[OptLevel], runs: u32) -> DynResult<()> {
+    for program in every_if_empty(programs) {
+        let name = value_name(&program);
+        let instr = eval_instr(program.source())?;
+
+        println!("{name}:");
+        println!("    {:<10} {:<5} {:>12} {:>14} {:>16}", "tape", "level", "time", "instructions", "instructions/s");
+
+        /* Output of the first configuration, which all of the others have to match */
+        let mut expected = None;
+
+        for &tape in every_if_empty(tapes).iter() {
+            for &level in every_if_empty(levels).iter() {
+                let config = format!("{}/O{}", value_name(&tape), value_name(&level));
+
+                info!("Running {name} with tape/level: {config}");
+
+                let mut fastest: Option<RunReport> = None;
+
+                for _ in 0 .. runs.max(1) {
+                    /* Fresh interpreter for every run, with a captured output */
+                    let buffer = SharedBuffer::default();
+                    let builder = Interpreter::builder()
+                        .display_mode(DisplayMode::ASCII)
+                        .tape_kind(tape)
+                        .opt_level(level)
+                        .input(Box::new(empty()))
+                        .output(Box::new(buffer.clone()));
+
+                    let report = construct_interp(builder, DataSize::U16, program.cell_size())?
+                        .run_with_report(&instr)?;
+
+                    /* Every configuration has to give the same output */
+                    let output = buffer.take();
+                    match &expected {
+                        Some(expected) if *expected != output =>
+                            bail!("Output of {name} under {config} differs from the first configuration"),
+                        Some(_) => (),
+                        None => expected = Some(output)
+                        }
+
+                    if fastest.as_ref().is_none_or(|fastest| report.elapsed < fastest.elapsed) {
+                        fastest = Some(report);
+                        }
+                    }
+
+                if let Some(RunReport { executed, elapsed, .. }) = fastest {
+                    println!("    {:<10} O{:<4} {:>12.3?} {executed:>14} {:>16.0}", value_name(&tape), value_name(&level), elapsed, per_second(executed, elapsed));
+                    }
+                }
+            }
+        }
+
+    Ok(())
+    }
+
+/* Function for getting the chosen values, or all of them, when none were chosen */
+fn every_if_empty<T>(values: &[T]) -> Vec<T>
+where T: ValueEnum + Clone {
+    match values.is_empty() {
+        true => T::value_variants().to_vec(),
+        false => values.to_vec()
+        }
+    }
+
+/* Function for getting the rate of the executed instructions - a run too short to be measured counts as a nanosecond */
+fn per_second(executed: u64, elapsed: Duration) -> f64 {
+    executed as f64 / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::bench::*;
+
+    #[test]
+    fn bench_corpus() {
+        for &program in Benchmark::value_variants() {
+            let buffer = SharedBuffer::default();
+            let builder = Interpreter::builder()
+                .display_mode(DisplayMode::ASCII)
+                .input(Box::new(empty()))
+                .output(Box::new(buffer.clone()));
+
+            let instr = eval_instr(program.source())
+                .expect("Unreachable");
+            construct_interp(builder, DataSize::U16, program.cell_size())
+                .expect("Unreachable")
+                .run(&instr)
+                .expect("Unreachable");
+
+            /* The line feeds of the program, and the final one of the display mode */
+            let output = String::from_utf8(buffer.take())
+                .expect("Unreachable");
+            let output = output.trim_end_matches('\n');
+            let last = output.lines()
+                .last()
+                .unwrap_or_default();
+
+            match program {
+                Benchmark::Hello => assert_eq!(output, "Hello World!"),
+                Benchmark::Sierpinski => assert_eq!(last, "* ".repeat(32)),
+                Benchmark::Mandelbrot => assert_eq!(last, ".......,,,,,,,,,,,,,,:@@@,,,,.."),
+                Benchmark::Pi => assert_eq!(output, "31415926535897932384")
+                }
+            }
+        }
+    }
@@ -0,0 +1,162 @@
+use {
+    thiserror::Error,
+    crate::codec::*
+    };
+
+
+/* Magic bytes, and version of the encoded format */
+const MAGIC: &[u8; 4] = b"BCBN";
+const VERSION: u8 = 1;
+
+
+/* Bundle decoding's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum BundleError {
+    #[error("Bundle data is malformed")]
+    Malformed,
+    #[error("Bundle was made with an unsupported format version: {0}")]
+    UnsupportedVersion(u8)
+    }
+
+impl From<Malformed> for BundleError {
+    fn from(_: Malformed) -> Self {
+        BundleError::Malformed
+        }
+    }
+
+/* Self-contained program, with the settings it has to be run with */
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Bundle {
+    /* Sanitised source code */
+    pub program: String,
+    /* Settings required by the program, as names, and values of the command-line options */
+    pub settings: Vec<(String, String)>,
+    /* Input fed to the program, instead of the standard input */
+    pub input: Option<Vec<u8>>,
+    /* Free-form information, like the author, or the version */
+    pub metadata: Vec<(String, String)>
+    }
+
+impl Bundle {
+    /* Serialise into bytes */
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::with_capacity(64 + self.program.len());
+
+        output.extend_from_slice(MAGIC);
+        output.push(VERSION);
+
+        put_bytes(&mut output, self.program.as_bytes());
+
+        for pairs in [&self.settings, &self.metadata] {
+            output.extend_from_slice(&(pairs.len() as u64).to_le_bytes());
+            for (key, value) in pairs.iter() {
+                put_bytes(&mut output, key.as_bytes());
+                put_bytes(&mut output, value.as_bytes());
+                }
+            }
+
+        /* Missing input is marked by a zero byte, to tell it apart from an empty one */
+        match &self.input {
+            Some(input) => {
+                output.push(1);
+                put_bytes(&mut output, input);
+                },
+            None =>
+                output.push(0)
+            }
+
+        output
+        }
+
+    /* Deserialise from bytes */
+    pub fn decode(bytes: &[u8]) -> Result<Self, BundleError> {
+        let mut reader = Reader(bytes);
+
+        /* Check the header */
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(BundleError::Malformed);
+            }
+
+        match reader.take(1)?[0] {
+            VERSION => (),
+            version => return Err(BundleError::UnsupportedVersion(version))
+            };
+
+        let program = reader.string()?;
+
+        let mut pairs = || {
+            let len = reader.size()?;
+
+            (0 .. len)
+                .map(|_| Ok((reader.string()?, reader.string()?)))
+                .collect::<Result<Vec<_>, Malformed>>()
+            };
+        let settings = pairs()?;
+        let metadata = pairs()?;
+
+        let input = match reader.take(1)?[0] {
+            0 => None,
+            1 => Some(reader.bytes()?.to_vec()),
+            _ => return Err(BundleError::Malformed)
+            };
+
+        /* Trailing data means the bundle is corrupted */
+        if ! reader.is_empty() {
+            return Err(BundleError::Malformed);
+            }
+
+        Ok(Self { program, settings, input, metadata })
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::bundle::*;
+
+    fn sample() -> Bundle {
+        Bundle {
+            program: "++[->+<]>.".to_owned(),
+            settings: vec![("cell-size".to_owned(), "u16".to_owned())],
+            input: Some(b"\0hi".to_vec()),
+            metadata: vec![("author".to_owned(), "SQDexe".to_owned())]
+            }
+        }
+
+    #[test]
+    fn bundle_roundtrip() {
+        let bundle = sample();
+
+        assert_eq!(Bundle::decode(&bundle.encode()), Ok(bundle));
+        }
+
+    #[test]
+    fn bundle_roundtrip_no_input() {
+        let bundle = Bundle {
+            input: None,
+            ..sample()
+            };
+
+        assert_eq!(Bundle::decode(&bundle.encode()), Ok(bundle));
+        }
+
+    #[test]
+    fn bundle_truncated() {
+        let bytes = sample()
+            .encode();
+
+        let truncated = (0 .. bytes.len())
+            .all(|len| Bundle::decode(&bytes[.. len]) == Err(BundleError::Malformed));
+
+        assert!(truncated);
+        }
+
+    #[test]
+    fn bundle_version() {
+        let mut bytes = sample()
+            .encode();
+        bytes[MAGIC.len()] = VERSION + 1;
+
+        assert_eq!(Bundle::decode(&bytes), Err(BundleError::UnsupportedVersion(VERSION + 1)));
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,172 @@
+use {
+    thiserror::Error,
+    clap::ValueEnum,
+    crate::{
+        codec::*,
+        eval::InstructionSet,
+        ir::Ir,
+        utils::{
+            CellSize,
+            DataSize,
+            OptLevel
+            }
+        }
+    };
+
+
+/* Magic bytes, and version of the encoded format */
+const MAGIC: &[u8; 4] = b"BCBC";
+const VERSION: u8 = 1;
+
+
+/* Bytecode decoding's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum BytecodeError {
+    #[error("Bytecode data is malformed")]
+    Malformed,
+    #[error("Bytecode was made with an unsupported format version: {0}")]
+    UnsupportedVersion(u8)
+    }
+
+impl From<Malformed> for BytecodeError {
+    fn from(_: Malformed) -> Self {
+        BytecodeError::Malformed
+        }
+    }
+
+/* Lowered program, with the sizes it was compiled for - loaded without parsing, and optimising the source code again */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bytecode {
+    pub pointer_size: DataSize,
+    pub cell_size: CellSize,
+    /* Optimisation level the operations were lowered at */
+    pub opt_level: OptLevel,
+    pub ir: Ir
+    }
+
+impl Bytecode {
+    /* Lower the instructions into bytecode, at the optimisation level */
+    pub fn compile(instr: &InstructionSet, pointer_size: DataSize, cell_size: CellSize, opt_level: OptLevel) -> Self {
+        Self {
+            pointer_size,
+            cell_size,
+            opt_level,
+            ir: Ir::lower_at(instr, opt_level)
+            }
+        }
+
+    /* Serialise into bytes */
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::with_capacity(64 + 16 * self.ir.len());
+
+        output.extend_from_slice(MAGIC);
+        output.push(VERSION);
+
+        output.push(self.pointer_size as u8);
+        output.push(self.cell_size as u8);
+        output.push(self.opt_level as u8);
+
+        self.ir.encode(&mut output);
+
+        output
+        }
+
+    /* Deserialise from bytes */
+    pub fn decode(bytes: &[u8]) -> Result<Self, BytecodeError> {
+        let mut reader = Reader(bytes);
+
+        /* Check the header */
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(BytecodeError::Malformed);
+            }
+
+        match reader.take(1)?[0] {
+            VERSION => (),
+            version => return Err(BytecodeError::UnsupportedVersion(version))
+            };
+
+        /* Settings are stored as their variants' order */
+        let &[pointer_size, cell_size, opt_level] = reader.take(3)? else {
+            return Err(BytecodeError::Malformed);
+            };
+        let pointer_size = DataSize::value_variants()
+            .get(usize::from(pointer_size))
+            .copied()
+            .ok_or(Malformed)?;
+        let cell_size = CellSize::value_variants()
+            .get(usize::from(cell_size))
+            .copied()
+            .ok_or(Malformed)?;
+        let opt_level = OptLevel::value_variants()
+            .get(usize::from(opt_level))
+            .copied()
+            .ok_or(Malformed)?;
+
+        let ir = Ir::decode(&mut reader)?;
+
+        /* Trailing data means the bytecode is corrupted */
+        if ! reader.is_empty() {
+            return Err(BytecodeError::Malformed);
+            }
+
+        Ok(Self { pointer_size, cell_size, opt_level, ir })
+        }
+
+    /* Get whether the bytes start like bytecode */
+    pub fn is_bytecode(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        bytecode::*,
+        eval::eval_instr
+        };
+
+    fn sample() -> Bytecode {
+        let instr = eval_instr("++[->+<]>[-]<<.")
+            .expect("Unreachable");
+
+        Bytecode::compile(&instr, DataSize::U8, CellSize::U16, OptLevel::O2)
+        }
+
+    #[test]
+    fn bytecode_roundtrip() {
+        let bytecode = sample();
+
+        assert!(Bytecode::is_bytecode(&bytecode.encode()));
+        assert_eq!(Bytecode::decode(&bytecode.encode()), Ok(bytecode));
+        }
+
+    #[test]
+    fn bytecode_truncated() {
+        let bytes = sample()
+            .encode();
+
+        let truncated = (0 .. bytes.len())
+            .all(|len| Bytecode::decode(&bytes[.. len]) == Err(BytecodeError::Malformed));
+
+        assert!(truncated);
+        }
+
+    #[test]
+    fn bytecode_version() {
+        let mut bytes = sample()
+            .encode();
+        bytes[MAGIC.len()] = VERSION + 1;
+
+        assert_eq!(Bytecode::decode(&bytes), Err(BytecodeError::UnsupportedVersion(VERSION + 1)));
+        }
+
+    #[test]
+    fn bytecode_bad_size() {
+        let mut bytes = sample()
+            .encode();
+        bytes[MAGIC.len() + 2] = 0xFF;
+
+        assert_eq!(Bytecode::decode(&bytes), Err(BytecodeError::Malformed));
+        }
+    }
\ No newline at end of file
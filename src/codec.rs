@@ -0,0 +1,76 @@
+use core::mem::size_of;
+
+
+/* Error of reading past the end of, or an invalid value in the encoded data */
+#[derive(PartialEq, Debug)]
+pub struct Malformed;
+
+
+/* Helper container for reading encoded values */
+pub struct Reader<'a> (
+    pub &'a [u8]
+    );
+
+impl<'a> Reader<'a> {
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], Malformed> {
+        let Some((head, tail)) = self.0.split_at_checked(len) else {
+            return Err(Malformed);
+            };
+
+        self.0 = tail;
+        Ok(head)
+        }
+
+    pub fn u64(&mut self) -> Result<u64, Malformed> {
+        let bytes = self.take(size_of::<u64>())?;
+        /* Unsafe note - unwrap is safe, because exactly 8 bytes were taken */
+        let array = bytes.try_into();
+        Ok(u64::from_le_bytes(unsafe {
+            array.unwrap_unchecked()
+            }))
+        }
+
+    pub fn i16(&mut self) -> Result<i16, Malformed> {
+        let bytes = self.take(size_of::<i16>())?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
+        }
+
+    pub fn i64(&mut self) -> Result<i64, Malformed> {
+        self.u64()
+            .map(|value| value as i64)
+        }
+
+    /* Lengths, and indices have to fit into the platform's usize */
+    pub fn size(&mut self) -> Result<usize, Malformed> {
+        self.u64()?
+            .try_into()
+            .map_err(|_| Malformed)
+        }
+
+    /* Length prefixed bytes */
+    pub fn bytes(&mut self) -> Result<&'a [u8], Malformed> {
+        let len = self.size()?;
+        self.take(len)
+        }
+
+    /* Length prefixed UTF-8 text */
+    pub fn string(&mut self) -> Result<String, Malformed> {
+        let bytes = self.bytes()?;
+
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| Malformed)
+        }
+
+    /* Get whether everything was read */
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+        }
+    }
+
+
+/* Function for writing length prefixed bytes */
+pub fn put_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    output.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    output.extend_from_slice(bytes);
+    }
\ No newline at end of file
@@ -0,0 +1,235 @@
+use {
+    thiserror::Error,
+    crate::{
+        eval::*,
+        ir::*,
+        utils::*
+        }
+    };
+
+
+/* Size of the compiled programs' output buffer, placed at the start of the zero-initialised memory, right before the tape */
+pub(crate) const BUFFER_SIZE: u64 = 0x1000;
+
+
+/* Compilation's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum CompileError {
+    #[error("Instruction is not supported by the compiler: {0}")]
+    Unsupported(char),
+    #[error("ASCII, or Unicode display is not supported for 1-bit cells")]
+    AsciiBitCells,
+    #[error("Tape doesn't fit into the target's memory")]
+    TapeTooLarge,
+    #[error("Unicode display is not supported by the target")]
+    UnicodeDisplay,
+    #[error("64-bit cells are not supported by the target")]
+    WideCells,
+    #[error("Arbitrary-precision cells are not supported by the compilers")]
+    BigCells,
+    #[error("Signed cells are not supported by the compilers")]
+    SignedCells
+    }
+
+/* Settings of the compiled program - the same meaning as in the Interpreter */
+#[derive(Clone, Copy, Debug)]
+pub struct CodegenOptions {
+    pub pointer_size: DataSize,
+    pub cell_size: CellSize,
+    pub display_mode: DisplayMode,
+    pub opt_level: OptLevel
+    }
+
+impl Default for CodegenOptions {
+    /* The default settings of the command-line */
+    fn default() -> Self {
+        Self {
+            pointer_size: DataSize::U16,
+            cell_size: CellSize::U8,
+            display_mode: DisplayMode::ASCII,
+            opt_level: OptLevel::O3
+            }
+        }
+    }
+
+impl CodegenOptions {
+    /* Check for incompatible settings */
+    pub(crate) const fn validate(&self) -> Result<(), CompileError> {
+        match (self.pointer_size, self.cell_size, self.display_mode) {
+            /* Compiled programs hold the whole tape, which a 64-bit pointer can't address */
+            (DataSize::U64, _, _) => Err(CompileError::TapeTooLarge),
+            (_, CellSize::Big, _) => Err(CompileError::BigCells),
+            (_, CellSize::I8 | CellSize::I16 | CellSize::I32, _) => Err(CompileError::SignedCells),
+            (_, CellSize::U1, DisplayMode::ASCII | DisplayMode::Unicode) => Err(CompileError::AsciiBitCells),
+            _ => Ok(())
+            }
+        }
+
+    /* Check for incompatible settings, and for the cells, and display modes the machine code doesn't encode */
+    pub(crate) const fn validate_native(&self) -> Result<(), CompileError> {
+        match (self.cell_size, self.display_mode) {
+            (CellSize::U64, _) => Err(CompileError::WideCells),
+            (_, DisplayMode::Unicode) => Err(CompileError::UnicodeDisplay),
+            _ => self.validate()
+            }
+        }
+
+    /* Get the number of bytes a cell is stored in - 1-bit cells take a whole byte */
+    pub(crate) const fn cell_bytes(&self) -> u64 {
+        match self.cell_size {
+            CellSize::U1 | CellSize::U8 | CellSize::I8 => 1,
+            CellSize::U16 | CellSize::I16 => 2,
+            CellSize::U32 | CellSize::I32 => 4,
+            CellSize::U64 => 8,
+            /* Arbitrary-precision cells were rejected while validating */
+            CellSize::Big => 8
+            }
+        }
+
+    /* Get the mask of the cell's value */
+    pub(crate) const fn cell_mask(&self) -> u64 {
+        match self.cell_size {
+            CellSize::U1 => 0x1,
+            CellSize::U8 | CellSize::I8 => 0xFF,
+            CellSize::U16 | CellSize::I16 => 0xFFFF,
+            CellSize::U32 | CellSize::I32 => 0xFFFF_FFFF,
+            CellSize::U64 => u64::MAX,
+            /* Arbitrary-precision cells were rejected while validating */
+            CellSize::Big => u64::MAX
+            }
+        }
+
+    /* Get the number of bits of the pointer */
+    pub(crate) const fn pointer_bits(&self) -> u32 {
+        match self.pointer_size {
+            DataSize::U8 => u8::BITS,
+            DataSize::U16 => u16::BITS,
+            DataSize::U32 => u32::BITS,
+            DataSize::U64 => u64::BITS
+            }
+        }
+
+    /* Get the number of cells on the tape */
+    pub(crate) const fn tape_len(&self) -> u64 {
+        1 << self.pointer_bits()
+        }
+
+    /* Get the size of the zero-initialised memory - the output buffer, and the tape */
+    pub(crate) const fn bss_size(&self) -> u64 {
+        BUFFER_SIZE + self.tape_len() * self.cell_bytes()
+        }
+    }
+
+
+/* Operating system the executable is made for */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Platform {
+    Linux,
+    MacOs
+    }
+
+/* System calls used by the runtime routines */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Syscall {
+    Read,
+    Write,
+    Exit
+    }
+
+impl Syscall {
+    /* Get the number on macOS - the same for every architecture */
+    pub(crate) const fn bsd_number(self) -> u32 {
+        match self {
+            Syscall::Exit => 1,
+            Syscall::Read => 3,
+            Syscall::Write => 4
+            }
+        }
+    }
+
+/* Placement of the executable in memory - the code is addressed relative to itself, so it can be moved */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct Layout {
+    pub platform: Platform,
+    /* Address of the code's first byte */
+    pub code_address: u64,
+    /* Address of the zero-initialised memory */
+    pub bss_address: u64
+    }
+
+
+/* Function for lowering the instructions for the backends - random values, debugging instructions, procedures, and tape switches can't be compiled */
+pub(crate) fn lower_checked(instr: &InstructionSet, level: OptLevel) -> Result<Ir, CompileError> {
+    let ir = Ir::lower_at(instr, level);
+
+    let unsupported = ir.ops().iter().find_map(|&op| match op {
+        IrOp::Random => Some(Instruction::Random),
+        IrOp::Dump => Some(Instruction::Dump),
+        IrOp::ProcStart(_) => Some(Instruction::ProcStart),
+        IrOp::ProcEnd => Some(Instruction::ProcEnd),
+        IrOp::Call => Some(Instruction::ProcCall),
+        IrOp::Break => Some(Instruction::Breakpoint),
+        IrOp::ShowPointer => Some(Instruction::ShowPointer),
+        IrOp::ShowTape => Some(Instruction::ShowTape),
+        IrOp::Switch(offset) => Some(match offset < 0 {
+            true => Instruction::TapeUp,
+            false => Instruction::TapeDown
+            }),
+        _ => None
+        });
+
+    match unsupported {
+        Some(inst) => Err(CompileError::Unsupported(inst.symbol())),
+        None => Ok(ir)
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::codegen::*;
+
+    #[test]
+    fn lower_checked_basic() {
+        let instr = eval_instr("+++-->><[-].,")
+            .expect("Unreachable");
+        let ir = lower_checked(&instr, OptLevel::O3)
+            .expect("Unreachable");
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::SetAt(1, 0),
+            IrOp::Move(1),
+            IrOp::Output,
+            IrOp::Input
+            ]);
+        }
+
+    #[test]
+    fn lower_checked_unsupported() {
+        let instr = eval_instr_with("?", &[Dialect::ExtRand])
+            .expect("Unreachable");
+
+        assert_eq!(lower_checked(&instr, OptLevel::O3), Err(CompileError::Unsupported('?')));
+
+        let instr = eval_instr_with("+#", &[Dialect::ExtDebug])
+            .expect("Unreachable");
+
+        assert_eq!(lower_checked(&instr, OptLevel::O3), Err(CompileError::Unsupported('#')));
+
+        let instr = eval_instr_with("+:", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        assert_eq!(lower_checked(&instr, OptLevel::O3), Err(CompileError::Unsupported(':')));
+
+        let instr = eval_instr_with("+@", &[Dialect::ExtInspect])
+            .expect("Unreachable");
+
+        assert_eq!(lower_checked(&instr, OptLevel::O3), Err(CompileError::Unsupported('@')));
+
+        let instr = eval_instr_with("+v", &[Dialect::MultiTape])
+            .expect("Unreachable");
+
+        assert_eq!(lower_checked(&instr, OptLevel::O3), Err(CompileError::Unsupported('v')));
+        }
+    }
\ No newline at end of file
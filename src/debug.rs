@@ -0,0 +1,154 @@
+use {
+    anyhow::Result as DynResult,
+    std::io::{
+        stdin,
+        stderr,
+        BufRead,
+        Write
+        },
+    braincooker::*
+    };
+
+
+/* Number of cells shown on each side of the pointer, when no range was given */
+const TAPE_RADIUS: isize = 5;
+
+const HELP: &str = "\
+Commands:
+  s, step [N]         execute the next N instruction(s) [default: 1]
+  c, continue         run until the next breakpoint, or the end
+  b, break [OFFSET]   set a breakpoint before the instruction, or list them
+  d, delete OFFSET    remove the breakpoint
+  t, tape [FROM TO]   print the cells in the range [default: around the pointer]
+  w, where            print the current instruction, and the pointer
+  h, help             print this message
+  q, quit             stop debugging";
+
+
+/* Function for driving the debugger with the commands read from the terminal - its messages go to stderr, so the output stays the program's */
+pub fn run_debugger(mut debugger: Debugger, instr: &InstructionSet) -> DynResult<()> {
+    let mut line = String::new();
+
+    eprintln!("Debugging {} instruction(s), type `help` for the commands", instr.len());
+    report_location(&debugger, instr);
+
+    loop {
+        eprint!("(bcdb) ");
+        stderr().flush()?;
+
+        /* End of the commands stops debugging */
+        line.clear();
+        if stdin().lock().read_line(&mut line)? == 0 {
+            eprintln!();
+            return Ok(());
+            }
+
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+            };
+        let args: Vec<&str> = words.collect();
+
+        match (command, args.as_slice()) {
+            ("s" | "step", [] | [_]) => {
+                let Some(times) = parse_arg::<u64>(args.first().copied().unwrap_or("1")) else {
+                    continue;
+                    };
+
+                for _ in 0 .. times {
+                    if ! debugger.step()? {
+                        break;
+                        }
+                    }
+
+                report_location(&debugger, instr);
+                },
+            ("c" | "continue", []) => {
+                if let StopReason::Breakpoint(location) = debugger.resume()? {
+                    eprintln!("Breakpoint hit at: {location}");
+                    }
+
+                report_location(&debugger, instr);
+                },
+            ("b" | "break", []) => {
+                let locations: Vec<String> = debugger.breakpoints()
+                    .map(|location| location.to_string())
+                    .collect();
+
+                match locations.is_empty() {
+                    true => eprintln!("No breakpoints are set"),
+                    false => eprintln!("Breakpoints: {}", locations.join(", "))
+                    }
+                },
+            ("b" | "break", [location]) => if let Some(location) = parse_arg(location) {
+                match debugger.add_breakpoint(location) {
+                    true => eprintln!("Breakpoint set at: {location}"),
+                    false => eprintln!("Breakpoint can't be set at: {location}")
+                    }
+                },
+            ("d" | "delete", [location]) => if let Some(location) = parse_arg(location) {
+                match debugger.remove_breakpoint(location) {
+                    true => eprintln!("Breakpoint removed from: {location}"),
+                    false => eprintln!("No breakpoint at: {location}")
+                    }
+                },
+            ("t" | "tape", []) => {
+                let position = debugger.position();
+                print_cells(&mut debugger, position - TAPE_RADIUS, position + TAPE_RADIUS);
+                },
+            ("t" | "tape", [from, to]) => if let (Some(from), Some(to)) = (parse_arg(from), parse_arg(to)) {
+                print_cells(&mut debugger, from, to);
+                },
+            ("w" | "where", []) =>
+                report_location(&debugger, instr),
+            ("h" | "help", []) =>
+                eprintln!("{HELP}"),
+            ("q" | "quit", []) =>
+                return Ok(()),
+            _ =>
+                eprintln!("Unknown command, or wrong arguments: {}, type `help` for the commands", line.trim())
+            }
+        }
+    }
+
+/* Function for printing the next instruction, or the end of the program */
+fn report_location(debugger: &Debugger, instr: &InstructionSet) {
+    match debugger.is_finished() {
+        true => eprintln!("Program finished after {} executed instruction(s)", debugger.executed()),
+        false => {
+            let location = debugger.location();
+
+            eprintln!("At instruction {location} '{}', pointer at: {}", instr[location].symbol(), debugger.position());
+            }
+        }
+    }
+
+/* Function for printing the cells in the range, with the pointer marked */
+fn print_cells(debugger: &mut Debugger, from: isize, to: isize) {
+    let position = debugger.position();
+
+    for (index, value) in debugger.cells(from, to) {
+        let marker = match index == position {
+            true => ">",
+            false => " "
+            };
+
+        /* Cells off the tape are skipped */
+        if let Some(value) = value {
+            eprintln!("{marker} {index:>6}: {value}");
+            }
+        }
+    }
+
+/* Function for parsing a command's argument, and reporting the wrong ones */
+fn parse_arg<T>(value: &str) -> Option<T>
+where T: core::str::FromStr {
+    let parsed = value.parse()
+        .ok();
+
+    if parsed.is_none() {
+        eprintln!("Invalid argument: {value}");
+        }
+
+    parsed
+    }
\ No newline at end of file
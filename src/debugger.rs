@@ -0,0 +1,297 @@
+use {
+    std::collections::BTreeSet,
+    num_bigint::BigUint,
+    crate::{
+        eval::{
+            Instruction,
+            InstructionSet
+            },
+        interp::*,
+        ir::{
+            Ir,
+            IrOp
+            }
+        }
+    };
+
+
+/* Reason the debugged run stopped at */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StopReason {
+    /* Next instruction has a breakpoint - given by its location */
+    Breakpoint(usize),
+    /* Every instruction was executed */
+    Finished
+    }
+
+/* Step debugger, driving the Interpreter one instruction at a time - locations are instruction indices */
+pub struct Debugger {
+    interp: Box<dyn InterpRun>,
+    ir: Ir,
+    op_ptr: usize,
+    executed: u64,
+    breakpoints: BTreeSet<usize>,
+    /* Whether the last step executed the `!` instruction - it stops like a breakpoint on the next one */
+    passed_break: bool
+    }
+
+impl Debugger {
+    /* Constructor function - every instruction is lowered on its own, so each one is a single step */
+    pub fn new(interp: Box<dyn InterpRun>, instr: &InstructionSet) -> Self {
+        Self {
+            interp,
+            ir: Ir::lower_unfused(instr),
+            op_ptr: 0,
+            executed: 0,
+            breakpoints: BTreeSet::new(),
+            passed_break: false
+            }
+        }
+
+    /* Getters */
+    pub fn location(&self) -> usize {
+        self.ir.source(self.op_ptr)
+        }
+    pub fn is_finished(&self) -> bool {
+        self.op_ptr >= self.ir.len()
+        }
+    pub const fn executed(&self) -> u64 {
+        self.executed
+        }
+    pub fn position(&self) -> isize {
+        self.interp.position()
+        }
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter()
+            .copied()
+        }
+    pub fn is_breakpoint(&self, location: usize) -> bool {
+        self.breakpoints.contains(&location)
+        }
+    pub const fn passed_break(&self) -> bool {
+        self.passed_break
+        }
+
+    /* Set a breakpoint before the instruction - false, when it's past the end */
+    pub fn add_breakpoint(&mut self, location: usize) -> bool {
+        location < self.ir.len() && self.breakpoints.insert(location)
+        }
+
+    /* Remove the breakpoint - false, when there was none */
+    pub fn remove_breakpoint(&mut self, location: usize) -> bool {
+        self.breakpoints.remove(&location)
+        }
+
+    /* Execute the next instruction - false, when there was none left */
+    pub fn step(&mut self) -> Result<bool, RunError> {
+        if self.is_finished() {
+            return Ok(false);
+            }
+
+        self.passed_break = self.ir[self.op_ptr] == IrOp::Break;
+        self.op_ptr = self.interp.step(&self.ir, self.op_ptr, &mut self.executed)?;
+
+        /* Show the output right away, as the run can stop at any point */
+        self.interp.flush()?;
+
+        Ok(true)
+        }
+
+    /* Execute the instructions, until the next breakpoint, the one after the `!` instruction, or the end - the current one is never a stop */
+    pub fn resume(&mut self) -> Result<StopReason, RunError> {
+        while self.step()? {
+            let location = self.location();
+
+            if (self.is_breakpoint(location) || self.passed_break) && ! self.is_finished() {
+                return Ok(StopReason::Breakpoint(location));
+                }
+            }
+
+        Ok(StopReason::Finished)
+        }
+
+    /* Get the values of the cells in the range - none, for the ones off the tape */
+    pub fn cells(&mut self, from: isize, to: isize) -> Vec<(isize, Option<BigUint>)> {
+        (from ..= to)
+            .map(|index| (index, self.interp.peek(index)))
+            .collect()
+        }
+    }
+
+
+/* Single executed instruction, with the state it left behind */
+#[derive(Clone, PartialEq, Debug)]
+pub struct StepEvent {
+    /* Location of the executed instruction */
+    pub location: usize,
+    pub instruction: Instruction,
+    /* Pointer position, and its cell's value after the instruction */
+    pub position: isize,
+    pub cell: BigUint
+    }
+
+/* Borrowing driver of the Interpreter, executing one instruction at a time - also iterates over the steps, until the end, or the first error */
+pub struct Stepper<'a> {
+    interp: &'a mut dyn InterpRun,
+    instr: &'a InstructionSet,
+    ir: Ir,
+    op_ptr: usize,
+    executed: u64
+    }
+
+impl<'a> Stepper<'a> {
+    /* Constructor function - every instruction is lowered on its own, so each one is a single step */
+    pub fn new(interp: &'a mut dyn InterpRun, instr: &'a InstructionSet) -> Self {
+        Self {
+            interp,
+            instr,
+            ir: Ir::lower_unfused(instr),
+            op_ptr: 0,
+            executed: 0
+            }
+        }
+
+    /* Getters */
+    pub fn location(&self) -> usize {
+        self.ir.source(self.op_ptr)
+        }
+    pub fn is_finished(&self) -> bool {
+        self.op_ptr >= self.ir.len()
+        }
+    pub const fn executed(&self) -> u64 {
+        self.executed
+        }
+
+    /* Execute the next instruction - none, when there was none left */
+    pub fn step(&mut self) -> Result<Option<StepEvent>, RunError> {
+        if self.is_finished() {
+            return Ok(None);
+            }
+
+        let location = self.location();
+        self.op_ptr = self.interp.step(&self.ir, self.op_ptr, &mut self.executed)?;
+        self.interp.flush()?;
+
+        let position = self.interp.position();
+
+        Ok(Some(StepEvent {
+            location,
+            instruction: self.instr[location],
+            position,
+            cell: self.interp.peek(position)
+                .unwrap_or_default()
+            }))
+        }
+    }
+
+impl Iterator for Stepper<'_> {
+    type Item = Result<StepEvent, RunError>;
+
+    /* Failed step ends the iteration, as the state is no longer consistent */
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = self.step();
+        if step.is_err() {
+            self.op_ptr = self.ir.len();
+            }
+
+        step.transpose()
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        debugger::*,
+        eval::{
+            eval_instr,
+            eval_instr_with
+            },
+        utils::{
+            Dialect,
+            SharedBuffer
+            }
+        };
+
+    fn debugger(code: &str) -> (Debugger, SharedBuffer) {
+        let instr = eval_instr(code)
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        let interp = Interpreter::builder()
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+
+        (Debugger::new(Box::new(interp), &instr), buffer)
+        }
+
+    #[test]
+    fn debugger_step() {
+        let (mut debugger, _) = debugger("++>+");
+
+        assert!(debugger.step().expect("Unreachable"));
+        assert!(debugger.step().expect("Unreachable"));
+        assert!(debugger.step().expect("Unreachable"));
+
+        assert_eq!(debugger.location(), 3);
+        assert_eq!(debugger.position(), 1);
+        assert_eq!(debugger.cells(-1, 1), vec![(-1, None), (0, Some(BigUint::from(2_u8))), (1, Some(BigUint::from(0_u8)))]);
+
+        assert!(debugger.step().expect("Unreachable"));
+        assert!(! debugger.step().expect("Unreachable"));
+        assert!(debugger.is_finished());
+        assert_eq!(debugger.executed(), 4);
+        }
+
+    #[test]
+    fn stepper_events() {
+        let instr = eval_instr("+>[-]<.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        let mut interp = Interpreter::builder()
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+        let events = interp.stepper(&instr)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Unreachable");
+
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[1], StepEvent { location: 1, instruction: Instruction::Right, position: 1, cell: BigUint::ZERO });
+        assert_eq!(events[4].location, 6);
+        assert_eq!(buffer.take(), b"1");
+        }
+
+    #[test]
+    fn debugger_breakpoints() {
+        let (mut debugger, buffer) = debugger("+++[>+<-]>.");
+
+        assert!(debugger.add_breakpoint(5));
+        assert!(! debugger.add_breakpoint(11));
+        assert!(debugger.is_breakpoint(5));
+
+        /* Breakpoint inside the loop is hit on every iteration */
+        for _ in 0 .. 3 {
+            assert_eq!(debugger.resume().expect("Unreachable"), StopReason::Breakpoint(5));
+            }
+        assert_eq!(debugger.cells(0, 1), vec![(0, Some(BigUint::from(1_u8))), (1, Some(BigUint::from(2_u8)))]);
+
+        assert!(debugger.remove_breakpoint(5));
+        assert_eq!(debugger.resume().expect("Unreachable"), StopReason::Finished);
+        assert_eq!(buffer.take(), b"3");
+        }
+
+    #[test]
+    fn debugger_inline_breakpoints() {
+        let instr = eval_instr_with("+[!-]+!", &[Dialect::ExtInspect])
+            .expect("Unreachable");
+        let interp = Interpreter::builder()
+            .build::<u8, u8>();
+        let mut debugger = Debugger::new(Box::new(interp), &instr);
+
+        assert_eq!(debugger.resume().expect("Unreachable"), StopReason::Breakpoint(3));
+        assert!(debugger.passed_break());
+        assert_eq!(debugger.resume().expect("Unreachable"), StopReason::Finished);
+        assert_eq!(debugger.cells(0, 0), vec![(0, Some(BigUint::from(1_u8)))]);
+        }
+    }
\ No newline at end of file
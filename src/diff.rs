@@ -0,0 +1,300 @@
+use {
+    core::{
+        fmt::{
+            Display,
+            Formatter,
+            Result as FmtResult
+            },
+        mem::{
+            replace,
+            take
+            }
+        },
+    crate::eval::*
+    };
+
+
+/* Normalised program tree - runs of the same instruction are merged, and loops hold their bodies */
+enum Node {
+    Run {
+        inst: Instruction,
+        count: usize,
+        at: usize
+        },
+    Loop {
+        body: Vec<Node>,
+        at: usize
+        }
+    }
+
+impl Node {
+    /* Structural equality, ignoring the locations */
+    fn same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Node::Run { inst, count, .. }, Node::Run { inst: other_inst, count: other_count, .. }) =>
+                inst == other_inst && count == other_count,
+            (Node::Loop { body, .. }, Node::Loop { body: other_body, .. }) =>
+                body.len() == other_body.len() && body.iter()
+                    .zip(other_body)
+                    .all(|(node, other)| node.same(other)),
+            _ => false
+            }
+        }
+
+    /* Whether a removed node, and an inserted one are better reported as a single change */
+    fn comparable(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Node::Run { inst, .. }, Node::Run { inst: other_inst, .. }) =>
+                inst == other_inst,
+            (Node::Loop { .. }, Node::Loop { .. }) =>
+                true,
+            _ => false
+            }
+        }
+
+    const fn at(&self) -> usize {
+        match self {
+            Node::Run { at, .. } | Node::Loop { at, .. } => *at
+            }
+        }
+
+    /* Get the source code of the node */
+    fn source(&self) -> String {
+        match self {
+            Node::Run { inst, count, .. } =>
+                inst.symbol()
+                    .to_string()
+                    .repeat(*count),
+            Node::Loop { body, .. } =>
+                format!("[{}]", body.iter()
+                    .map(Node::source)
+                    .collect::<String>()
+                    )
+            }
+        }
+    }
+
+
+/* Single structural difference - locations are indices of the instructions in the old, and the new program */
+#[derive(Clone, PartialEq, Debug)]
+pub enum Change {
+    Removed {
+        at: usize,
+        code: String
+        },
+    Inserted {
+        at: usize,
+        code: String
+        },
+    ChangedRun {
+        old_at: usize,
+        new_at: usize,
+        old: String,
+        new: String
+        },
+    ChangedLoop {
+        old_at: usize,
+        new_at: usize,
+        changes: Vec<Change>
+        }
+    }
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Change::Removed { at, code } =>
+                write!(f, "Removed at {at}: {code}"),
+            Change::Inserted { at, code } =>
+                write!(f, "Inserted at {at}: {code}"),
+            Change::ChangedRun { old_at, new_at, old, new } =>
+                write!(f, "Changed run at {old_at} -> {new_at}: {old} -> {new}"),
+            /* Changes inside of the loop are indented below it */
+            Change::ChangedLoop { old_at, new_at, changes } => {
+                write!(f, "Changed loop at {old_at} -> {new_at}:")?;
+
+                for change in changes {
+                    write!(f, "\n    {}", change.to_string().replace('\n', "\n    "))?;
+                    }
+
+                Ok(())
+                }
+            }
+        }
+    }
+
+
+/* Function for comparing two programs by their structure, rather than their text */
+pub fn diff_instr(old: &InstructionSet, new: &InstructionSet) -> Vec<Change> {
+    diff_nodes(&build_tree(old), &build_tree(new))
+    }
+
+/* Helper function, for building the normalised tree */
+fn build_tree(instr: &InstructionSet) -> Vec<Node> {
+    /* Outer levels, waiting for their loops to close */
+    let mut stack = Vec::new();
+    let mut current = Vec::new();
+
+    for i in 0 .. instr.len() {
+        match instr[i] {
+            Instruction::LoopOpen =>
+                stack.push((i, take(&mut current))),
+            Instruction::LoopClose => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some((at, outer)) = stack.pop() else {
+                    continue;
+                    };
+
+                let body = replace(&mut current, outer);
+                current.push(Node::Loop { body, at });
+                },
+            inst => match current.last_mut() {
+                Some(Node::Run { inst: last, count, .. }) if *last == inst =>
+                    *count += 1,
+                _ =>
+                    current.push(Node::Run { inst, count: 1, at: i })
+                }
+            }
+        }
+
+    current
+    }
+
+/* Helper function, for comparing a single level of the trees - longest common subsequence of the nodes */
+fn diff_nodes(old: &[Node], new: &[Node]) -> Vec<Change> {
+    /* Lengths of the common subsequences of the suffixes */
+    let mut table = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+
+    for i in (0 .. old.len()).rev() {
+        for j in (0 .. new.len()).rev() {
+            table[i][j] = match old[i].same(&new[j]) {
+                true => table[i + 1][j + 1] + 1,
+                false => table[i + 1][j].max(table[i][j + 1])
+                };
+            }
+        }
+
+    let mut output = Vec::new();
+    let (mut removed, mut inserted) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        match (old.get(i), new.get(j)) {
+            /* Flush the differences, once a common node is reached */
+            (Some(old_node), Some(new_node)) if old_node.same(new_node) => {
+                pair_changes(&mut output, take(&mut removed), take(&mut inserted));
+                i += 1;
+                j += 1;
+                },
+            (Some(old_node), _) if j == new.len() || table[i + 1][j] >= table[i][j + 1] => {
+                removed.push(old_node);
+                i += 1;
+                },
+            (_, Some(new_node)) => {
+                inserted.push(new_node);
+                j += 1;
+                },
+            /* Loop condition, and the guard above guarantee, that it's never reached */
+            _ =>
+                break
+            }
+        }
+
+    pair_changes(&mut output, removed, inserted);
+
+    output
+    }
+
+/* Helper function, for merging removed, and inserted nodes between the same common ones into changes */
+fn pair_changes(output: &mut Vec<Change>, removed: Vec<&Node>, inserted: Vec<&Node>) {
+    let mut inserted = inserted.into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    for old_node in removed {
+        /* Find the first inserted node of the same kind */
+        let pair = inserted.iter_mut()
+            .find(|new_node| new_node.is_some_and(|new_node| old_node.comparable(new_node)))
+            .and_then(Option::take);
+
+        output.push(match (old_node, pair) {
+            (Node::Run { .. }, Some(new_node)) =>
+                Change::ChangedRun {
+                    old_at: old_node.at(),
+                    new_at: new_node.at(),
+                    old: old_node.source(),
+                    new: new_node.source()
+                    },
+            (Node::Loop { body, .. }, Some(new_node @ Node::Loop { body: new_body, .. })) =>
+                Change::ChangedLoop {
+                    old_at: old_node.at(),
+                    new_at: new_node.at(),
+                    changes: diff_nodes(body, new_body)
+                    },
+            _ =>
+                Change::Removed {
+                    at: old_node.at(),
+                    code: old_node.source()
+                    }
+            });
+        }
+
+    output.extend(inserted.into_iter()
+        .flatten()
+        .map(|new_node| Change::Inserted {
+            at: new_node.at(),
+            code: new_node.source()
+            })
+        );
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::diff::*;
+
+    fn diff_str(old: &str, new: &str) -> Vec<Change> {
+        let old = eval_instr(old)
+            .expect("Unreachable");
+        let new = eval_instr(new)
+            .expect("Unreachable");
+
+        diff_instr(&old, &new)
+        }
+
+    #[test]
+    fn diff_same() {
+        /* Formatting, and comments don't matter */
+        assert!(diff_str("++[->+<]", "+ + [ - > + < ] comment").is_empty());
+        }
+
+    #[test]
+    fn diff_inserted() {
+        assert_eq!(diff_str("+.", "+.>."), vec![
+            Change::Inserted { at: 2, code: ">".to_owned() },
+            Change::Inserted { at: 3, code: ".".to_owned() }
+            ]);
+        }
+
+    #[test]
+    fn diff_removed() {
+        assert_eq!(diff_str("[-]+", "+"), vec![
+            Change::Removed { at: 0, code: "[-]".to_owned() }
+            ]);
+        }
+
+    #[test]
+    fn diff_changed_run() {
+        assert_eq!(diff_str("+++.", "++++."), vec![
+            Change::ChangedRun { old_at: 0, new_at: 0, old: "+++".to_owned(), new: "++++".to_owned() }
+            ]);
+        }
+
+    #[test]
+    fn diff_changed_loop() {
+        assert_eq!(diff_str("+[->+<].", "+[->++<]."), vec![
+            Change::ChangedLoop { old_at: 1, new_at: 1, changes: vec![
+                Change::ChangedRun { old_at: 4, new_at: 4, old: "+".to_owned(), new: "++".to_owned() }
+                ] }
+            ]);
+        }
+    }
\ No newline at end of file
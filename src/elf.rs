@@ -0,0 +1,169 @@
+use crate::codegen::*;
+
+
+/* Address the code gets loaded at - headers come first, so the code starts right after them */
+pub const CODE_ADDRESS: u64 = 0x40_0000;
+/* Address of the zero-initialised memory */
+pub const BSS_ADDRESS: u64 = 0x1000_0000;
+
+/* Machine identifiers */
+pub const MACHINE_X86_64: u16 = 0x3E;
+pub const MACHINE_AARCH64: u16 = 0xB7;
+pub const MACHINE_RISCV: u16 = 0xF3;
+
+/* Sizes of the headers */
+const HEADER_SIZE: u64 = 64;
+const SEGMENT_SIZE: u64 = 56;
+const SEGMENTS: u64 = 2;
+const SECTION_SIZE: u64 = 64;
+const SECTIONS: u64 = 4;
+
+/* Alignments of the code, the zero-initialised memory, and the section headers */
+const CODE_ALIGN: u64 = 16;
+const BSS_ALIGN: u64 = 16;
+const SECTION_ALIGN: u64 = 8;
+
+/* Offset of the code in the file - right after the headers, aligned */
+const CODE_OFFSET: u64 = (HEADER_SIZE + SEGMENTS * SEGMENT_SIZE).next_multiple_of(CODE_ALIGN);
+
+/* Names of the sections, and their offsets in the string table */
+const SECTION_NAMES: &[u8] = b"\0.text\0.bss\0.shstrtab\0";
+const TEXT_NAME: u32 = 1;
+const BSS_NAME: u32 = 7;
+const SHSTRTAB_NAME: u32 = 12;
+
+
+/* Function for getting the address of an offset in the code */
+pub const fn code_address(offset: u64) -> u64 {
+    CODE_ADDRESS + CODE_OFFSET + offset
+    }
+
+/* Placement of the executable in memory, for the code generation */
+pub(crate) const LAYOUT: Layout = Layout {
+    platform: Platform::Linux,
+    code_address: code_address(0),
+    bss_address: BSS_ADDRESS
+    };
+
+/* Function for wrapping the code into a static ELF64 executable - one segment with the code, and one with the zero-initialised memory, described by the sections for the tools */
+pub fn executable(machine: u16, code: &[u8], entry: u64, bss_size: u64) -> Vec<u8> {
+    let code_end = CODE_OFFSET + code.len() as u64;
+    let sections_offset = (code_end + SECTION_NAMES.len() as u64).next_multiple_of(SECTION_ALIGN);
+    let file_size = sections_offset + SECTIONS * SECTION_SIZE;
+
+    let mut output = Vec::with_capacity(file_size as usize);
+
+    /* Identification - 64-bit, little-endian, current version, System V */
+    output.extend_from_slice(b"\x7FELF");
+    output.extend_from_slice(&[2, 1, 1, 0]);
+    output.extend_from_slice(&[0; 8]);
+
+    /* Executable file type, machine, version, and the entry point */
+    output.extend_from_slice(&2_u16.to_le_bytes());
+    output.extend_from_slice(&machine.to_le_bytes());
+    output.extend_from_slice(&1_u32.to_le_bytes());
+    output.extend_from_slice(&code_address(entry).to_le_bytes());
+
+    /* Locations of the program, and section headers, and no flags */
+    output.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+    output.extend_from_slice(&sections_offset.to_le_bytes());
+    output.extend_from_slice(&0_u32.to_le_bytes());
+
+    /* Sizes, and numbers of the headers, and the section with their names */
+    output.extend_from_slice(&(HEADER_SIZE as u16).to_le_bytes());
+    output.extend_from_slice(&(SEGMENT_SIZE as u16).to_le_bytes());
+    output.extend_from_slice(&(SEGMENTS as u16).to_le_bytes());
+    output.extend_from_slice(&(SECTION_SIZE as u16).to_le_bytes());
+    output.extend_from_slice(&(SECTIONS as u16).to_le_bytes());
+    output.extend_from_slice(&(SECTIONS as u16 - 1).to_le_bytes());
+
+    /* Readable, and executable code, loaded together with the headers */
+    push_segment(&mut output, 0b101, 0, CODE_ADDRESS, code_end, code_end);
+    /* Readable, and writable memory, not backed by the file */
+    push_segment(&mut output, 0b110, 0, BSS_ADDRESS, 0, bss_size);
+
+    output.resize(CODE_OFFSET as usize, 0);
+    output.extend_from_slice(code);
+    output.extend_from_slice(SECTION_NAMES);
+    output.resize(sections_offset as usize, 0);
+
+    /* Mandatory empty section, code, zero-initialised memory, and the names */
+    output.extend_from_slice(&[0; SECTION_SIZE as usize]);
+    let sections = [
+        Section { name: TEXT_NAME, kind: 1, flags: 0b110, address: code_address(0), offset: CODE_OFFSET, size: code.len() as u64, align: CODE_ALIGN },
+        Section { name: BSS_NAME, kind: 8, flags: 0b011, address: BSS_ADDRESS, offset: code_end, size: bss_size, align: BSS_ALIGN },
+        Section { name: SHSTRTAB_NAME, kind: 3, flags: 0, address: 0, offset: code_end, size: SECTION_NAMES.len() as u64, align: 1 }
+        ];
+    for section in sections {
+        section.push(&mut output);
+        }
+
+    output
+    }
+
+/* Helper function, for writing a loadable segment's header */
+fn push_segment(output: &mut Vec<u8>, flags: u32, offset: u64, address: u64, file_size: u64, memory_size: u64) {
+    output.extend_from_slice(&1_u32.to_le_bytes());
+    output.extend_from_slice(&flags.to_le_bytes());
+    output.extend_from_slice(&offset.to_le_bytes());
+    output.extend_from_slice(&address.to_le_bytes());
+    output.extend_from_slice(&address.to_le_bytes());
+    output.extend_from_slice(&file_size.to_le_bytes());
+    output.extend_from_slice(&memory_size.to_le_bytes());
+    output.extend_from_slice(&0x1000_u64.to_le_bytes());
+    }
+
+
+/* Section's header - without links, or fixed-size entries */
+struct Section {
+    name: u32,
+    kind: u32,
+    flags: u64,
+    address: u64,
+    offset: u64,
+    size: u64,
+    align: u64
+    }
+
+impl Section {
+    /* Write the header */
+    fn push(&self, output: &mut Vec<u8>) {
+        output.extend_from_slice(&self.name.to_le_bytes());
+        output.extend_from_slice(&self.kind.to_le_bytes());
+        output.extend_from_slice(&self.flags.to_le_bytes());
+        output.extend_from_slice(&self.address.to_le_bytes());
+        output.extend_from_slice(&self.offset.to_le_bytes());
+        output.extend_from_slice(&self.size.to_le_bytes());
+        output.extend_from_slice(&[0; 8]);
+        output.extend_from_slice(&self.align.to_le_bytes());
+        output.extend_from_slice(&0_u64.to_le_bytes());
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::elf::*;
+
+    #[test]
+    fn elf_layout() {
+        let code = [0xC3];
+        let output = executable(MACHINE_X86_64, &code, 0, 16);
+
+        assert_eq!(&output[.. 4], b"\x7FELF");
+        assert_eq!(output[CODE_OFFSET as usize], 0xC3);
+        assert_eq!(CODE_OFFSET % CODE_ALIGN, 0);
+        assert_eq!(output.len() % SECTION_ALIGN as usize, 0);
+        }
+
+    #[test]
+    fn elf_section_names() {
+        let name = |offset: u32| SECTION_NAMES[offset as usize ..]
+            .split(|&byte| byte == 0)
+            .next();
+
+        assert_eq!(name(TEXT_NAME), Some(&b".text"[..]));
+        assert_eq!(name(BSS_NAME), Some(&b".bss"[..]));
+        assert_eq!(name(SHSTRTAB_NAME), Some(&b".shstrtab"[..]));
+        }
+    }
\ No newline at end of file
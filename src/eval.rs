@@ -1,16 +1,50 @@
 use {
     thiserror::Error,
-    std::collections::HashMap,
+    alloc::{
+        boxed::Box,
+        collections::BTreeMap,
+        string::{
+            String,
+            ToString
+            },
+        vec,
+        vec::{
+            IntoIter,
+            Vec
+            }
+        },
     core::{
+        cmp::Reverse,
+        fmt::{
+            Display,
+            Formatter,
+            Result as FmtResult,
+            Write
+            },
         hint::unreachable_unchecked,
-        ops::Index
+        ops::Index,
+        slice::Iter,
+        str::FromStr
         },
-    crate::rle::*,
+    crate::{
+        rle::*,
+        utils::{
+            CommentMode,
+            Dialect
+            }
+        }
+    };
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize
     };
 
 
 /* Language instruction set */
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Instruction {
     Right,
     Left,
@@ -19,7 +53,47 @@ pub enum Instruction {
     LoopOpen,
     LoopClose,
     Output,
-    Input
+    Input,
+    Random,
+    Dump,
+    /* Procedure's definition, for the current cell's value, and its end */
+    ProcStart,
+    ProcEnd,
+    /* Call of the procedure defined for the current cell's value */
+    ProcCall,
+    /* Breakpoint, and the prints of the pointer, and the cells around it */
+    Breakpoint,
+    ShowPointer,
+    ShowTape,
+    /* Switch to the previous, or the next tape */
+    TapeUp,
+    TapeDown
+    }
+
+impl Instruction {
+    /* Get the character representing the instruction in the source code */
+    pub const fn symbol(self) -> char {
+        match self {
+            Instruction::Right => '>',
+            Instruction::Left => '<',
+            Instruction::Increment => '+',
+            Instruction::Decrement => '-',
+            Instruction::LoopOpen => '[',
+            Instruction::LoopClose => ']',
+            Instruction::Output => '.',
+            Instruction::Input => ',',
+            Instruction::Random => '?',
+            Instruction::Dump => '#',
+            Instruction::ProcStart => '(',
+            Instruction::ProcEnd => ')',
+            Instruction::ProcCall => ':',
+            Instruction::Breakpoint => '!',
+            Instruction::ShowPointer => '@',
+            Instruction::ShowTape => '$',
+            Instruction::TapeUp => '^',
+            Instruction::TapeDown => 'v'
+            }
+        }
     }
 
 /* Evaluation's result output type */
@@ -30,67 +104,368 @@ pub enum EvalError {
     #[error("Unnecessery loop closing was found at: {0}")]
     UnnecesseryBracket(usize),
     #[error("Unclosed loop(s) was(were) found in number of: {0}")]
-    UnclosedBracket(u16)
+    UnclosedBracket(u16),
+    #[error("Unknown character {0:?} was found at: {1}")]
+    UnknownCharacter(char, usize),
+    #[error("Unknown, or unpaired Ook! instruction was found at: {0}")]
+    UnknownOok(usize),
+    #[error("Procedure defined inside another one was found at: {0}")]
+    NestedProcedure(usize),
+    #[error("Unnecessery procedure closing was found at: {0}")]
+    UnnecesseryParenthesis(usize),
+    #[error("Unclosed procedure was found at: {0}")]
+    UnclosedProcedure(usize),
+    #[error("Loop crossing the procedure's boundary was found at: {0}")]
+    ProcedureLoop(usize)
+    }
+
+/* Map of the instructions' indices to the indices of their characters in the source code */
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SourceMap(Vec<usize>);
+
+impl SourceMap {
+    /* Get the index of the instruction's character - none, for the instructions past the end */
+    pub fn offset(&self, index: usize) -> Option<usize> {
+        self.0.get(index)
+            .copied()
+        }
+
+    /* Get the line, and the column of the instruction - both counted from one */
+    pub fn location(&self, instr_str: &str, index: usize) -> Option<(usize, usize)> {
+        self.offset(index)
+            .map(|offset| source_location(instr_str, offset))
+        }
+
+    /* Drop the positions of the first instructions - for keeping the map in line with the pruned instruction set */
+    pub fn skip(&mut self, count: usize) {
+        self.0.drain(.. count.min(self.0.len()));
+        }
+
+    /* Getters */
+    pub fn len(&self) -> usize {
+        self.0.len()
+        }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+        }
+    }
+
+/* Token map's parsing result output type - lines are counted from one */
+#[derive(PartialEq, Debug, Error)]
+pub enum TokenMapError {
+    #[error("Line {0} doesn't hold an instruction, followed by its token")]
+    InvalidLine(usize),
+    #[error("Instruction '{0}' was given more than one token")]
+    Duplicate(char),
+    #[error("Instruction '{0}' wasn't given a token")]
+    Missing(char),
+    #[error("Token {0:?} stands for more than one instruction")]
+    Ambiguous(String)
+    }
+
+/* Instructions of the language, without the extensions */
+const BASIC_INSTRUCTIONS: [Instruction; 8] = [
+    Instruction::Right,
+    Instruction::Left,
+    Instruction::Increment,
+    Instruction::Decrement,
+    Instruction::LoopOpen,
+    Instruction::LoopClose,
+    Instruction::Output,
+    Instruction::Input
+    ];
+
+/* Tokens standing for the instructions, in the languages which only substitute them - kept from the longest, so the longest one matching wins */
+#[derive(Clone, PartialEq, Debug)]
+pub struct TokenMap(Vec<(String, Instruction)>);
+
+impl FromStr for TokenMap {
+    type Err = TokenMapError;
+
+    /* Parsing of the specification - every non-empty line holds the instruction's symbol, and its token after the whitespace */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens: Vec<(String, Instruction)> = Vec::with_capacity(BASIC_INSTRUCTIONS.len());
+
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+                }
+
+            let mut chars = line.chars();
+            let inst = chars.next()
+                .and_then(|chr| BASIC_INSTRUCTIONS.into_iter().find(|inst| inst.symbol() == chr))
+                .ok_or(TokenMapError::InvalidLine(n + 1))?;
+            let token = chars.as_str();
+
+            /* Symbol has to be separated from its token */
+            match token.trim_start() {
+                trimmed if trimmed.is_empty() || trimmed.len() == token.len() =>
+                    return Err(TokenMapError::InvalidLine(n + 1)),
+                trimmed => {
+                    if tokens.iter().any(|&(_, other)| other == inst) {
+                        return Err(TokenMapError::Duplicate(inst.symbol()));
+                        }
+                    if tokens.iter().any(|(other, _)| other == trimmed) {
+                        return Err(TokenMapError::Ambiguous(trimmed.to_string()));
+                        }
+
+                    tokens.push((trimmed.to_string(), inst));
+                    }
+                }
+            }
+
+        if let Some(inst) = BASIC_INSTRUCTIONS.into_iter().find(|&inst| ! tokens.iter().any(|&(_, other)| other == inst)) {
+            return Err(TokenMapError::Missing(inst.symbol()));
+            }
+
+        tokens.sort_by_key(|(token, _)| Reverse(token.len()));
+
+        Ok(Self(tokens))
+        }
+    }
+
+impl EvalError {
+    /* Get the index of the character the error points at - for the unclosed loops, it's the outermost one's opening, and none, when the source code doesn't have it */
+    pub fn location(&self, instr_str: &str) -> Option<usize> {
+        match *self {
+            EvalError::LoopOverload(i) | EvalError::UnnecesseryBracket(i) | EvalError::UnknownCharacter(_, i) | EvalError::UnknownOok(i) |
+            EvalError::NestedProcedure(i) | EvalError::UnnecesseryParenthesis(i) | EvalError::UnclosedProcedure(i) | EvalError::ProcedureLoop(i) =>
+                Some(i),
+            EvalError::UnclosedBracket(_) => {
+                let mut loop_stack = Vec::new();
+
+                for (i, chr) in instr_str.chars().enumerate() {
+                    match chr {
+                        '[' => loop_stack.push(i),
+                        ']' => drop(loop_stack.pop()),
+                        _ => ()
+                        }
+                    }
+
+                loop_stack.first()
+                    .copied()
+                }
+            }
+        }
+    }
+
+/* Function for getting the line, and the column of the character at the index - both counted from one */
+pub fn source_location(instr_str: &str, index: usize) -> (usize, usize) {
+    instr_str.chars()
+        .take(index)
+        .fold((1, 1), |(line, column), chr| match chr {
+            '\n' => (line + 1, 1),
+            _ => (line, column + 1)
+            })
     }
 
 /* Function for evaluation, checking, sanitisation of provided instructions */
+#[inline]
 pub fn eval_instr(instr_str: &str) -> Result<InstructionSet, EvalError> {
-    let mut output = Vec::with_capacity(instr_str.len());
-    /* Increments for loop opening, decrements for loop closing */
-    let mut loop_count: u16 = 0;
+    eval_instr_with(instr_str, &[])
+    }
+
+/* Function for evaluation, with additional language extensions enabled */
+#[inline]
+pub fn eval_instr_with(instr_str: &str, dialects: &[Dialect]) -> Result<InstructionSet, EvalError> {
+    eval_instr_mapped(instr_str, dialects)
+        .map(|(instr, _)| instr)
+    }
+
+/* Function for evaluation, also giving back the instructions' positions in the source code */
+#[inline]
+pub fn eval_instr_mapped(instr_str: &str, dialects: &[Dialect]) -> Result<(InstructionSet, SourceMap), EvalError> {
+    eval_instr_strict(instr_str, dialects, CommentMode::Any)
+    }
+
+/* Function for evaluation, with the characters outside the instructions restricted by the mode - the unknown ones are errors, instead of comments */
+pub fn eval_instr_strict(instr_str: &str, dialects: &[Dialect], comments: CommentMode) -> Result<(InstructionSet, SourceMap), EvalError> {
+    /* Check which extensions are enabled */
+    let ext_rand = dialects.contains(&Dialect::ExtRand);
+    let ext_debug = dialects.contains(&Dialect::ExtDebug);
+    let pbrain = dialects.contains(&Dialect::Pbrain);
+    let ext_inspect = dialects.contains(&Dialect::ExtInspect);
+    let multi_tape = dialects.contains(&Dialect::MultiTape);
 
     /* Iterate over the characters, and indices */
-    for (i, chr) in instr_str.chars().enumerate() {
-        /* Discard if character is not correct */
-        let inst = match chr {
-            '>' => Instruction::Right,
-            '<' => Instruction::Left,
-            '+' => Instruction::Increment,
-            '-' => Instruction::Decrement,
-            '[' => {
-                /* Check for loop start */
-                loop_count = loop_count
-                    .checked_add(1)
-                    .ok_or(EvalError::LoopOverload(i))?;
-                
-                Instruction::LoopOpen
-                },
-            ']' => {
-                /* Check for loop end */
-                loop_count = loop_count
-                    .checked_sub(1)
-                    .ok_or(EvalError::UnnecesseryBracket(i))?;
+    let tokens = instr_str.chars()
+        .enumerate()
+        .filter_map(|(i, chr)| {
+            /* Discard if character is not correct */
+            let inst = match chr {
+                '>' => Instruction::Right,
+                '<' => Instruction::Left,
+                '+' => Instruction::Increment,
+                '-' => Instruction::Decrement,
+                '[' => Instruction::LoopOpen,
+                ']' => Instruction::LoopClose,
+                '.' => Instruction::Output,
+                ',' => Instruction::Input,
+                '?' if ext_rand => Instruction::Random,
+                '#' if ext_debug => Instruction::Dump,
+                '(' if pbrain => Instruction::ProcStart,
+                ')' if pbrain => Instruction::ProcEnd,
+                ':' if pbrain => Instruction::ProcCall,
+                '!' if ext_inspect => Instruction::Breakpoint,
+                '@' if ext_inspect => Instruction::ShowPointer,
+                '$' if ext_inspect => Instruction::ShowTape,
+                '^' if multi_tape => Instruction::TapeUp,
+                'v' if multi_tape => Instruction::TapeDown,
+                _ => return match comments {
+                    CommentMode::Any => None,
+                    CommentMode::Whitespace if chr.is_whitespace() => None,
+                    _ => Some(Err(EvalError::UnknownCharacter(chr, i)))
+                    }
+                };
+
+            Some(Ok((i, inst)))
+            });
+
+    collect_instr(tokens, instr_str.len())
+    }
 
-                Instruction::LoopClose
+/* Function for evaluation of the languages substituting the instructions with other tokens - anything, which isn't a token, is a comment, restricted by the mode */
+pub fn eval_tokens(instr_str: &str, map: &TokenMap, comments: CommentMode) -> Result<(InstructionSet, SourceMap), EvalError> {
+    let mut tokens = Vec::new();
+    let mut rest = instr_str;
+    let mut i = 0;
+
+    while let Some(chr) = rest.chars().next() {
+        match map.0.iter().find(|(token, _)| rest.starts_with(token.as_str())) {
+            Some((token, inst)) => {
+                tokens.push(Ok((i, *inst)));
+                i += token.chars().count();
+                rest = &rest[token.len() ..];
                 },
-            '.' => Instruction::Output,
-            ',' => Instruction::Input,
-            _ => continue
+            None => {
+                match comments {
+                    CommentMode::Any => (),
+                    CommentMode::Whitespace if chr.is_whitespace() => (),
+                    /* Instructions before it still get checked first */
+                    _ => {
+                        tokens.push(Err(EvalError::UnknownCharacter(chr, i)));
+                        break;
+                        }
+                    };
+
+                i += 1;
+                rest = &rest[chr.len_utf8() ..];
+                }
+            }
+        }
+
+    let capacity = tokens.len();
+    collect_instr(tokens.into_iter(), capacity)
+    }
+
+/* Nesting of the loops, and the procedures, followed one instruction at a time - procedures can't be nested, and the loops can't cross their boundaries */
+#[derive(Default)]
+struct Nesting {
+    /* Increments for loop opening, decrements for loop closing */
+    loop_count: u16,
+    /* Open procedure's location, with the loop count at its start */
+    procedure: Option<(usize, u16)>
+    }
+
+impl Nesting {
+    /* Follow the instruction at the location */
+    fn step(&mut self, i: usize, inst: Instruction) -> Result<(), EvalError> {
+        match (inst, self.procedure) {
+            /* Check for loop start */
+            (Instruction::LoopOpen, _) => self.loop_count = self.loop_count
+                .checked_add(1)
+                .ok_or(EvalError::LoopOverload(i))?,
+            /* Check for loop end - the loops opened before the procedure can't end inside it */
+            (Instruction::LoopClose, Some((_, outer))) if self.loop_count == outer =>
+                return Err(EvalError::ProcedureLoop(i)),
+            (Instruction::LoopClose, _) => self.loop_count = self.loop_count
+                .checked_sub(1)
+                .ok_or(EvalError::UnnecesseryBracket(i))?,
+            /* Check for procedure start, and end */
+            (Instruction::ProcStart, None) =>
+                self.procedure = Some((i, self.loop_count)),
+            (Instruction::ProcStart, Some(_)) =>
+                return Err(EvalError::NestedProcedure(i)),
+            (Instruction::ProcEnd, Some((_, outer))) if self.loop_count == outer =>
+                self.procedure = None,
+            (Instruction::ProcEnd, Some(_)) =>
+                return Err(EvalError::ProcedureLoop(i)),
+            (Instruction::ProcEnd, None) =>
+                return Err(EvalError::UnnecesseryParenthesis(i)),
+            _ => ()
             };
 
-        /* Add the instruction to the list */
-        output.push(inst);
+        Ok(())
         }
 
-    /* Check for any other unmatched brackets */
-    if loop_count != 0 {
-        return Err(EvalError::UnclosedBracket(loop_count));
+    /* Check for any other unmatched brackets, or parentheses */
+    fn finish(self) -> Result<(), EvalError> {
+        match (self.loop_count, self.procedure) {
+            (0, None) => Ok(()),
+            (0, Some((start, _))) => Err(EvalError::UnclosedProcedure(start)),
+            (count, _) => Err(EvalError::UnclosedBracket(count))
+            }
+        }
+    }
+
+/* Function for checking the loops, and the procedures of the tokenised instructions, and collecting them with their positions - shared by every frontend */
+pub(crate) fn collect_instr(tokens: impl Iterator<Item = Result<(usize, Instruction), EvalError>>, capacity: usize) -> Result<(InstructionSet, SourceMap), EvalError> {
+    let mut output = Vec::with_capacity(capacity);
+    let mut positions = Vec::with_capacity(capacity);
+    let mut nesting = Nesting::default();
+
+    for token in tokens {
+        let (i, inst) = token?;
+        nesting.step(i, inst)?;
+
+        /* Add the instruction to the list, with its position */
+        output.push(inst);
+        positions.push(i);
         }
 
-    /* Resize the list for space saving */
+    nesting.finish()?;
+
+    /* Resize the lists for space saving */
     output.shrink_to_fit();
+    positions.shrink_to_fit();
 
     /* Final product */
-    Ok(InstructionSet(output))
+    Ok((InstructionSet(output), SourceMap(positions)))
+    }
+
+/* Function for checking whether the loops, and the procedures of already parsed instructions are balanced - every run counts as its number of instructions, and the errors point at the runs */
+pub(crate) fn check_loops(runs: impl Iterator<Item = (u16, Instruction)>) -> Result<(), EvalError> {
+    let mut nesting = Nesting::default();
+
+    for (i, (count, inst)) in runs.enumerate() {
+        for _ in 0 .. count {
+            nesting.step(i, inst)?;
+            }
+        }
+
+    nesting.finish()
     }
 
 
-/* Container for sanitised instructions */
+/* Container for sanitised instructions - deserialised ones are checked the same way, as the parsed ones */
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "Vec<Instruction>"))]
 pub struct InstructionSet (
     Vec<Instruction>
     );
 
+impl TryFrom<Vec<Instruction>> for InstructionSet {
+    type Error = EvalError;
+
+    /* Conversion from the instructions, which have to keep their loops balanced */
+    fn try_from(value: Vec<Instruction>) -> Result<Self, Self::Error> {
+        check_loops(value.iter().map(|&inst| (1, inst)))?;
+
+        Ok(InstructionSet(value))
+        }
+    }
+
 impl Index<usize> for InstructionSet {
     type Output = Instruction;
 
@@ -100,7 +475,47 @@ impl Index<usize> for InstructionSet {
         }
     }
 
+impl Display for InstructionSet {
+    /* Written back as the source code - only the instructions, without the comments */
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.0.iter()
+            .try_for_each(|inst| f.write_char(inst.symbol()))
+        }
+    }
+
+impl FromStr for InstructionSet {
+    type Err = EvalError;
+
+    /* Parsing of the source code, without any language extensions */
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        eval_instr(s)
+        }
+    }
+
+impl IntoIterator for InstructionSet {
+    type Item = Instruction;
+    type IntoIter = IntoIter<Instruction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+        }
+    }
+
+impl<'a> IntoIterator for &'a InstructionSet {
+    type Item = &'a Instruction;
+    type IntoIter = Iter<'a, Instruction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+        }
+    }
+
 impl InstructionSet {
+    /* Get the instructions back as the source code */
+    pub fn to_source(&self) -> String {
+        self.to_string()
+        }
+
     /* Get number of instructions */
     #[inline]
     pub const fn len(&self) -> usize {
@@ -112,6 +527,22 @@ impl InstructionSet {
         self.0.is_empty()
         }
 
+    /* Get the instructions, in order */
+    #[inline]
+    pub const fn as_slice(&self) -> &[Instruction] {
+        self.0.as_slice()
+        }
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, Instruction> {
+        self.0.iter()
+        }
+
+    /* Get a stable hash of the instructions - FNV-1a over the instruction kinds */
+    pub fn fingerprint(&self) -> u64 {
+        self.0.iter()
+            .fold(0xCBF2_9CE4_8422_2325, |hash, &inst| (hash ^ inst as u64).wrapping_mul(0x0100_0000_01B3))
+        }
+
     /* Function for prunning an optional, "comment loop" that can be created on first instruction */
     pub fn prune_comment_loop(&mut self) -> bool {
         /* Early return */
@@ -165,25 +596,20 @@ impl InstructionSet {
         count
         }
 
-    /* Function for building a jump table based on loop openings, and closings */
+    /* Function for building a jump table based on loop openings, and closings, and the procedures' bounds - every instruction has its entry, so jumps are a plain load */
     pub fn build_jump_table(&self) -> JumpTable {
-        let mut output = HashMap::new();
+        let mut output = vec![0; self.len()];
 
-        /* Early return */
-        if self.is_empty() {
-            return JumpTable(output);
-            }
-
-        /* Stack for loop openings */
+        /* Stack for loop openings, and procedure starts - they don't cross each other */
         let mut loop_stack = Vec::new();
 
         /* Iterate over instructions, and indices */
         for (i, inst) in self.0.iter().enumerate() {
             match inst {
-                Instruction::LoopOpen =>
+                Instruction::LoopOpen | Instruction::ProcStart =>
                     /* Push loop opening index */
                     loop_stack.push(i),
-                Instruction::LoopClose => {
+                Instruction::LoopClose | Instruction::ProcEnd => {
                     /* Get coresponding index, current index */
                     /* Unsafe note - unwrap is safe, because the instruction set was sanitised during evaluation */
                     let (start, end) = {
@@ -195,19 +621,16 @@ impl InstructionSet {
                         (unwrapped, i)
                         };
 
-                    /* Push jumps - opening <-> closing */
-                    output.insert(start, end);
-                    output.insert(end, start);                    
+                    /* Store jumps - opening <-> closing */
+                    output[start] = end;
+                    output[end] = start;
                     },
                 _ => continue
                 }
             }
 
-        /* Resize the map for space saving */
-        output.shrink_to_fit();
-
         /* Final product */
-        JumpTable(output)
+        JumpTable(output.into_boxed_slice())
         }
 
     /* Function for compressing the Instruction Set */
@@ -251,9 +674,10 @@ impl InstructionSet {
     }
 
 
-/* Container for a jump table, based on provided instructions */
+/* Container for a jump table, based on provided instructions - indexed by the instructions' locations, with the brackets' entries holding their other ends */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct JumpTable (
-    HashMap<usize, usize>
+    Box<[usize]>
     );
 
 impl Index<usize> for JumpTable {
@@ -261,7 +685,63 @@ impl Index<usize> for JumpTable {
 
     /* Index access operation */
     fn index(&self, index: usize) -> &Self::Output {
-        &self.0[&index]
+        &self.0[index]
+        }
+    }
+
+/* Procedures defined during the run, by the cell's value they were defined for, and the calls waiting for them to return - the locations are up to the runner */
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ProcedureTable {
+    procedures: BTreeMap<u64, usize>,
+    calls: Vec<usize>
+    }
+
+impl ProcedureTable {
+    /* Define the procedure starting at the location - the previous one for the value gets replaced */
+    pub fn define(&mut self, value: u64, start: usize) {
+        self.procedures.insert(value, start);
+        }
+
+    /* Call the procedure defined for the value, remembering the location of the call - none, when there's no such procedure */
+    pub fn call(&mut self, value: u64, from: usize) -> Option<usize> {
+        let start = self.procedures.get(&value)
+            .copied()?;
+        self.calls.push(from);
+
+        Some(start)
+        }
+
+    /* Return from the last call - none, when there's no call waiting */
+    pub fn ret(&mut self) -> Option<usize> {
+        self.calls.pop()
+        }
+
+    /* Forget every procedure, and call */
+    pub fn clear(&mut self) {
+        self.procedures.clear();
+        self.calls.clear();
+        }
+
+    /* Getters */
+    pub fn depth(&self) -> usize {
+        self.calls.len()
+        }
+    /* Get the procedures' values, and locations */
+    pub fn procedures(&self) -> impl Iterator<Item = (u64, usize)> + '_ {
+        self.procedures.iter()
+            .map(|(&value, &start)| (value, start))
+        }
+    /* Get the locations of the calls waiting, from the first one */
+    pub fn calls(&self) -> &[usize] {
+        &self.calls
+        }
+
+    /* Construct from the procedures, and the calls - like the ones of a snapshot */
+    pub fn from_parts(procedures: impl IntoIterator<Item = (u64, usize)>, calls: Vec<usize>) -> Self {
+        Self {
+            procedures: procedures.into_iter().collect(),
+            calls
+            }
         }
     }
 
@@ -363,6 +843,191 @@ mod test {
         assert_eq!(instr, output);
         }
 
+    #[test]
+    fn eval_dialect_rand() {
+        let instructions = eval_instr_with("+?.", &[Dialect::ExtRand])
+            .expect("Unreachable");
+        let output = InstructionSet(vec![Increment, Random, Output]);
+
+        assert_eq!(instructions, output);
+        }
+
+    #[test]
+    fn eval_dialect_rand_disabled() {
+        let instructions = eval_instr("+?.")
+            .expect("Unreachable");
+        let output = InstructionSet(vec![Increment, Output]);
+
+        assert_eq!(instructions, output);
+        }
+
+    #[test]
+    fn fingerprint_basic() {
+        let first = eval_instr("+[->+<]")
+            .expect("Unreachable");
+        let same = eval_instr("+ [ - > + < ] comment")
+            .expect("Unreachable");
+        let other = eval_instr("+[->-<]")
+            .expect("Unreachable");
+
+        assert_eq!(first.fingerprint(), same.fingerprint());
+        assert_ne!(first.fingerprint(), other.fingerprint());
+        }
+
+    #[test]
+    fn jump_table_basic() {
+        let table = eval_instr("+[>[-]<-]")
+            .expect("Unreachable")
+            .build_jump_table();
+
+        assert_eq!((table[1], table[8]), (8, 1));
+        assert_eq!((table[3], table[5]), (5, 3));
+        }
+
+    #[test]
+    fn to_source_basic() {
+        let instr = eval_instr("Hello +[> .<-] world,")
+            .expect("Unreachable");
+
+        assert_eq!(instr.to_source(), "+[>.<-],");
+        assert_eq!(format!("{instr}"), "+[>.<-],");
+        }
+
+    #[test]
+    fn error_location() {
+        let source = "+[\n-]]\n[[";
+
+        assert_eq!(eval_instr(source).err().and_then(|err| err.location(source)), Some(5));
+        assert_eq!(source_location(source, 5), (2, 3));
+
+        let source = "[]\n [[]";
+        let err = eval_instr(source)
+            .expect_err("Unreachable");
+
+        assert_eq!(err.location(source).map(|index| source_location(source, index)), Some((2, 2)));
+        }
+
+    #[test]
+    fn eval_strict() {
+        let source = "+[\n\t-}";
+
+        assert_eq!(eval_instr_strict(source, &[], CommentMode::Forbidden).err(), Some(EvalError::UnknownCharacter('\n', 2)));
+        assert_eq!(eval_instr_strict(source, &[], CommentMode::Whitespace).err(), Some(EvalError::UnknownCharacter('}', 5)));
+        assert_eq!(eval_instr_strict("+ ?", &[Dialect::ExtRand], CommentMode::Whitespace).map(|(instr, _)| instr), eval_instr_with("+?", &[Dialect::ExtRand]));
+
+        let err = eval_instr_strict(source, &[], CommentMode::Whitespace)
+            .expect_err("Unreachable");
+        assert_eq!(err.location(source).map(|index| source_location(source, index)), Some((2, 3)));
+        }
+
+    #[test]
+    fn eval_pbrain() {
+        let pbrain = [Dialect::Pbrain];
+        let instr = eval_instr_with("+(-[.]):", &pbrain)
+            .expect("Unreachable");
+        let jumps = instr.build_jump_table();
+
+        assert_eq!((jumps[1], jumps[6]), (6, 1));
+        assert_eq!(eval_instr("(:)").map(|instr| instr.len()), Ok(0));
+        assert_eq!(eval_instr_with("((", &pbrain), Err(EvalError::NestedProcedure(1)));
+        assert_eq!(eval_instr_with("+)", &pbrain), Err(EvalError::UnnecesseryParenthesis(1)));
+        assert_eq!(eval_instr_with("+(:", &pbrain), Err(EvalError::UnclosedProcedure(1)));
+        assert_eq!(eval_instr_with("[(])", &pbrain), Err(EvalError::ProcedureLoop(2)));
+        assert_eq!(eval_instr_with("([)]", &pbrain), Err(EvalError::ProcedureLoop(2)));
+        }
+
+    #[test]
+    fn eval_token_map() {
+        let map: TokenMap = "> right\n< left\n+ inc\n- dec\n[ while\n] end\n. out\n, in\n"
+            .parse()
+            .expect("Unreachable");
+
+        let (instr, source_map) = eval_tokens("inc while dec end out incinc", &map, CommentMode::Any)
+            .expect("Unreachable");
+
+        assert_eq!(Ok(instr), eval_instr("+[-].++"));
+        assert_eq!(source_map.offset(5), Some(22));
+        assert_eq!(eval_tokens("end", &map, CommentMode::Any).err(), Some(EvalError::UnnecesseryBracket(0)));
+        assert_eq!(eval_tokens("inc x", &map, CommentMode::Whitespace).err(), Some(EvalError::UnknownCharacter('x', 4)));
+        }
+
+    #[test]
+    fn token_map_errors() {
+        assert_eq!("> a\n> b".parse::<TokenMap>(), Err(TokenMapError::Duplicate('>')));
+        assert_eq!("> a\n< a".parse::<TokenMap>(), Err(TokenMapError::Ambiguous("a".to_string())));
+        assert_eq!("\n>a".parse::<TokenMap>(), Err(TokenMapError::InvalidLine(2)));
+        assert_eq!("x a".parse::<TokenMap>(), Err(TokenMapError::InvalidLine(1)));
+        assert_eq!("> a".parse::<TokenMap>(), Err(TokenMapError::Missing('<')));
+        }
+
+    #[test]
+    fn source_map_basic() {
+        let source = "a+\n[ -]\n.";
+        let (instr, map) = eval_instr_mapped(source, &[])
+            .expect("Unreachable");
+
+        assert_eq!(map.len(), instr.len());
+        assert_eq!(map.offset(2), Some(5));
+        assert_eq!(map.location(source, 4), Some((3, 1)));
+        assert_eq!(map.offset(5), None);
+
+        let source = "[comment]\n+.";
+        let (mut instr, mut map) = eval_instr_mapped(source, &[])
+            .expect("Unreachable");
+
+        let len = instr.len();
+        instr.prune_comment_loop();
+        map.skip(len - instr.len());
+
+        assert_eq!(map.location(source, 0), Some((2, 1)));
+        }
+
+    #[test]
+    fn iter_basic() {
+        let instr = eval_instr("+[-].")
+            .expect("Unreachable");
+
+        assert_eq!(instr.as_slice(), [Increment, LoopOpen, Decrement, LoopClose, Output]);
+        assert_eq!(instr.iter().filter(|&&inst| inst == LoopOpen).count(), 1);
+        assert_eq!((&instr).into_iter().count(), 5);
+        assert_eq!(instr.into_iter().last(), Some(Output));
+        }
+
+    #[test]
+    fn from_str_basic() {
+        assert_eq!("++[>.<-]".parse(), eval_instr("++[>.<-]"));
+        assert_eq!("+]".parse::<InstructionSet>(), Err(EvalError::UnnecesseryBracket(1)));
+        }
+
+    #[test]
+    fn try_from_loops() {
+        assert_eq!(InstructionSet::try_from(vec![LoopOpen, Output, LoopClose]), Ok(InstructionSet(vec![LoopOpen, Output, LoopClose])));
+        assert_eq!(InstructionSet::try_from(vec![Output, LoopClose, LoopOpen]), Err(EvalError::UnnecesseryBracket(1)));
+        assert_eq!(InstructionSet::try_from(vec![LoopOpen, LoopOpen, LoopClose]), Err(EvalError::UnclosedBracket(1)));
+        }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let instr = eval_instr("+[>.<-]")
+            .expect("Unreachable");
+        let json = serde_json::to_string(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(serde_json::from_str::<InstructionSet>(&json).ok(), Some(instr));
+        assert!(serde_json::from_str::<InstructionSet>(r#"["LoopClose"]"#).is_err());
+
+        let rle = eval_instr("+++[-]")
+            .expect("Unreachable")
+            .encode_run_length();
+        let json = serde_json::to_string(&rle)
+            .expect("Unreachable");
+
+        assert_eq!(json, r#"[[3,"Increment"],[1,"LoopOpen"],[1,"Decrement"],[1,"LoopClose"]]"#);
+        assert_eq!(serde_json::from_str::<RLEInstructionSet>(&json).ok(), Some(rle));
+        assert!(serde_json::from_str::<RLEInstructionSet>(r#"[[2,"LoopOpen"],[1,"LoopClose"]]"#).is_err());
+        }
+
     #[test]
     fn prune_basic() {
         let mut instructions = eval_instr("[+++]>+<-")
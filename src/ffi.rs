@@ -0,0 +1,200 @@
+use {
+    std::{
+        ffi::{
+            c_char,
+            c_void,
+            CStr
+            },
+        io::{
+            sink,
+            Cursor,
+            Result as IOResult,
+            Write
+            },
+        slice::from_raw_parts
+        },
+    crate::{
+        eval::{
+            eval_instr,
+            InstructionSet
+            },
+        interp::*,
+        utils::{
+            DisplayMode,
+            EofMode
+            }
+        }
+    };
+
+
+/* Status codes handed back by the C functions */
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BcStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    Parse = 3,
+    Build = 4,
+    Run = 5,
+    Output = 6
+    }
+
+/* Callback getting the written bytes - the data is valid only during the call */
+pub type BcOutput = extern "C" fn(data: *const u8, len: usize, user: *mut c_void);
+
+/* Parsed program, opaque on the C side */
+pub struct BcProgram(InstructionSet);
+
+/* Writer passing the bytes over to the callback */
+struct CallbackWriter {
+    callback: BcOutput,
+    user: *mut c_void
+    }
+
+/* Unsafe note - it is safe, because the writer lives only during the `bc_run`, on the caller's thread */
+unsafe impl Send for CallbackWriter {}
+
+impl Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        (self.callback)(buf.as_ptr(), buf.len(), self.user);
+        Ok(buf.len())
+        }
+    fn flush(&mut self) -> IOResult<()> {
+        Ok(())
+        }
+    }
+
+
+/** Parse the null-terminated source code - on success, the program is written to `program`, and has to be released with `bc_free`
+# Safety
+`code` has to be a valid C string, and `program` a valid pointer, or null */
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bc_eval(code: *const c_char, program: *mut *mut BcProgram) -> BcStatus {
+    if code.is_null() || program.is_null() {
+        return BcStatus::NullPointer;
+        }
+
+    /* Unsafe note - it is safe, because the caller guarantees the string is valid */
+    let code = match unsafe { CStr::from_ptr(code) }.to_str() {
+        Ok(code) => code,
+        Err(_) => return BcStatus::InvalidUtf8
+        };
+
+    match eval_instr(code) {
+        Ok(instr) => {
+            /* Unsafe note - it is safe, because the pointer was checked, and the caller guarantees it's valid */
+            unsafe {
+                *program = Box::into_raw(Box::new(BcProgram(instr)));
+                }
+            BcStatus::Ok
+            },
+        Err(_) => BcStatus::Parse
+        }
+    }
+
+/** Run the program on the given input bytes, with byte cells, and the end of input read as zero - the written bytes go to the callback, when there is one
+# Safety
+`program` has to come from `bc_eval`, and `input` has to point to `input_len` bytes, or be null with zero length */
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bc_run(program: *const BcProgram, input: *const u8, input_len: usize, output: Option<BcOutput>, user: *mut c_void) -> BcStatus {
+    if program.is_null() || (input.is_null() && input_len != 0) {
+        return BcStatus::NullPointer;
+        }
+
+    /* Unsafe note - it is safe, because the caller guarantees the pointers are valid */
+    let (BcProgram(instr), input) = unsafe {
+        let input = match input.is_null() {
+            true => &[],
+            false => from_raw_parts(input, input_len)
+            };
+
+        (&*program, input)
+        };
+
+    let builder = Interpreter::builder()
+        .display_mode(DisplayMode::Raw)
+        .byte_input(true)
+        .eof(EofMode::Zero)
+        .input(Box::new(Cursor::new(input.to_vec())));
+    let builder = match output {
+        Some(callback) => builder.output(Box::new(CallbackWriter { callback, user })),
+        None => builder.output(Box::new(sink()))
+        };
+
+    let mut interp = match builder.try_build::<u16, u8>() {
+        Ok(interp) => interp,
+        Err(_) => return BcStatus::Build
+        };
+
+    match interp.run(instr) {
+        Ok(()) => BcStatus::Ok,
+        Err(RunError::IO(_)) => BcStatus::Output,
+        Err(_) => BcStatus::Run
+        }
+    }
+
+/** Release the program - null is ignored
+# Safety
+`program` has to come from `bc_eval`, and can't be used after this call */
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bc_free(program: *mut BcProgram) {
+    if ! program.is_null() {
+        /* Unsafe note - it is safe, because the caller guarantees the program came from `bc_eval` */
+        drop(unsafe { Box::from_raw(program) });
+        }
+    }
+
+/* Get the static, null-terminated description of the status */
+#[unsafe(no_mangle)]
+pub extern "C" fn bc_status_message(status: BcStatus) -> *const c_char {
+    let message: &CStr = match status {
+        BcStatus::Ok => c"Ok",
+        BcStatus::NullPointer => c"Null pointer was given",
+        BcStatus::InvalidUtf8 => c"Source code isn't valid UTF-8",
+        BcStatus::Parse => c"Source code couldn't be parsed",
+        BcStatus::Build => c"Interpreter couldn't be built",
+        BcStatus::Run => c"Run failed",
+        BcStatus::Output => c"Input, or output failed"
+        };
+
+    message.as_ptr()
+    }
+
+
+#[cfg(test)]
+mod test {
+    use {
+        std::ptr::{
+            null,
+            null_mut
+            },
+        crate::ffi::*
+        };
+
+    extern "C" fn collect(data: *const u8, len: usize, user: *mut c_void) {
+        /* Unsafe note - it is safe, because the test hands over its own vector */
+        unsafe {
+            (*user.cast::<Vec<u8>>()).extend_from_slice(from_raw_parts(data, len));
+            }
+        }
+
+    #[test]
+    fn ffi_run() {
+        let mut program = null_mut();
+        let mut output = Vec::<u8>::new();
+
+        /* Unsafe note - it is safe, because all of the pointers are valid */
+        unsafe {
+            assert_eq!(bc_eval(c",[.,]".as_ptr(), &mut program), BcStatus::Ok);
+            assert_eq!(bc_run(program, b"HAL".as_ptr(), 3, Some(collect), (&raw mut output).cast()), BcStatus::Ok);
+            assert_eq!(bc_run(program, null(), 0, None, null_mut()), BcStatus::Ok);
+            assert_eq!(bc_run(null(), null(), 0, None, null_mut()), BcStatus::NullPointer);
+            bc_free(program);
+
+            assert_eq!(bc_eval(c"[".as_ptr(), &mut program), BcStatus::Parse);
+            }
+
+        assert_eq!(output, b"HAL");
+        }
+    }
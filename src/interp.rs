@@ -1,38 +1,246 @@
 use {
+    thiserror::Error,
     log::{
         info,
         warn
         },
+    num_bigint::BigUint,
     std::io::{
+        Error as IOError,
         stdin,
         stdout,
+        stderr,
+        ErrorKind,
         Result as IOResult,
         BufReader,
         BufWriter,
         Write,
         Read,
-        BufRead
+        BufRead,
+        Cursor
+        },
+    std::sync::{
+        atomic::{
+            AtomicBool,
+            Ordering
+            },
+        Arc
+        },
+    std::time::Instant,
+    core::{
+        time::Duration,
+        marker::PhantomData,
+        mem::{
+            replace,
+            take
+            },
+        ops::ControlFlow
         },
     crate::{
+        debugger::Stepper,
         eval::*,
+        ir::*,
+        profile::Profile,
+        rle::RLEInstructionSet,
+        rng::*,
+        snapshot::*,
         tape::*,
         utils::*
         }
     };
 
+#[cfg(feature = "jit")]
+use crate::jit::*;
+
+
+/* Number of cells dumped on each side of the pointer */
+const DUMP_RADIUS: isize = 5;
+
+/* Deepest the procedure calls can be nested, before the run fails - keeps a runaway recursion from filling the memory */
+pub const MAX_CALL_DEPTH: usize = 1 << 16;
 
 /* The Interpreter container for running code */
 pub struct Interpreter<T = u16, U = u8> {
-    tape: Tape<T, U>,
-    output: BufWriter<Box<dyn Write>>,
-    input: BufReader<Box<dyn Read>>,
+    tape: Box<dyn TapeBackend<U>>,
+    pointer: PhantomData<T>,
+    output: BufWriter<Box<dyn Write + Send>>,
+    input: BufReader<Box<dyn Read + Send>>,
+    /* Destination of the state's dumps */
+    dump: Box<dyn Write + Send>,
     read_buffer: String,
-    display_mode: DisplayMode
+    byte_input: bool,
+    eof: EofMode,
+    pointer_overflow: PointerOverflow,
+    cell_overflow: CellOverflow,
+    bit_cells: bool,
+    display_mode: DisplayMode,
+    /* Formatting of the numeric values, and whether one was written already */
+    numeric_format: NumericFormat,
+    numeric_written: bool,
+    unbuffered: bool,
+    max_output: Option<u64>,
+    written: u64,
+    read: u64,
+    max_steps: Option<u64>,
+    time_limit: Option<Duration>,
+    recording: Option<Vec<u8>>,
+    capture: Option<SharedBuffer>,
+    hook: Option<ArithHook>,
+    opt_level: OptLevel,
+    /* Execution counts of the operations, and the profile they made - only when profiling */
+    profiling: bool,
+    op_counts: Vec<u64>,
+    profile: Option<Profile>,
+    rng: Rng,
+    /* Cells the tape starts with, from the first one on, and the cell the pointer starts at */
+    initial_tape: Vec<U>,
+    start_position: isize,
+    /* Flag pausing the runs, fingerprint of the program being run, and the instruction, and count a run was paused at */
+    pause: Option<PauseFlag>,
+    program: Option<u64>,
+    paused_at: Option<(usize, u64)>,
+    /* Whether the `!` breakpoint was passed, so the run stops at the next instruction it can be resumed from */
+    break_pending: bool,
+    /* Procedures defined by the run, and its calls waiting - by their instructions' locations, so they can be snapshotted */
+    procedures: ProcedureTable,
+    /* Number of instructions between the checkpoints, their hook, and the count the next one is due at */
+    checkpoints: Option<(u64, CheckpointHook)>,
+    next_checkpoint: u64,
+    #[cfg(feature = "jit")]
+    tiering: Option<Tiering>
+    }
+
+/* Arithmetic events, which can be observed during the execution */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArithEvent {
+    /* Cell was incremented past its maximum value, and wrapped to zero */
+    CellOverflow,
+    /* Cell was decremented below zero, and wrapped to its maximum value - arbitrary-precision cells stop at zero instead */
+    CellUnderflow,
+    /* Pointer moved past an end of a fixed tape, and wrapped to the other one */
+    PointerWrap
+    }
+
+/* Callback for the arithmetic events - breaking aborts the run */
+pub type ArithHook = Box<dyn FnMut(ArithEvent) -> ControlFlow<()> + Send>;
+
+/* Flag pausing the runs of the Interpreters it was given to, which can be raised from any thread */
+#[derive(Clone, Default)]
+pub struct PauseFlag (
+    Arc<AtomicBool>
+    );
+
+impl PauseFlag {
+    /* Pause the run before its next instruction */
+    pub fn pause(&self) {
+        self.0.store(true, Ordering::Relaxed);
+        }
+
+    fn is_raised(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+        }
+    fn lower(&self) {
+        self.0.store(false, Ordering::Relaxed);
+        }
+    }
+
+/* Callback for the periodic snapshots of a run - failing aborts the run */
+pub type CheckpointHook = Box<dyn FnMut(&Snapshot) -> IOResult<()> + Send>;
+
+/* Everything left after a finished run */
+#[derive(PartialEq, Debug)]
+pub struct RunOutcome {
+    /* Everything written, when the output was captured in memory */
+    pub output: Option<Vec<u8>>,
+    /* Final pointer position */
+    pub position: isize,
+    /* Final cells differing from the fill value, with their locations */
+    pub tape: Vec<(isize, BigUint)>,
+    pub stats: RunStats
+    }
+
+/* Execution statistics */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RunStats {
+    pub instructions: usize,
+    /* Optimised loops count their instructions once, as they're done in a single step */
+    pub executed: u64
+    }
+
+/* Everything a single run did */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RunReport {
+    /* Optimised loops count their instructions once, as they're done in a single step */
+    pub executed: u64,
+    pub elapsed: Duration,
+    /* Cells left holding something else than the fill value - the ones the run touched, but set back to it aren't counted */
+    pub set_cells: usize,
+    /* Bytes written, and read by the run - the final newline isn't counted */
+    pub written: u64,
+    pub read: u64
+    }
+
+/* Execution's result output type */
+#[derive(Debug, Error)]
+pub enum RunError {
+    #[error(transparent)]
+    IO(#[from] IOError),
+    #[error("Output limit of {0} byte(s) was exceeded")]
+    OutputLimit(u64),
+    #[error("Step limit of {0} instruction(s) was exceeded")]
+    StepLimit(u64),
+    #[error("Time limit of {0:?} was exceeded")]
+    Timeout(Duration),
+    #[error("Snapshot doesn't match the program, or the Interpreter's settings")]
+    SnapshotMismatch,
+    #[error("Snapshot can only hold a single tape")]
+    MultipleTapes,
+    #[error("Run was paused at instruction: {0}")]
+    Paused(usize),
+    #[error("Run stopped at the breakpoint, before instruction: {0}")]
+    Breakpoint(usize),
+    #[error("Run was aborted by the hook on: {0:?}")]
+    Aborted(ArithEvent),
+    #[error("Pointer was moved off the tape to {position} at instruction: {instruction}")]
+    PointerOutOfBounds {
+        position: isize,
+        instruction: usize
+        },
+    #[error("Cell at {position} was taken past its bounds at instruction: {instruction}")]
+    CellOverflow {
+        position: isize,
+        instruction: usize
+        },
+    #[error("Undefined procedure {value} was called at instruction: {instruction}")]
+    UnknownProcedure {
+        value: u64,
+        instruction: usize
+        },
+    #[error("Procedure calls were nested deeper than {MAX_CALL_DEPTH} at instruction: {0}")]
+    CallDepth(usize),
+    #[error("Operations were fused, so they can't be run one step at a time - compile them at the lowest optimisation level")]
+    FusedOperations
+    }
+
+impl RunError {
+    /* Get the index of the instruction the error points at - none, for the errors without one */
+    pub const fn instruction(&self) -> Option<usize> {
+        match *self {
+            RunError::Paused(instruction) |
+            RunError::Breakpoint(instruction) |
+            RunError::PointerOutOfBounds { instruction, .. } |
+            RunError::CellOverflow { instruction, .. } |
+            RunError::UnknownProcedure { instruction, .. } |
+            RunError::CallDepth(instruction) =>
+                Some(instruction),
+            _ => None
+            }
+        }
     }
 
 
 impl<T, U> Default for Interpreter<T, U>
-where T: TapePointer, U: TapeCell {
+where T: TapePointer + 'static, U: TapeCell + 'static {
     /* The default Interpreter settings */
     fn default() -> Self {
         Interpreter::builder()
@@ -41,66 +249,331 @@ where T: TapePointer, U: TapeCell {
     }
 
 
-/* Trait for generic ability to run the Interpreter */
-pub trait InterpRun {
-    fn run(&mut self, instr: &InstructionSet) -> IOResult<()>;
+/* Trait for generic ability to run the Interpreter - boxed Interpreters can be handed over to other threads */
+pub trait InterpRun: Send {
+    fn run(&mut self, instr: &InstructionSet) -> Result<(), RunError>;
+    fn run_rle(&mut self, rle: &RLEInstructionSet) -> Result<(), RunError>;
+    fn run_ir(&mut self, ir: &Ir) -> Result<(), RunError>;
+    fn warm_start(&mut self, instr: &InstructionSet) -> Result<Snapshot, RunError>;
+    fn resume(&mut self, instr: &InstructionSet, snapshot: &Snapshot) -> Result<(), RunError>;
+    fn run_consuming(self, instr: &InstructionSet) -> Result<RunOutcome, RunError>
+    where Self: Sized;
+    fn run_with_report(&mut self, instr: &InstructionSet) -> Result<RunReport, RunError>;
+    fn run_with_io(&mut self, instr: &InstructionSet, input: &[u8]) -> Result<Vec<u8>, RunError>;
+    fn reset(&mut self);
+    fn take_profile(&mut self) -> Option<Profile>;
+    fn prepare(&mut self, instr: &InstructionSet) -> Ir;
+    fn run_until_input(&mut self, ir: &Ir, op_ptr: usize, count: &mut u64) -> Result<usize, RunError>;
+    fn step(&mut self, ir: &Ir, op_ptr: usize, count: &mut u64) -> Result<usize, RunError>;
+    fn stepper<'a>(&'a mut self, instr: &'a InstructionSet) -> Stepper<'a>;
+    fn position(&self) -> isize;
+    fn cells(&self) -> Vec<(isize, BigUint)>;
+    fn all_cells(&self) -> Vec<(usize, isize, BigUint)>;
+    fn tape_image(&self) -> (isize, Vec<u8>);
+    fn snapshot(&self) -> Option<Snapshot>;
+    fn peek(&mut self, index: isize) -> Option<BigUint>;
+    fn flush(&mut self) -> Result<(), RunError>;
+    fn set_display_mode(&mut self, value: DisplayMode) -> Result<(), BuildError>;
     }
 
 impl<T, U> InterpRun for Interpreter<T, U>
 where T: TapePointer, U: TapeCell {    
     /* Run the source code's instructions */
-    fn run(&mut self, instr: &InstructionSet) -> IOResult<()> {
-        let instr_len = instr.len();
+    fn run(&mut self, instr: &InstructionSet) -> Result<(), RunError> {
+        let ir = self.lower(instr);
+
+        self.run_from(&ir, 0, 0)
+            .map(drop)
+        }
 
-        /* Generate the jump table for loops */
-        let jump_table = instr.build_jump_table();
+    /* Run the run-length encoded instructions, applying every run at once */
+    fn run_rle(&mut self, rle: &RLEInstructionSet) -> Result<(), RunError> {
+        #[cfg(feature = "jit")]
+        if let Some(tiering) = &mut self.tiering {
+            tiering.prepare(rle.fingerprint());
+            }
+        self.program = None;
+        self.procedures.clear();
 
-        /* Helper types for the instructions' execution */
-        let mut instr_ptr: usize = 0;
+        let ir = Ir::lower_runs(rle, self.hook.is_none() && self.opt_level > OptLevel::O0);
 
-        /* Debug variable */
-        let mut count: u64 = 0;
-
-        /* Main loop */
-        while instr_ptr < instr_len {
-            /* Get instruction's type, and execute it */
-            match instr[instr_ptr] {
-                Instruction::Right => 
-                    self.tape.right(),
-                Instruction::Left =>
-                    self.tape.left(),
-                Instruction::Increment =>
-                    self.tape.increment(),
-                Instruction::Decrement =>
-                    self.tape.decrement(),
-                Instruction::LoopOpen =>
-                    if self.tape.is_zero() {
-                        instr_ptr = jump_table[instr_ptr];
-                        },
-                Instruction::LoopClose => 
-                    if ! self.tape.is_zero() {
-                        instr_ptr = jump_table[instr_ptr];
-                        },            
-                Instruction::Output => 
-                    self.write()?,
-                Instruction::Input =>
-                    self.read()?
+        self.run_from(&ir, 0, 0)
+            .map(drop)
+        }
+
+    /* Run the already lowered operations, as they are - fused runs hide the wrapping from the hook */
+    fn run_ir(&mut self, ir: &Ir) -> Result<(), RunError> {
+        #[cfg(feature = "jit")]
+        if let Some(tiering) = &mut self.tiering {
+            tiering.prepare(ir.fingerprint());
+            }
+        self.program = None;
+        self.procedures.clear();
+
+        /* Already lowered operations can't be split back into the steps - neither for the arbitrary-precision cells, which stop at zero */
+        if (self.single_steps() || U::BOUND.is_none()) && ! ir.is_unfused() {
+            return Err(RunError::FusedOperations);
+            }
+
+        self.run_from(ir, 0, 0)
+            .map(drop)
+        }
+
+    /* Run the deterministic prefix - everything before the first input, and capture the state */
+    fn warm_start(&mut self, instr: &InstructionSet) -> Result<Snapshot, RunError> {
+        if self.tape.tape_count() > 1 {
+            return Err(RunError::MultipleTapes);
+            }
+
+        /* Record the output, instead of writing it - it gets replayed when resuming */
+        self.recording = Some(Vec::new());
+
+        let ir = self.lower(instr);
+        let outcome = self.execute(&ir, 0, 0, true);
+        let output = self.recording.take()
+            .unwrap_or_default();
+        let (op_ptr, count) = outcome?;
+        let instr_ptr = ir.source(op_ptr);
+
+        info!("Warm start stopped at instruction: {instr_ptr}");
+
+        Ok(Snapshot {
+            program: instr.fingerprint(),
+            instr_ptr,
+            count,
+            rng: self.rng.state(),
+            output,
+            position: self.tape.position(),
+            cells: self.tape_values(),
+            procedures: self.procedures.procedures()
+                .collect(),
+            calls: self.procedures.calls()
+                .to_vec()
+            })
+        }
+
+    /* Restore the state from a snapshot into a freshly built Interpreter, and run the rest of the instructions */
+    fn resume(&mut self, instr: &InstructionSet, snapshot: &Snapshot) -> Result<(), RunError> {
+        if self.tape.tape_count() > 1 {
+            return Err(RunError::MultipleTapes);
+            }
+
+        /* Check the snapshot was made for this program, and stopped at the start of an operation */
+        let ir = self.lower(instr);
+        let start = ir.find_source(snapshot.instr_ptr)
+            .filter(|_| snapshot.program == instr.fingerprint())
+            .ok_or(RunError::SnapshotMismatch)?;
+
+        /* Check every cell fits the tape, before anything gets modified */
+        let cells = snapshot.cells.iter()
+            .map(|(index, value)| match self.tape.seek(*index) {
+                true => U::from_exact(value)
+                    .map(|value| (*index, value)),
+                false => None
+                })
+            .collect::<Option<Vec<_>>>()
+            .filter(|_| self.tape.seek(snapshot.position))
+            .ok_or(RunError::SnapshotMismatch)?;
+
+        /* Check the procedures start, and the calls wait at the matching instructions */
+        let defined = snapshot.procedures.iter()
+            .all(|&(_, start)| matches!(ir.find_source(start).map(|op_ptr| &ir[op_ptr]), Some(IrOp::ProcStart(_))));
+        let called = snapshot.calls.iter()
+            .all(|&call| matches!(ir.find_source(call).map(|op_ptr| &ir[op_ptr]), Some(IrOp::Call)));
+        if ! (defined && called) {
+            return Err(RunError::SnapshotMismatch);
+            }
+
+        /* Restore the tape, and the pointer - the initial cells are overwritten, as the snapshot holds the whole state */
+        self.tape.clear();
+        for (index, value) in cells {
+            self.tape.seek(index);
+            self.tape.set(value);
+            }
+        self.tape.seek(snapshot.position);
+        self.procedures = ProcedureTable::from_parts(snapshot.procedures.iter().copied(), snapshot.calls.clone());
+
+        /* Restore the generator, and replay the output */
+        self.rng = Rng::new(snapshot.rng);
+        self.emit(&snapshot.output)?;
+        self.numeric_written = ! snapshot.output.is_empty();
+
+        info!("Resuming from instruction: {}", snapshot.instr_ptr);
+
+        self.run_from(&ir, start, snapshot.count)
+            .map(drop)
+        }
+
+    /* Run the source code's instructions, and hand back everything the run left behind */
+    fn run_consuming(mut self, instr: &InstructionSet) -> Result<RunOutcome, RunError> {
+        let ir = self.lower(instr);
+        let executed = self.run_from(&ir, 0, 0)?;
+
+        Ok(RunOutcome {
+            output: self.capture.as_ref()
+                .map(SharedBuffer::take),
+            position: self.tape.position(),
+            tape: self.tape_values(),
+            stats: RunStats {
+                instructions: instr.len(),
+                executed
                 }
+            })
+        }
 
-            /* Increment instruction pointer with every loop */
-            instr_ptr += 1;
+    /* Run the source code's instructions, and report what the run did */
+    fn run_with_report(&mut self, instr: &InstructionSet) -> Result<RunReport, RunError> {
+        let (written, read) = (self.written, self.read);
+        let started = Instant::now();
 
-            /* Debug information */
-            count += 1;
+        let ir = self.lower(instr);
+        let executed = self.run_from(&ir, 0, 0)?;
+
+        Ok(RunReport {
+            executed,
+            elapsed: started.elapsed(),
+            set_cells: self.tape.all_cells().len(),
+            written: self.written - written,
+            read: self.read - read
+            })
+        }
+
+    /* Run the source code's instructions on the given input, and hand back everything written - the Interpreter's own I/O is put back afterwards */
+    fn run_with_io(&mut self, instr: &InstructionSet, input: &[u8]) -> Result<Vec<u8>, RunError> {
+        let buffer = SharedBuffer::default();
+
+        let input = replace(&mut self.input, BufReader::new(Box::new(Cursor::new(input.to_vec()))));
+        let output = replace(&mut self.output, BufWriter::new(Box::new(buffer.clone())));
+
+        let outcome = self.run(instr);
+
+        self.input = input;
+        self.output = output;
+
+        outcome.map(|()| buffer.take())
+        }
+
+    /* Bring the Interpreter back to its freshly built state, keeping the settings, the I/O, and the allocated tape */
+    fn reset(&mut self) {
+        self.tape.clear();
+        self.load_initial_tape();
+        self.read_buffer.clear();
+        self.written = 0;
+        self.read = 0;
+        self.numeric_written = false;
+        self.recording = None;
+        self.paused_at = None;
+        self.break_pending = false;
+        self.procedures.clear();
+
+        /* Drop the output captured so far */
+        if let Some(capture) = &self.capture {
+            capture.take();
             }
+        }
+
+    /* Take the profile of the last run - only made, when profiling */
+    fn take_profile(&mut self) -> Option<Profile> {
+        self.profile.take()
+        }
+
+    /* Lower the source code's instructions the same way the runs do, for driving them from the outside */
+    fn prepare(&mut self, instr: &InstructionSet) -> Ir {
+        self.lower(instr)
+        }
+
+    /* Run the operations from the given one, until the next input, or the end, and flush the output - returns the operation it stopped at, and never profiles, as the counts are only kept for whole runs */
+    fn run_until_input(&mut self, ir: &Ir, op_ptr: usize, count: &mut u64) -> Result<usize, RunError> {
+        let profiling = replace(&mut self.profiling, false);
+        let outcome = self.execute(ir, op_ptr, *count, true);
+        self.profiling = profiling;
 
-        /* Last flush before execution ends */
-        self.output.write(b"\n")?;
+        let (op_ptr, executed) = outcome?;
+        *count = executed;
+
+        /* The end finishes the run like the others, raw output stays byte-exact */
+        if op_ptr >= ir.len() && self.display_mode != DisplayMode::Raw {
+            self.output.write_all(b"\n")?;
+            }
         self.output.flush()?;
 
-        /* Debug information */
-        info!("Number of instructions: {instr_len}");
-        info!("Number of executed instructions: {count}");
+        Ok(op_ptr)
+        }
+
+    /* Execute only the given operation, for driving the run from the outside - returns the next one, and never compiles the loops */
+    fn step(&mut self, ir: &Ir, op_ptr: usize, count: &mut u64) -> Result<usize, RunError> {
+        self.execute_op(ir, op_ptr, count)
+        }
+
+    /* Get a driver executing the source code's instructions one at a time */
+    fn stepper<'a>(&'a mut self, instr: &'a InstructionSet) -> Stepper<'a> {
+        Stepper::new(self, instr)
+        }
+
+    /* Get the pointer's position */
+    fn position(&self) -> isize {
+        self.tape.position()
+        }
+
+    /* Get all cells differing from the fill value on the current tape, widened without a loss */
+    fn cells(&self) -> Vec<(isize, BigUint)> {
+        self.tape_values()
+        }
+
+    /* Get all cells differing from the fill value on every tape, with the tape's index, widened without a loss */
+    fn all_cells(&self) -> Vec<(usize, isize, BigUint)> {
+        self.tape.all_cells()
+            .into_iter()
+            .map(|(tape, index, value)| (tape, index, value.exact()))
+            .collect()
+        }
+
+    /* Get every cell of the current tape as little-endian bytes of its width, with the location of the first one - 1-bit cells take a byte each, and the unbounded ones a decimal line each */
+    fn tape_image(&self) -> (isize, Vec<u8>) {
+        let (start, values) = self.tape.values();
+
+        let bytes = match U::BOUND {
+            Some(_) => values.iter()
+                .flat_map(|value| value.le_bytes())
+                .collect(),
+            None => values.iter()
+                .flat_map(|value| format!("{}\n", value.exact()).into_bytes())
+                .collect()
+            };
+
+        (start, bytes)
+        }
+
+    /* Capture the state a run of the source code's instructions was paused, or stopped by a breakpoint at - none, when the last run wasn't */
+    fn snapshot(&self) -> Option<Snapshot> {
+        let (instr_ptr, count) = self.paused_at?;
+
+        self.capture(instr_ptr, count)
+        }
+
+    /* Get the value of the cell, widened without a loss - none, when it's off the tape */
+    fn peek(&mut self, index: isize) -> Option<BigUint> {
+        let position = self.tape.position();
+
+        let value = self.tape.seek(index)
+            .then(|| self.tape.get().exact());
+        self.tape.seek(position);
+
+        value
+        }
+
+    /* Write out everything buffered so far */
+    fn flush(&mut self) -> Result<(), RunError> {
+        self.output.flush()?;
+
+        Ok(())
+        }
+
+    /* Change the way of displaying the cells between the runs - checked like when building */
+    fn set_display_mode(&mut self, value: DisplayMode) -> Result<(), BuildError> {
+        check_display(self.bit_cells, value, &self.numeric_format)?;
+
+        self.display_mode = value;
 
         Ok(())
         }
@@ -112,22 +585,410 @@ impl Interpreter<(), ()> {
     pub const fn builder() -> InterpreterBuilder {
         InterpreterBuilder {
             display_mode: None,
+            numeric_format: NumericFormat {
+                separator: Vec::new(),
+                prefix: Vec::new(),
+                suffix: Vec::new()
+                },
+            unbuffered: false,
+            byte_input: false,
+            eof: None,
+            pointer_overflow: None,
+            cell_overflow: None,
+            bit_cells: false,
+            tape_kind: None,
+            tape_size: None,
+            tapes: None,
+            max_output: None,
+            max_steps: None,
+            time_limit: None,
+            seed: None,
+            capture: None,
+            hook: None,
+            opt_level: None,
+            profile: false,
+            fill: 0,
+            initial_tape: Vec::new(),
+            start_position: 0,
+            pause: None,
+            checkpoints: None,
+            #[cfg(feature = "jit")]
+            jit_threshold: Some(JIT_THRESHOLD),
             output: None,
-            input: None
+            input: None,
+            dump: None
             }
         }
     }
 
 impl<T, U> Interpreter<T, U>
 where T: TapePointer, U: TapeCell { 
-    fn write(&mut self) -> IOResult<()> {
+    /* Check whether the operations have to be single steps - so every wrap gets observed, and every move, and cell gets checked, or saturated on its own change, instead of a fused, or cancelled run's */
+    fn single_steps(&self) -> bool {
+        self.hook.is_some() || self.pointer_overflow != PointerOverflow::Wrap || self.cell_overflow != CellOverflow::Wrap
+        }
+
+    /* Lower the instructions for the execution, at the optimisation level - runs aren't fused, when they have to be single steps, and the loops aren't rewritten when profiling, so every instruction keeps its count */
+    fn lower(&mut self, instr: &InstructionSet) -> Ir {
+        #[cfg(feature = "jit")]
+        if let Some(tiering) = &mut self.tiering {
+            tiering.prepare(instr.fingerprint());
+            }
+        self.program = Some(instr.fingerprint());
+        self.procedures.clear();
+
+        let opt_level = match self.profiling {
+            true => self.opt_level.min(OptLevel::O1),
+            false => self.opt_level
+            };
+
+        match (self.single_steps(), U::BOUND.is_some()) {
+            (true, _) => Ir::lower_unfused(instr),
+            (false, true) => Ir::lower_at(instr, opt_level),
+            (false, false) => Ir::lower_unbounded_at(instr, opt_level)
+            }
+        }
+
+    /* Run the operations from the given one, until the end - returns the number of executed instructions */
+    fn run_from(&mut self, ir: &Ir, start: usize, count: u64) -> Result<u64, RunError> {
+        self.paused_at = None;
+        self.break_pending = false;
+
+        /* Only the runs of the source code's instructions can be resumed, so only they get checkpoints */
+        self.next_checkpoint = match &self.checkpoints {
+            Some((every, _)) if self.program.is_some() => count.saturating_add(*every),
+            _ => u64::MAX
+            };
+
+        if self.profiling {
+            self.op_counts = vec![0; ir.len()];
+            }
+
+        let outcome = self.execute(ir, start, count, false);
+
+        /* Profile is kept, even when the run fails */
+        if self.profiling {
+            self.profile = Some(Profile::new(ir, &take(&mut self.op_counts)));
+            }
+
+        self.next_checkpoint = u64::MAX;
+
+        let (_, count) = outcome?;
+
+        /* Last flush before execution ends, raw output stays byte-exact */
+        if self.display_mode != DisplayMode::Raw {
+            self.output.write_all(b"\n")?;
+            }
+        self.output.flush()?;
+
+        /* Debug information */
+        info!("Number of instructions: {}", ir.source(ir.len()));
+        info!("Number of executed instructions: {count}");
+
+        Ok(count)
+        }
+
+    /* Write the initial cells from the start of the tape, and move the pointer to the starting cell - returns whether they all fit on it */
+    fn load_initial_tape(&mut self) -> bool {
+        for (index, value) in self.initial_tape.iter().enumerate() {
+            if ! self.tape.seek(index as isize) {
+                self.tape.seek(self.start_position);
+                return false;
+                }
+
+            self.tape.set(value.clone());
+            }
+        self.tape.seek(self.start_position);
+
+        true
+        }
+
+    /* Capture the state before the instruction - none, when the instructions being run aren't the source code's, or there are other tapes */
+    fn capture(&self, instr_ptr: usize, count: u64) -> Option<Snapshot> {
+        if self.tape.tape_count() > 1 {
+            return None;
+            }
+
+        Some(Snapshot {
+            program: self.program?,
+            instr_ptr,
+            count,
+            rng: self.rng.state(),
+            /* Output was already written by then */
+            output: Vec::new(),
+            position: self.tape.position(),
+            cells: self.tape_values(),
+            procedures: self.procedures.procedures()
+                .collect(),
+            calls: self.procedures.calls()
+                .to_vec()
+            })
+        }
+
+    /* Pass the state to the checkpoint hook, and schedule the next one - the output written so far gets saved first */
+    fn checkpoint(&mut self, instr_ptr: usize, count: u64) -> Result<(), RunError> {
+        self.output.flush()?;
+
+        let snapshot = self.capture(instr_ptr, count);
+        if let Some(snapshot) = snapshot && let Some((every, hook)) = &mut self.checkpoints {
+            hook(&snapshot)?;
+            self.next_checkpoint = count.saturating_add(*every);
+            }
+
+        Ok(())
+        }
+
+    /* Get all cells differing from the fill value, widened without a loss */
+    fn tape_values(&self) -> Vec<(isize, BigUint)> {
+        self.tape.cells()
+            .into_iter()
+            .map(|(index, value)| (index, value.exact()))
+            .collect()
+        }
+
+    /* Main loop - returns the operation it stopped at, and the updated number of executed instructions */
+    fn execute(&mut self, ir: &Ir, start: usize, count: u64, until_input: bool) -> Result<(usize, u64), RunError> {
+        let ir_len = ir.len();
+
+        /* Helper types for the operations' execution */
+        let mut op_ptr = start;
+
+        /* Debug variable */
+        let mut count = count;
+
+        /* Time the run has to finish by, and the number of operations executed since the clock was last read */
+        let deadline = self.time_limit
+            .map(|limit| (limit, Instant::now() + limit));
+        let mut ticks = 0u16;
+
+        while op_ptr < ir_len {
+            /* Stop, when paused from the outside, or by a breakpoint, at the first operation a run can be resumed from - the output written so far gets shown */
+            let paused = self.pause.as_ref()
+                .is_some_and(PauseFlag::is_raised);
+            if (paused || self.break_pending) && is_resumable(ir, op_ptr) {
+                let instr_ptr = ir.source(op_ptr);
+                self.paused_at = Some((instr_ptr, count));
+                self.output.flush()?;
+
+                /* Breakpoint goes first, and the pause stays for the resumed run */
+                return Err(match replace(&mut self.break_pending, false) {
+                    true => RunError::Breakpoint(instr_ptr),
+                    false => {
+                        if let Some(pause) = &self.pause {
+                            pause.lower();
+                            }
+                        RunError::Paused(instr_ptr)
+                        }
+                    });
+                }
+
+            /* Stop, before going over the step limit */
+            if let Some(limit) = self.max_steps && count >= limit {
+                return Err(RunError::StepLimit(limit));
+                }
+
+            /* Stop, once the time is up - the clock is only read every few thousand operations */
+            ticks = ticks.wrapping_add(1);
+            if let Some((limit, deadline)) = deadline && ticks % 4096 == 1 && Instant::now() >= deadline {
+                self.output.flush()?;
+                return Err(RunError::Timeout(limit));
+                }
+
+            /* Hand out a snapshot, once enough instructions were executed since the last one */
+            if count >= self.next_checkpoint && is_resumable(ir, op_ptr) {
+                self.checkpoint(ir.source(op_ptr), count)?;
+                }
+
+            /* Stop before the first input, when only running the deterministic prefix */
+            if until_input && ir[op_ptr] == IrOp::Input {
+                break;
+                }
+
+            if self.profiling {
+                self.op_counts[op_ptr] += 1;
+                }
+
+            let next = self.execute_op(ir, op_ptr, &mut count)?;
+
+            /* Hot loops continue natively, until they end - jumping back from the loop's end means it runs again */
+            #[cfg(feature = "jit")]
+            let next = match next <= op_ptr && matches!(ir[op_ptr], IrOp::LoopEnd(_)) {
+                true => self.tier_up(ir, next - 1, op_ptr, &mut count)
+                    .map_or(next, |exit| exit + 1),
+                false => next
+                };
+
+            op_ptr = next;
+            }
+
+        Ok((op_ptr, count))
+        }
+
+    /* Execute a single operation - returns the next one */
+    #[inline(always)]
+    fn execute_op(&mut self, ir: &Ir, op_ptr: usize, count: &mut u64) -> Result<usize, RunError> {
+        /* Wrapping only gets detected, when someone is listening - the operations are single steps then */
+        let hooked = self.hook.is_some();
+
+        let mut op_ptr = op_ptr;
+
+        /* Number of instructions the operation came from */
+        let span = ir.span(op_ptr) as u64;
+
+        /* Get operation's type, and execute it */
+        match ir[op_ptr] {
+            IrOp::Move(value) => match self.pointer_overflow {
+                PointerOverflow::Wrap => {
+                    let before = self.tape.position();
+                    self.tape.shift(value);
+
+                    let wrapped = match value > 0 {
+                        true => self.tape.position() < before,
+                        false => self.tape.position() > before
+                        };
+                    if hooked && wrapped {
+                        self.event(ArithEvent::PointerWrap)?;
+                        }
+                    },
+                /* Checked pointer goes straight to its target, which has to be on the tape */
+                PointerOverflow::Error => {
+                    let position = self.tape.position().saturating_add(value);
+
+                    if ! self.tape.seek(position) {
+                        return Err(RunError::PointerOutOfBounds { position, instruction: ir.source(op_ptr) });
+                        }
+                    }
+                },
+            IrOp::Add(value) => match self.cell_overflow {
+                CellOverflow::Wrap => {
+                    let wraps = hooked && self.tape.get().checked_offset(i64::from(value)).is_none();
+                    self.tape.add(value);
+
+                    match value > 0 {
+                        true if wraps =>
+                            self.event(ArithEvent::CellOverflow)?,
+                        false if wraps =>
+                            self.event(ArithEvent::CellUnderflow)?,
+                        _ => ()
+                        }
+                    },
+                CellOverflow::Saturate => {
+                    let cell = self.tape.get()
+                        .saturating_offset(i64::from(value));
+                    self.tape.set(cell);
+                    },
+                CellOverflow::Error => {
+                    let Some(cell) = self.tape.get().checked_offset(i64::from(value)) else {
+                        return Err(RunError::CellOverflow { position: self.tape.position(), instruction: ir.source(op_ptr) });
+                        };
+                    self.tape.set(cell);
+                    }
+                },
+            IrOp::Set(value) =>
+                self.tape.set(truncate_cell_value(value)),
+            IrOp::AddAt(offset, value) =>
+                self.tape.add_at(offset, value),
+            IrOp::SetAt(offset, value) =>
+                self.tape.set_at(offset, truncate_cell_value(value)),
+            /* Nothing gets added by a zero cell, as the loop wouldn't run */
+            IrOp::MulAdd(offset, factor) => if ! self.tape.is_zero() {
+                let source = self.tape.get();
+
+                self.tape.shift(offset);
+                let value = self.tape.get()
+                    .add_product(&source, i64::from(factor));
+                self.tape.set(value);
+                self.tape.shift(-offset);
+                },
+            /* Loops carry the location of their other end, so jumping needs no lookup */
+            IrOp::LoopStart(end) =>
+                if self.tape.is_zero() {
+                    op_ptr = end;
+                    },
+            IrOp::LoopEnd(start) => 
+                if ! self.tape.is_zero() {
+                    op_ptr = start;
+                    },
+            IrOp::Output => 
+                self.write()?,
+            IrOp::Input =>
+                self.read()?,
+            IrOp::Random =>
+                self.tape.set(truncate_cell_value(self.rng.next_u64())),
+            IrOp::Dump =>
+                self.dump(ir.source(op_ptr))?,
+            IrOp::Break =>
+                self.break_pending = true,
+            IrOp::ShowPointer =>
+                self.show_pointer(ir.source(op_ptr))?,
+            IrOp::ShowTape =>
+                self.show_tape(ir.source(op_ptr))?,
+            IrOp::Switch(offset) =>
+                self.tape.switch(offset),
+            /* Definition only gets remembered, and the body is skipped */
+            IrOp::ProcStart(end) => {
+                self.procedures.define(self.tape.get().widen(), ir.source(op_ptr));
+                op_ptr = end;
+                },
+            /* Return right after the call - the procedure's end is never reached otherwise */
+            IrOp::ProcEnd => if let Some(call) = self.procedures.ret() {
+                op_ptr = ir.find_source(call)
+                    .unwrap_or(op_ptr);
+                },
+            IrOp::Call => {
+                let (value, instruction) = (self.tape.get().widen(), ir.source(op_ptr));
+
+                if self.procedures.depth() >= MAX_CALL_DEPTH {
+                    return Err(RunError::CallDepth(instruction));
+                    }
+
+                op_ptr = self.procedures.call(value, instruction)
+                    .and_then(|start| ir.find_source(start))
+                    .ok_or(RunError::UnknownProcedure { value, instruction })?;
+                }
+            }
+
+        /* Debug information */
+        *count += span;
+
+        /* Increment operation pointer with every operation */
+        Ok(op_ptr + 1)
+        }
+
+    /* Run the loop's body natively, once the loop got hot - returns the operation it stopped at */
+    #[cfg(feature = "jit")]
+    fn tier_up(&mut self, ir: &Ir, open: usize, close: usize, count: &mut u64) -> Option<usize> {
+        let hot_loop = self.tiering.as_mut()?
+            .hot_loop::<T, U>(ir, open, close)?;
+
+        let index = self.tape.position() as usize;
+        let index = hot_loop.run(self.tape.raw_cells()?, index, count);
+        self.tape.seek(index as isize);
+
+        Some(close)
+        }
+
+    /* Pass the event to the hook, and abort if it says so */
+    fn event(&mut self, event: ArithEvent) -> Result<(), RunError> {
+        let aborted = self.hook.as_mut()
+            .is_some_and(|hook| hook(event).is_break());
+
+        match aborted {
+            true => Err(RunError::Aborted(event)),
+            false => Ok(())
+            }
+        }
+
+    fn write(&mut self) -> Result<(), RunError> {
         /* Get output data based on display mode, and byte's type */
         let value = self.tape.get();
+        /* Fallback is padded to the cell's width - unbounded cells are padded as bytes */
+        let size = 2 + 2 * U::BOUND.map_or(1, |max| max.le_bytes().len());
 
         /* Get bytes representing the value */
         let bytes = match self.display_mode {
             /* Print as ASCII if value is graphic */
-            DisplayMode::ASCII if is_ascii_printable(value) => {
+            DisplayMode::ASCII if is_ascii_printable(value.clone()) => {
                 let converted = value.to_u8(); 
                 /* Unsafe note - unwrap is safe, because guard only allows u8 values */
                 let unwrapped = unsafe {
@@ -138,17 +999,122 @@ where T: TapePointer, U: TapeCell {
                 },
             /* Print fallback for ASCII */
             DisplayMode::ASCII => 
-                format!("{value:#0size$X}",
-                    size = 2 + 2 * value.to_ne_bytes().as_ref().len()
-                    ).into_bytes(),
+                format!("{value:#0size$X}")
+                    .into_bytes(),
             /* Print raw numeric value*/
             DisplayMode::Numeric =>
                 value.to_string()
-                    .into_bytes()
+                    .into_bytes(),
+            /* Print numeric value in other bases */
+            DisplayMode::Hex =>
+                format!("{value:X}")
+                    .into_bytes(),
+            DisplayMode::Bin =>
+                format!("{value:b}")
+                    .into_bytes(),
+            DisplayMode::Oct =>
+                format!("{value:o}")
+                    .into_bytes(),
+            /* Write the cell's low byte as it is */
+            DisplayMode::Raw =>
+                vec![value.le_bytes()[0]],
+            /* Print as UTF-8 if value is a printable scalar value, with the same fallback as ASCII */
+            DisplayMode::Unicode => match unicode_printable(value.clone()) {
+                Some(character) =>
+                    character.to_string()
+                        .into_bytes(),
+                None =>
+                    format!("{value:#0size$X}")
+                        .into_bytes()
+                }
             };
         
-        /* Write to the output */
-        self.output.write(&bytes)?;
+        /* Surround the numeric values with their formatting */
+        let bytes = match self.display_mode.is_numeric() {
+            true => {
+                let first = ! replace(&mut self.numeric_written, true);
+                self.numeric_format.apply(&bytes, first)
+                },
+            false => bytes
+            };
+
+        self.emit(&bytes)?;
+
+        /* Show the output right away, when every output gets flushed */
+        if self.unbuffered {
+            self.output.flush()?;
+            }
+
+        Ok(())
+        }
+
+    /* Write the instruction pointer, and the cells around the pointer - the current one in brackets, and the ones off the tape skipped */
+    fn dump(&mut self, instr_ptr: usize) -> Result<(), RunError> {
+        /* Output written so far goes first, so they show up in order */
+        self.output.flush()?;
+
+        let position = self.tape.position();
+        let (_, cells) = self.window(position);
+
+        writeln!(self.dump, "#{instr_ptr}: pointer at {position}, cells: {cells}")?;
+
+        Ok(())
+        }
+
+    /* Write the instruction pointer, and the pointer's location */
+    fn show_pointer(&mut self, instr_ptr: usize) -> Result<(), RunError> {
+        self.output.flush()?;
+
+        writeln!(self.dump, "@{instr_ptr}: pointer at {}", self.tape.position())?;
+
+        Ok(())
+        }
+
+    /* Write the instruction pointer, and the cells around the pointer, from the location of the first one */
+    fn show_tape(&mut self, instr_ptr: usize) -> Result<(), RunError> {
+        self.output.flush()?;
+
+        let (first, cells) = self.window(self.tape.position());
+
+        writeln!(self.dump, "${instr_ptr}: cells from {first}: {cells}")?;
+
+        Ok(())
+        }
+
+    /* Get the location of the first cell around the position, and the cells' values - the current one in brackets, and the ones off the tape skipped */
+    fn window(&mut self, position: isize) -> (isize, String) {
+        let mut first = None;
+        let mut cells = Vec::new();
+
+        for index in position - DUMP_RADIUS ..= position + DUMP_RADIUS {
+            let Some(value) = self.peek(index) else {
+                continue;
+                };
+
+            first.get_or_insert(index);
+            cells.push(match index == position {
+                true => format!("[{value}]"),
+                false => value.to_string()
+                });
+            }
+
+        (first.unwrap_or(position), cells.join(" "))
+        }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<(), RunError> {
+        /* Check the output limit, before anything over it gets written */
+        self.written += bytes.len() as u64;
+        if let Some(limit) = self.max_output && self.written > limit {
+            return Err(RunError::OutputLimit(limit));
+            }
+
+        /* Write to the output, or the recording */
+        match &mut self.recording {
+            Some(recording) =>
+                recording.extend_from_slice(bytes),
+            None =>
+                self.output.write_all(bytes)?
+            }
 
         Ok(())
         }
@@ -157,11 +1123,31 @@ where T: TapePointer, U: TapeCell {
         /* Cautionary output flush */
         self.output.flush()?;
 
+        /* Take a single raw byte, when using byte semantics */
+        if self.byte_input {
+            let mut byte = [0];
+
+            return match self.input.read_exact(&mut byte) {
+                Ok(()) => {
+                    self.read += 1;
+                    self.tape.set(truncate_cell_value(u64::from(byte[0])));
+                    Ok(())
+                    },
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof =>
+                    self.end_of_input(),
+                Err(err) =>
+                    Err(err)
+                };
+            }
+
         /* Try to get input byte, as long as it isn't correct */
         loop {
-            /* Clear buffer, and read */
+            /* Clear buffer, and read - stop at the end of input, rather than prompting forever */
             self.read_buffer.clear();
-            self.input.read_line(&mut self.read_buffer)?;
+            match self.input.read_line(&mut self.read_buffer)? {
+                0 => return self.end_of_input(),
+                len => self.read += len as u64
+                };
 
             /* Check whether is correct, then set, and break */
             if let Ok(new_value) = parse_cell_value(&self.read_buffer.trim()) {
@@ -173,30 +1159,298 @@ where T: TapePointer, U: TapeCell {
             warn!("Please input correct data!");
             }
         }
+
+    /* Handle reading past the end of input, according to the settings */
+    fn end_of_input(&mut self) -> IOResult<()> {
+        match self.eof {
+            EofMode::Zero =>
+                self.tape.set(U::ZERO),
+            /* Unbounded cells were rejected while building */
+            EofMode::Max =>
+                if let Some(max) = U::BOUND {
+                    self.tape.set(max);
+                    },
+            EofMode::Unchanged =>
+                (),
+            EofMode::Error =>
+                return Err(ErrorKind::UnexpectedEof.into())
+            }
+
+        Ok(())
+        }
     }
 
 
+/* Function for checking whether a run can be resumed before the operation - operations replacing a loop share its location, so only the first one of them can */
+fn is_resumable(ir: &Ir, op_ptr: usize) -> bool {
+    ir.find_source(ir.source(op_ptr)) == Some(op_ptr)
+    }
+
+/* Function for checking whether the cells can be displayed in the mode - 1-bit cells have no characters, and only the numbers are formatted */
+fn check_display(bit_cells: bool, display_mode: DisplayMode, numeric_format: &NumericFormat) -> Result<(), BuildError> {
+    match (bit_cells, display_mode) {
+        (true, DisplayMode::ASCII | DisplayMode::Unicode) =>
+            Err(BuildError::AsciiBitCells),
+        (_, display_mode) if ! display_mode.is_numeric() && *numeric_format != NumericFormat::default() =>
+            Err(BuildError::NumericFormat),
+        _ => Ok(())
+        }
+    }
+
+
+/* Building's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum BuildError {
+    #[error("ASCII, or Unicode display is not supported for 1-bit cells")]
+    AsciiBitCells,
+    #[error("1-bit cells are not supported on an infinite tape")]
+    InfiniteBitCells,
+    #[error("1-bit cells are not supported on a growable tape")]
+    GrowableBitCells,
+    #[error("Fixed tape can't hold every cell of a 64-bit pointer - use a growable, or an infinite tape")]
+    FixedTapeTooLarge,
+    #[error("Pointer can address more cells, than this platform - use a smaller pointer, or an infinite tape")]
+    PointerTooWide,
+    #[error("Tape size has to be between 1, and the number of cells the pointer can address")]
+    TapeSize,
+    #[error("Infinite tape has no size to set")]
+    InfiniteTapeSize,
+    #[error("Number of tapes has to be at least 1")]
+    TapeCount,
+    #[error("Checkpoints can only hold a single tape")]
+    CheckpointTapes,
+    #[error("1-bit cells can only wrap around")]
+    CheckedBitCells,
+    #[error("1-bit cells are not supported on a shared tape")]
+    SharedBitCells,
+    #[error("1-bit cells are not supported on a custom tape")]
+    CustomBitCells,
+    #[error("Arbitrary-precision cells have no maximum value to set at the end of input")]
+    UnboundedEofMax,
+    #[error("Initial tape doesn't fit the tape, or its cells")]
+    InitialTape,
+    #[error("Starting cell is off the tape")]
+    StartPosition,
+    #[error("Fill value doesn't fit the cells")]
+    Fill,
+    #[error("Numeric format is only used by the numeric display modes")]
+    NumericFormat
+    }
+
 /* The Interpreter Builder container */
 pub struct InterpreterBuilder {
     display_mode: Option<DisplayMode>,
-    output: Option<BufWriter<Box<dyn Write>>>,
-    input: Option<BufReader<Box<dyn Read>>>
+    numeric_format: NumericFormat,
+    unbuffered: bool,
+    byte_input: bool,
+    eof: Option<EofMode>,
+    pointer_overflow: Option<PointerOverflow>,
+    cell_overflow: Option<CellOverflow>,
+    bit_cells: bool,
+    tape_kind: Option<TapeKind>,
+    tape_size: Option<usize>,
+    tapes: Option<usize>,
+    max_output: Option<u64>,
+    max_steps: Option<u64>,
+    time_limit: Option<Duration>,
+    seed: Option<u64>,
+    capture: Option<SharedBuffer>,
+    hook: Option<ArithHook>,
+    opt_level: Option<OptLevel>,
+    profile: bool,
+    fill: i64,
+    initial_tape: Vec<BigUint>,
+    start_position: isize,
+    pause: Option<PauseFlag>,
+    checkpoints: Option<(u64, CheckpointHook)>,
+    #[cfg(feature = "jit")]
+    jit_threshold: Option<u32>,
+    output: Option<BufWriter<Box<dyn Write + Send>>>,
+    input: Option<BufReader<Box<dyn Read + Send>>>,
+    dump: Option<Box<dyn Write + Send>>
     }
 
 impl InterpreterBuilder {
-    /* Build the Interpreter form the Builder container */
+    /* Build the Interpreter form the Builder container - panics on an invalid configuration */
     pub fn build<T, U>(self) -> Interpreter<T, U>
-    where T: TapePointer, U: TapeCell  {
-        Interpreter {
-            tape: Tape::default(),
+    where T: TapePointer + 'static, U: TapeCell + 'static {
+        match self.try_build() {
+            Ok(interp) => interp,
+            Err(err) => panic!("Invalid Interpreter configuration: {err}")
+            }
+        }
+
+    /* Build the Interpreter form the Builder container, after checking the settings are compatible */
+    pub fn try_build<T, U>(self) -> Result<Interpreter<T, U>, BuildError>
+    where T: TapePointer + 'static, U: TapeCell + 'static {
+        self.build_on(None)
+        }
+
+    /* Build the Interpreter over a clone of the shared tape - its kind, size, and tape count settings are left out, while the fill value, and the initial cells are written for every clone */
+    pub fn try_build_shared<T, U>(self, tape: &SharedTape<U>) -> Result<Interpreter<T, U>, BuildError>
+    where T: TapePointer + 'static, U: TapeCell + 'static {
+        if self.bit_cells {
+            return Err(BuildError::SharedBitCells);
+            }
+
+        self.build_on(Some(Box::new(tape.clone())))
+        }
+
+    /* Build the Interpreter over the given tape, like a prefilled, or a resized one - its kind, size, and tape count settings are left out, while the fill value, and the initial cells are still written, and the pointer starts at the starting cell */
+    pub fn try_build_with<T, U, B>(self, tape: B) -> Result<Interpreter<T, U>, BuildError>
+    where T: TapePointer + 'static, U: TapeCell + 'static, B: TapeBackend<U> + 'static {
+        if self.bit_cells {
+            return Err(BuildError::CustomBitCells);
+            }
+
+        self.build_on(Some(Box::new(tape)))
+        }
+
+    /* Build the Interpreter over the given tape, or the one picked by the settings */
+    fn build_on<T, U>(self, custom_tape: Option<Box<dyn TapeBackend<U>>>) -> Result<Interpreter<T, U>, BuildError>
+    where T: TapePointer + 'static, U: TapeCell + 'static {
+        let custom = custom_tape.is_some();
+        let display_mode = self.display_mode.unwrap_or_default();
+        let tape_kind = self.tape_kind.unwrap_or_default();
+        let pointer_overflow = self.pointer_overflow.unwrap_or_default();
+        let cell_overflow = self.cell_overflow.unwrap_or_default();
+
+        /* Check for incompatible settings */
+        if self.eof == Some(EofMode::Max) && U::BOUND.is_none() {
+            return Err(BuildError::UnboundedEofMax);
+            }
+
+        check_display(self.bit_cells, display_mode, &self.numeric_format)?;
+
+        match (self.bit_cells, tape_kind) {
+            (true, TapeKind::Infinite) =>
+                return Err(BuildError::InfiniteBitCells),
+            (true, TapeKind::Growable) =>
+                return Err(BuildError::GrowableBitCells),
+            (_, TapeKind::Fixed) if ! custom && self.tape_size.is_none() && T::MAX.to_usize().and_then(|max| max.checked_add(1)).is_none() =>
+                return Err(BuildError::FixedTapeTooLarge),
+            (_, TapeKind::Growable) if ! custom && self.tape_size.is_none() && T::MAX.to_usize().is_none() =>
+                return Err(BuildError::PointerTooWide),
+            _ => ()
+            };
+
+        if self.bit_cells && cell_overflow != CellOverflow::Wrap {
+            return Err(BuildError::CheckedBitCells);
+            }
+
+        match (self.tape_size, tape_kind) {
+            (Some(_), TapeKind::Infinite) =>
+                return Err(BuildError::InfiniteTapeSize),
+            (Some(size), _) if size == 0 || T::MAX.to_usize().is_none_or(|max| size - 1 > max) =>
+                return Err(BuildError::TapeSize),
+            _ => ()
+            };
+
+        match self.tapes {
+            Some(0) =>
+                return Err(BuildError::TapeCount),
+            Some(count) if count > 1 && self.checkpoints.is_some() =>
+                return Err(BuildError::CheckpointTapes),
+            _ => ()
+            };
+
+        /* Loops can be compiled only for a plain fixed tape, with no one listening for the wrapping, nor counting the executions, nor checking the bounds - compiled loops neither count the steps, nor look at the clock, the pause flag, and the checkpoints */
+        #[cfg(feature = "jit")]
+        let tiering = self.jit_threshold
+            .filter(|_| tape_kind == TapeKind::Fixed && ! custom && self.tape_size.is_none() && ! self.bit_cells && self.hook.is_none() && ! self.profile)
+            .filter(|_| pointer_overflow == PointerOverflow::Wrap && cell_overflow == CellOverflow::Wrap && self.tapes.is_none_or(|count| count == 1))
+            .filter(|_| self.max_steps.is_none() && self.time_limit.is_none() && self.pause.is_none() && self.checkpoints.is_none())
+            .map(Tiering::new);
+
+        /* Pick the tape layout - every one of several tapes gets the same */
+        let layout = || -> Box<dyn TapeBackend<U>> {
+            match (tape_kind, self.bit_cells) {
+                (TapeKind::Fixed, true) => Box::new(self.tape_size.map_or_else(BitTape::<T, U>::default, BitTape::with_size)),
+                (TapeKind::Fixed, false) => Box::new(self.tape_size.map_or_else(Tape::<T, U>::default, Tape::with_size)),
+                (TapeKind::Infinite, _) => Box::new(InfiniteTape::<U>::default()),
+                (TapeKind::Growable, _) => Box::new(self.tape_size.map_or_else(GrowableTape::<T, U>::default, GrowableTape::with_size))
+                }
+            };
+        let mut tape: Box<dyn TapeBackend<U>> = match (custom_tape, self.tapes) {
+            (Some(tape), _) => tape,
+            (None, Some(count)) if count > 1 => Box::new(MultiTape::new((0 .. count).map(|_| layout()).collect())
+                .ok_or(BuildError::TapeCount)?),
+            (None, _) => layout()
+            };
+
+        /* Check the fill value fits the cell type - 1-bit cells only hold zeros, and ones */
+        let fill = U::ZERO.checked_offset(self.fill)
+            .filter(|_| ! self.bit_cells || matches!(self.fill, 0 ..= 1))
+            .ok_or(BuildError::Fill)?;
+        /* Shared cells keep what the other clones wrote, unless told otherwise */
+        if self.fill != 0 {
+            tape.fill(fill);
+            }
+
+        /* Check the initial cells fit the cell type - 1-bit cells only hold zeros, and ones */
+        let initial_tape = self.initial_tape.iter()
+            .map(|value| U::from_exact(value)
+                .filter(|_| ! self.bit_cells || value.bits() <= 1)
+                )
+            .collect::<Option<Vec<_>>>()
+            .ok_or(BuildError::InitialTape)?;
+
+        let mut interp = Interpreter {
+            tape,
+            pointer: PhantomData,
             read_buffer: String::with_capacity(8),
-            display_mode: self.display_mode.unwrap_or_default(),
+            byte_input: self.byte_input,
+            eof: self.eof.unwrap_or_default(),
+            pointer_overflow,
+            cell_overflow,
+            bit_cells: self.bit_cells,
+            display_mode,
+            numeric_format: self.numeric_format,
+            numeric_written: false,
+            unbuffered: self.unbuffered,
+            max_output: self.max_output,
+            written: 0,
+            read: 0,
+            max_steps: self.max_steps,
+            time_limit: self.time_limit,
+            recording: None,
+            capture: self.capture,
+            hook: self.hook,
+            opt_level: self.opt_level.unwrap_or_default(),
+            profiling: self.profile,
+            op_counts: Vec::new(),
+            profile: None,
+            rng: self.seed.map_or_else(Rng::default, Rng::new),
+            initial_tape,
+            start_position: self.start_position,
+            pause: self.pause,
+            program: None,
+            paused_at: None,
+            break_pending: false,
+            procedures: ProcedureTable::default(),
+            checkpoints: self.checkpoints,
+            next_checkpoint: u64::MAX,
+            #[cfg(feature = "jit")]
+            tiering,
             output: self.output.unwrap_or(
-                BufWriter::new(Box::new(stdout().lock()))
+                BufWriter::new(Box::new(stdout()))
                 ),
             input: self.input.unwrap_or(
-                BufReader::new(Box::new(stdin().lock()))
+                BufReader::new(Box::new(stdin()))
+                ),
+            dump: self.dump.unwrap_or(
+                Box::new(stderr())
                 )
+            };
+
+        /* Check the starting cell, and the initial cells fit on the tape */
+        if ! interp.tape.seek(self.start_position) {
+            return Err(BuildError::StartPosition);
+            }
+
+        match interp.load_initial_tape() {
+            true => Ok(interp),
+            false => Err(BuildError::InitialTape)
             }
         }
 
@@ -205,12 +1459,1413 @@ impl InterpreterBuilder {
         self.display_mode = Some(value);
         self
         }
-    pub fn output(mut self, value: Box<dyn Write>) -> Self {
+    /* Separator, prefix, and suffix of the values written in the numeric display modes */
+    pub fn numeric_format(mut self, value: NumericFormat) -> Self {
+        self.numeric_format = value;
+        self
+        }
+    /* Flush every output right away, for the interactive programs - it's otherwise buffered until an input, or the end */
+    pub const fn unbuffered(mut self, value: bool) -> Self {
+        self.unbuffered = value;
+        self
+        }
+    pub const fn byte_input(mut self, value: bool) -> Self {
+        self.byte_input = value;
+        self
+        }
+    /* Behaviour of the input instruction at the end of the input - failing by default */
+    pub const fn eof(mut self, value: EofMode) -> Self {
+        self.eof = Some(value);
+        self
+        }
+    /* Moving the pointer off the tape fails the run, instead of wrapping - the loops aren't rewritten then */
+    pub const fn pointer_overflow(mut self, value: PointerOverflow) -> Self {
+        self.pointer_overflow = Some(value);
+        self
+        }
+    /* Taking a cell past its bounds stops it there, or fails the run, instead of wrapping - the loops aren't rewritten then */
+    pub const fn cell_overflow(mut self, value: CellOverflow) -> Self {
+        self.cell_overflow = Some(value);
+        self
+        }
+    pub const fn bit_cells(mut self, value: bool) -> Self {
+        self.bit_cells = value;
+        self
+        }
+    pub const fn tape_kind(mut self, value: TapeKind) -> Self {
+        self.tape_kind = Some(value);
+        self
+        }
+    /* Number of cells the pointer wraps at, instead of the whole range of the pointer - not for the infinite tape */
+    pub const fn tape_size(mut self, value: usize) -> Self {
+        self.tape_size = Some(value);
+        self
+        }
+    /* Number of tapes of the same layout, switched between by the multi-tape dialect - the runs on several ones can't be snapshotted */
+    pub const fn tapes(mut self, value: usize) -> Self {
+        self.tapes = Some(value);
+        self
+        }
+    pub const fn max_output(mut self, value: u64) -> Self {
+        self.max_output = Some(value);
+        self
+        }
+    /* Number of executed instructions, after which the run is aborted - optimised loops count all of their instructions at once */
+    pub const fn max_steps(mut self, value: u64) -> Self {
+        self.max_steps = Some(value);
+        self
+        }
+    /* Time, after which each run is aborted - unlike the pause flag, the run can't be resumed then */
+    pub const fn time_limit(mut self, value: Duration) -> Self {
+        self.time_limit = Some(value);
+        self
+        }
+    pub const fn seed(mut self, value: u64) -> Self {
+        self.seed = Some(value);
+        self
+        }
+    pub fn output(mut self, value: Box<dyn Write + Send>) -> Self {
         self.output = Some(BufWriter::new(value));
         self
         }
-    pub fn input(mut self, value: Box<dyn Read>) -> Self {
+    pub fn input(mut self, value: Box<dyn Read + Send>) -> Self {
         self.input = Some(BufReader::new(value));
         self
         }
+    /* Destination of the `#`, `@`, and `$` instructions' prints - stderr by default */
+    pub fn dump_output(mut self, value: Box<dyn Write + Send>) -> Self {
+        self.dump = Some(value);
+        self
+        }
+    pub const fn opt_level(mut self, value: OptLevel) -> Self {
+        self.opt_level = Some(value);
+        self
+        }
+    /* Count the executions of every instruction, and loop - the loops only get their runs merged then, and aren't compiled */
+    pub const fn profile(mut self, value: bool) -> Self {
+        self.profile = value;
+        self
+        }
+    /* Value every cell starts with, instead of zero - it comes back on every reset, under the initial cells */
+    pub const fn fill(mut self, value: i64) -> Self {
+        self.fill = value;
+        self
+        }
+    /* Cells the tape starts with, from the first one on - they come back on every reset */
+    pub fn initial_tape<U>(mut self, value: &[U]) -> Self
+    where U: TapeCell {
+        self.initial_tape = value.iter()
+            .map(TapeCell::exact)
+            .collect();
+        self
+        }
+    /* Cell the pointer starts at, instead of the first one - negative ones are only on the infinite tape */
+    pub const fn start_position(mut self, value: isize) -> Self {
+        self.start_position = value;
+        self
+        }
+    /* Flag pausing the runs - a paused run can be captured with a snapshot, and resumed from it */
+    pub fn pause_flag(mut self, value: PauseFlag) -> Self {
+        self.pause = Some(value);
+        self
+        }
+    /* Snapshot the runs of the source code's instructions every given number of executed instructions, and pass them to the hook */
+    pub fn checkpoint(mut self, every: u64, hook: CheckpointHook) -> Self {
+        self.checkpoints = Some((every.max(1), hook));
+        self
+        }
+    pub fn arith_hook(mut self, value: ArithHook) -> Self {
+        self.hook = Some(value);
+        self
+        }
+    /* Number of iterations, after which a loop gets compiled to native code - nothing turns the tiered execution off */
+    #[cfg(feature = "jit")]
+    pub const fn jit_threshold(mut self, value: Option<u32>) -> Self {
+        self.jit_threshold = value;
+        self
+        }
+    /* Write the output into memory, so it ends up in the outcome of a consuming run */
+    pub fn capture_output(mut self) -> Self {
+        let buffer = SharedBuffer::default();
+
+        self.output = Some(BufWriter::new(Box::new(buffer.clone())));
+        self.capture = Some(buffer);
+        self
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::interp::*;
+
+    /* Helper function, for the cells widened without a loss */
+    fn exact<const N: usize>(cells: [(isize, u64); N]) -> Vec<(isize, BigUint)> {
+        cells.into_iter()
+            .map(|(index, value)| (index, BigUint::from(value)))
+            .collect()
+        }
+
+    #[test]
+    fn build_default() {
+        let interp = Interpreter::builder()
+            .try_build::<u8, u8>();
+
+        assert!(interp.is_ok());
+        }
+
+    #[test]
+    fn build_bit_cells() {
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .try_build::<u8, u8>();
+
+        assert!(interp.is_ok());
+        }
+
+    #[test]
+    fn build_err_ascii_bit_cells() {
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .display_mode(DisplayMode::ASCII)
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::AsciiBitCells));
+        }
+
+    #[test]
+    fn build_err_infinite_bit_cells() {
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .tape_kind(TapeKind::Infinite)
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::InfiniteBitCells));
+        }
+
+    #[test]
+    #[should_panic]
+    fn build_invalid() {
+        Interpreter::builder()
+            .bit_cells(true)
+            .display_mode(DisplayMode::ASCII)
+            .build::<u8, u8>();
+        }
+
+    #[test]
+    fn run_rle_captured() {
+        let rle = eval_instr("++>+++.<[-]>>---")
+            .expect("Unreachable")
+            .encode_run_length();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .build::<u8, u8>();
+        interp.run_rle(&rle)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"3\n".to_vec()));
+        assert_eq!(interp.tape.position(), 2);
+        assert_eq!(interp.tape_values(), exact([(1, 3), (2, 253)]));
+        }
+
+    #[test]
+    fn run_consuming_captured() {
+        let instr = eval_instr("++>+++.<[-]")
+            .expect("Unreachable");
+
+        let outcome = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(outcome, RunOutcome {
+            output: Some(b"3\n".to_vec()),
+            position: 0,
+            tape: exact([(1, 3)]),
+            stats: RunStats {
+                instructions: 11,
+                executed: 11
+                }
+            });
+        }
+
+    #[test]
+    fn custom_tape_run() {
+        let mut tape = Tape::<u8, u8>::with_size(4);
+        tape.set(2);
+        tape.right();
+        tape.set(3);
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .try_build_with::<u8, u8, _>(tape)
+            .expect("Unreachable");
+
+        interp.run(&eval_instr(">[-<+>]<.").expect("Unreachable"))
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"5\n".to_vec()));
+        assert_eq!(interp.cells(), exact([(0, 5)]));
+
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .try_build_with::<u8, u8, _>(Tape::<u8, u8>::default());
+
+        assert_eq!(interp.err(), Some(BuildError::CustomBitCells));
+        }
+
+    #[test]
+    fn shared_tape_run() {
+        let tape = SharedTape::<u8>::with_size(16);
+
+        let producer = Interpreter::builder()
+            .capture_output()
+            .try_build_shared::<u8, u8>(&tape)
+            .expect("Unreachable");
+        let mut consumer = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .start_position(1)
+            .capture_output()
+            .try_build_shared::<u8, u8>(&tape)
+            .expect("Unreachable");
+
+        std::thread::spawn(move || producer.run_consuming(&eval_instr(">+++<<+").expect("Unreachable")))
+            .join()
+            .expect("Unreachable")
+            .expect("Unreachable");
+        consumer.run(&eval_instr("[-<+>]<.").expect("Unreachable"))
+            .expect("Unreachable");
+
+        assert_eq!(consumer.capture.as_ref().map(SharedBuffer::take), Some(b"3\n".to_vec()));
+        assert_eq!(tape.cells(), vec![(0, 3), (15, 1)]);
+
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .try_build_shared::<u8, u8>(&tape);
+
+        assert_eq!(interp.err(), Some(BuildError::SharedBitCells));
+        }
+
+    #[test]
+    fn run_with_report() {
+        let instr = eval_instr(",.>,+.,")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .byte_input(true)
+            .input(Box::new(&b"ab"[..]))
+            .eof(EofMode::Unchanged)
+            .capture_output()
+            .build::<u8, u8>();
+        let report = interp.run_with_report(&instr)
+            .expect("Unreachable");
+
+        assert_eq!((report.executed, report.set_cells, report.written, report.read), (7, 2, 4, 2));
+        }
+
+    #[test]
+    fn run_with_io() {
+        let instr = eval_instr(",[+.,]")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Raw)
+            .byte_input(true)
+            .eof(EofMode::Zero)
+            .capture_output()
+            .build::<u8, u8>();
+
+        assert_eq!(interp.run_with_io(&instr, b"HAL").ok(), Some(b"IBM".to_vec()));
+        assert_eq!(interp.run_with_io(&instr, b"").ok(), Some(Vec::new()));
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(Vec::new()));
+        }
+
+    #[test]
+    fn run_consuming_uncaptured() {
+        let instr = eval_instr("+")
+            .expect("Unreachable");
+
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .build::<u8, u8>()
+            .run_consuming(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(outcome.output, None);
+        assert_eq!(outcome.tape, exact([(0, 1)]));
+        }
+
+    #[test]
+    #[cfg(feature = "jit")]
+    fn run_consuming_tiered() {
+        let instr = eval_instr("++++++++[>++++++++[>++++<-]<-]>>[>+>+<<-]>.<<+[>-]")
+            .expect("Unreachable");
+
+        let run = |threshold| Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .jit_threshold(threshold)
+            .capture_output()
+            .build::<u16, u16>()
+            .run_consuming(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(run(Some(1)), run(None));
+        }
+
+    #[test]
+    fn reset_reuse() {
+        let first = eval_instr(">+++.")
+            .expect("Unreachable");
+        let second = eval_instr("+.")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .max_output(4)
+            .capture_output()
+            .build::<u8, u8>();
+
+        interp.run(&first)
+            .expect("Unreachable");
+        interp.reset();
+
+        let outcome = interp.run_consuming(&second)
+            .expect("Unreachable");
+
+        assert_eq!(outcome.output, Some(b"1\n".to_vec()));
+        assert_eq!(outcome.tape, exact([(0, 1)]));
+        }
+
+    #[test]
+    fn interp_send() {
+        let instr = eval_instr("+++.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+
+        std::thread::spawn(move || interp.run(&instr))
+            .join()
+            .expect("Unreachable")
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), b"3\n");
+        }
+
+    #[test]
+    fn profile_run() {
+        let instr = eval_instr("+++[->+<]")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .profile(true)
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+        let profile = interp.take_profile()
+            .expect("Unreachable");
+
+        assert_eq!(profile.counts(), &[1, 1, 1, 1, 3, 3, 3, 3, 3]);
+        assert_eq!(profile.loops()[0].iterations, 3);
+        assert_eq!(interp.take_profile(), None);
+        }
+
+    #[test]
+    fn dump_state() {
+        let instr = eval_instr_with(">++#<.#", &[Dialect::ExtDebug])
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .dump_output(Box::new(buffer.clone()))
+            .build::<u8, u8>()
+            .run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), b"#3: pointer at 1, cells: 0 [2] 0 0 0 0 0\n#6: pointer at 0, cells: [0] 2 0 0 0 0\n");
+        }
+
+    #[test]
+    fn inspect_breakpoint() {
+        let instr = eval_instr_with(">++@<$!+.", &[Dialect::ExtInspect])
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .dump_output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+
+        assert!(matches!(interp.run(&instr), Err(RunError::Breakpoint(7))));
+        assert_eq!(buffer.take(), b"@3: pointer at 1\n$5: cells from 0: [0] 2 0 0 0 0\n");
+
+        let snapshot = interp.snapshot()
+            .expect("Unreachable");
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .build::<u8, u8>();
+        interp.resume(&instr, &snapshot)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"1\n".to_vec()));
+        }
+
+    #[test]
+    fn display_mode_change() {
+        let instr = eval_instr("+++++++++++++++++++++++++++++++++.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+        interp.set_display_mode(DisplayMode::ASCII)
+            .expect("Unreachable");
+        interp.run(&eval_instr(".").expect("Unreachable"))
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), b"33\n!\n");
+
+        let mut interp = Interpreter::builder()
+            .bit_cells(true)
+            .build::<u8, u8>();
+
+        assert_eq!(interp.set_display_mode(DisplayMode::ASCII), Err(BuildError::AsciiBitCells));
+
+        /* Numeric format is checked the same, as when building */
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .numeric_format(NumericFormat {
+                separator: b", ".to_vec(),
+                ..NumericFormat::default()
+                })
+            .build::<u8, u8>();
+
+        assert_eq!(interp.set_display_mode(DisplayMode::ASCII), Err(BuildError::NumericFormat));
+        assert_eq!(interp.set_display_mode(DisplayMode::Hex), Ok(()));
+        }
+
+    #[test]
+    fn cells_after_run() {
+        let instr = eval_instr("++>>+++<")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.cells(), exact([(0, 2), (2, 3)]));
+        assert_eq!(interp.position(), 1);
+        }
+
+    #[test]
+    fn tape_image_width() {
+        let instr = eval_instr(">+++<-")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .tape_kind(TapeKind::Infinite)
+            .build::<u8, u16>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.tape_image(), (0, vec![0xFF, 0xFF, 3, 0]));
+        }
+
+    #[test]
+    fn growable_tape_run() {
+        /* 32-bit pointer, with only the reached cells allocated */
+        let instr = eval_instr("+>>>>++[-<+>]<<<<[->+<]>.")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .tape_kind(TapeKind::Growable)
+            .capture_output()
+            .build::<u32, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.cells(), exact([(1, 1), (3, 2)]));
+        assert_eq!(interp.tape_image(), (0, vec![0, 1, 0, 2, 0, 0, 0, 0]));
+        }
+
+    #[test]
+    fn wide_sizes_run() {
+        let instr = eval_instr("->++.")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .tape_kind(TapeKind::Growable)
+            .capture_output()
+            .build::<u64, u64>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"2\n".to_vec()));
+        assert_eq!(interp.cells(), exact([(0, u64::MAX), (1, 2)]));
+        }
+
+    #[test]
+    fn build_err_fixed_tape_too_large() {
+        let interp = Interpreter::builder()
+            .try_build::<u64, u8>();
+        let sized = Interpreter::builder()
+            .tape_size(30000)
+            .try_build::<u64, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::FixedTapeTooLarge));
+        assert!(sized.is_ok());
+        }
+
+    #[test]
+    fn build_err_tape_size() {
+        let empty = Interpreter::builder()
+            .tape_size(0)
+            .try_build::<u8, u8>();
+        let too_long = Interpreter::builder()
+            .tape_size(257)
+            .try_build::<u8, u8>();
+        let growable = Interpreter::builder()
+            .tape_kind(TapeKind::Growable)
+            .tape_size(257)
+            .try_build::<u8, u8>();
+        let infinite = Interpreter::builder()
+            .tape_kind(TapeKind::Infinite)
+            .tape_size(30000)
+            .try_build::<u16, u8>();
+
+        assert_eq!(empty.err(), Some(BuildError::TapeSize));
+        assert_eq!(too_long.err(), Some(BuildError::TapeSize));
+        assert_eq!(growable.err(), Some(BuildError::TapeSize));
+        assert_eq!(infinite.err(), Some(BuildError::InfiniteTapeSize));
+        }
+
+    #[test]
+    fn multi_tape_run() {
+        let instr = eval_instr_with("++>+v+++^.v.^>.", &[Dialect::MultiTape])
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .tapes(2)
+            .capture_output()
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"130\n".to_vec()));
+        assert_eq!(interp.cells(), exact([(0, 2), (1, 1)]));
+        assert_eq!(interp.all_cells(), vec![(0, 0, BigUint::from(2_u8)), (0, 1, BigUint::from(1_u8)), (1, 0, BigUint::from(3_u8))]);
+        assert!(matches!(interp.warm_start(&instr), Err(RunError::MultipleTapes)));
+
+        let tapes = Interpreter::builder()
+            .tapes(0)
+            .try_build::<u8, u8>();
+        let checkpoints = Interpreter::builder()
+            .tapes(2)
+            .checkpoint(10, Box::new(|_| Ok(())))
+            .try_build::<u8, u8>();
+
+        assert_eq!(tapes.err(), Some(BuildError::TapeCount));
+        assert_eq!(checkpoints.err(), Some(BuildError::CheckpointTapes));
+        }
+
+    #[test]
+    fn tape_size_run() {
+        let instr = eval_instr("<+>>>>[-]+.")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .tape_size(30000)
+            .capture_output()
+            .build::<u16, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"1\n".to_vec()));
+        assert_eq!(interp.cells(), exact([(3, 1), (29999, 1)]));
+        }
+
+    #[test]
+    fn pointer_out_of_bounds() {
+        let instr = eval_instr("+[>+]")
+            .expect("Unreachable");
+
+        let outcome = Interpreter::builder()
+            .tape_size(3)
+            .pointer_overflow(PointerOverflow::Error)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&instr);
+        let infinite = Interpreter::builder()
+            .tape_kind(TapeKind::Infinite)
+            .pointer_overflow(PointerOverflow::Error)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&eval_instr("<+>").expect("Unreachable"));
+
+        assert!(matches!(outcome, Err(RunError::PointerOutOfBounds { position: 3, instruction: 2 })));
+        assert!(infinite.is_ok());
+        }
+
+    #[test]
+    fn pointer_out_of_bounds_single_steps() {
+        /* Moves get checked on their own, before the opposite ones could cancel them out */
+        for source in ["<>.", "<<<<>>>>>."] {
+            let outcome = Interpreter::builder()
+                .pointer_overflow(PointerOverflow::Error)
+                .capture_output()
+                .build::<u8, u8>()
+                .run_consuming(&eval_instr(source).expect("Unreachable"));
+
+            assert!(matches!(outcome, Err(RunError::PointerOutOfBounds { position: -1, instruction: 0 })));
+            }
+        }
+
+    #[test]
+    fn pointer_left_of_start() {
+        let instr = eval_instr("<+.")
+            .expect("Unreachable");
+
+        /* Helper for running on a growable tape, with the pointer of the given size */
+        fn run<T>(instr: &InstructionSet, pointer_overflow: PointerOverflow) -> Result<RunOutcome, RunError>
+        where T: TapePointer + 'static {
+            Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .tape_kind(TapeKind::Growable)
+                .pointer_overflow(pointer_overflow)
+                .capture_output()
+                .build::<T, u8>()
+                .run_consuming(instr)
+            }
+
+        /* Wrapping pointer goes to the last cell, which is the signed range's end for a 64-bit one */
+        let wrapped = [
+            run::<u8>(&instr, PointerOverflow::Wrap).map(|outcome| outcome.tape),
+            run::<u32>(&instr, PointerOverflow::Wrap).map(|outcome| outcome.tape),
+            run::<u64>(&instr, PointerOverflow::Wrap).map(|outcome| outcome.tape)
+            ];
+        let checked = [
+            run::<u8>(&instr, PointerOverflow::Error),
+            run::<u32>(&instr, PointerOverflow::Error),
+            run::<u64>(&instr, PointerOverflow::Error)
+            ];
+
+        assert_eq!(wrapped.map(Result::ok), [
+            Some(exact([(0xFF, 1)])),
+            Some(exact([(0xFFFF_FFFF, 1)])),
+            Some(exact([(isize::MAX, 1)]))
+            ]);
+        assert!(checked.iter().all(|outcome| matches!(outcome, Err(RunError::PointerOutOfBounds { position: -1, instruction: 0 }))));
+        }
+
+    #[test]
+    fn cell_overflow_modes() {
+        let mut saturated = Interpreter::builder()
+            .cell_overflow(CellOverflow::Saturate)
+            .capture_output()
+            .build::<u8, i8>();
+        saturated.run(&eval_instr(&"+".repeat(130)).expect("Unreachable"))
+            .expect("Unreachable");
+        let outcome = Interpreter::builder()
+            .cell_overflow(CellOverflow::Error)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&eval_instr(">>+<<-").expect("Unreachable"));
+
+        assert_eq!(saturated.cells(), exact([(0, 127)]));
+        assert!(matches!(outcome, Err(RunError::CellOverflow { position: 0, instruction: 5 })));
+        }
+
+    #[test]
+    fn cell_overflow_single_steps() {
+        let instr = eval_instr("-+.")
+            .expect("Unreachable");
+
+        /* Decrement gets checked, or saturated on its own, before the increment could cancel it out */
+        let mut saturated = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .cell_overflow(CellOverflow::Saturate)
+            .capture_output()
+            .build::<u8, u8>();
+        saturated.run(&instr)
+            .expect("Unreachable");
+        let outcome = Interpreter::builder()
+            .cell_overflow(CellOverflow::Error)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&instr);
+        let fused = Interpreter::builder()
+            .cell_overflow(CellOverflow::Error)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_ir(&Ir::lower(&instr));
+
+        assert_eq!(saturated.capture.as_ref().map(SharedBuffer::take), Some(b"1\n".to_vec()));
+        assert!(matches!(outcome, Err(RunError::CellOverflow { position: 0, instruction: 0 })));
+        assert!(matches!(fused, Err(RunError::FusedOperations)));
+        }
+
+    #[test]
+    fn build_err_checked_bit_cells() {
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .cell_overflow(CellOverflow::Saturate)
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::CheckedBitCells));
+        }
+
+    #[test]
+    fn build_err_numeric_format() {
+        let format = || NumericFormat {
+            separator: b", ".to_vec(),
+            ..NumericFormat::default()
+            };
+
+        for display_mode in [DisplayMode::ASCII, DisplayMode::Unicode, DisplayMode::Raw] {
+            let interp = Interpreter::builder()
+                .display_mode(display_mode)
+                .numeric_format(format())
+                .try_build::<u8, u8>();
+
+            assert_eq!(interp.err(), Some(BuildError::NumericFormat));
+            }
+
+        let interp = Interpreter::builder()
+            .display_mode(DisplayMode::Hex)
+            .numeric_format(format())
+            .try_build::<u8, u8>();
+
+        assert!(interp.is_ok());
+        }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn build_err_pointer_too_wide() {
+        let interp = Interpreter::builder()
+            .tape_kind(TapeKind::Growable)
+            .try_build::<u64, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::PointerTooWide));
+        }
+
+    #[test]
+    fn build_err_unbounded_eof_max() {
+        let interp = Interpreter::builder()
+            .eof(EofMode::Max)
+            .try_build::<u8, BigUint>();
+
+        assert_eq!(interp.err(), Some(BuildError::UnboundedEofMax));
+        }
+
+    #[test]
+    fn signed_cells_run() {
+        let instr = eval_instr(&format!("-.>{}.", "+".repeat(128)))
+            .expect("Unreachable");
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let observed = events.clone();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .arith_hook(Box::new(move |event| {
+                observed.lock()
+                    .expect("Unreachable")
+                    .push(event);
+                ControlFlow::Continue(())
+                }))
+            .build::<u8, i8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"-1-128\n".to_vec()));
+        assert_eq!(interp.cells(), exact([(0, 0xFF), (1, 0x80)]));
+        assert_eq!(*events.lock().expect("Unreachable"), vec![ArithEvent::CellOverflow]);
+        }
+
+    #[test]
+    fn big_cells_run() {
+        let instr = eval_instr(&format!("+{}.[-]-.", "[->++<]>[-<+>]<".repeat(70)))
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .build::<u8, BigUint>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"11805916207174113034240\n".to_vec()));
+        }
+
+    #[test]
+    fn big_cells_exact() {
+        let instr = eval_instr(&format!("+{}>+", "[->++<]>[-<+>]<".repeat(70)))
+            .expect("Unreachable");
+        let wide = BigUint::from(1_u8) << 70_u32;
+
+        let mut interp = Interpreter::builder()
+            .build::<u8, BigUint>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.cells(), vec![(0, wide.clone()), (1, BigUint::from(1_u8))]);
+        assert_eq!(interp.peek(0), Some(wide.clone()));
+        assert_eq!(interp.tape_image().1[.. 23], *b"1180591620717411303424\n");
+
+        let snapshot = interp.capture(0, 0)
+            .expect("Unreachable");
+        let restored = Snapshot::decode(&snapshot.encode())
+            .expect("Unreachable");
+
+        assert_eq!(restored.cells(), interp.cells());
+        }
+
+    #[test]
+    fn big_cells_underflow() {
+        let instr = eval_instr("+--")
+            .expect("Unreachable");
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let observed = events.clone();
+
+        /* Cell stops at zero, but it's still reported as the underflow */
+        let mut interp = Interpreter::builder()
+            .arith_hook(Box::new(move |event| {
+                observed.lock()
+                    .expect("Unreachable")
+                    .push(event);
+                ControlFlow::Continue(())
+                }))
+            .build::<u8, BigUint>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.cells(), Vec::new());
+        assert_eq!(*events.lock().expect("Unreachable"), vec![ArithEvent::CellUnderflow]);
+
+        let checked = Interpreter::builder()
+            .cell_overflow(CellOverflow::Error)
+            .build::<u8, BigUint>()
+            .run(&instr);
+
+        assert!(matches!(checked, Err(RunError::CellOverflow { position: 0, .. })));
+        }
+
+    #[test]
+    fn big_cells_opt_levels() {
+        let instr = eval_instr("-+.>+[->++<]>.")
+            .expect("Unreachable");
+
+        /* Decrement stops at zero, so the increment isn't cancelled out by it at any level */
+        for opt_level in [OptLevel::O0, OptLevel::O1, OptLevel::O2, OptLevel::O3] {
+            let mut interp = Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .opt_level(opt_level)
+                .capture_output()
+                .build::<u8, BigUint>();
+            interp.run(&instr)
+                .expect("Unreachable");
+
+            assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"12\n".to_vec()));
+            }
+
+        let fused = Interpreter::builder()
+            .build::<u8, BigUint>()
+            .run_ir(&Ir::lower(&instr));
+
+        assert!(matches!(fused, Err(RunError::FusedOperations)));
+        assert!(! Ir::lower_unbounded_at(&eval_instr("[+]").expect("Unreachable"), OptLevel::O3).ops().contains(&IrOp::Set(0)));
+        }
+
+    #[test]
+    fn initial_tape_run() {
+        let instr = eval_instr("[>]<.")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .initial_tape(&[3u8, 1, 2])
+            .capture_output()
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"2\n".to_vec()));
+
+        interp.reset();
+
+        assert_eq!(interp.cells(), exact([(0, 3), (1, 1), (2, 2)]));
+        assert_eq!(interp.position(), 0);
+        }
+
+    #[test]
+    fn procedures_run() {
+        let instr = eval_instr_with("(+++)>+(..)<:>:", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        for level in [OptLevel::O0, OptLevel::O3] {
+            let mut interp = Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .opt_level(level)
+                .capture_output()
+                .build::<u8, u8>();
+            interp.run(&instr)
+                .expect("Unreachable");
+
+            assert_eq!(interp.capture.as_ref().map(SharedBuffer::take), Some(b"11\n".to_vec()));
+            assert_eq!(interp.cells(), exact([(0, 3), (1, 1)]));
+            }
+
+        let instr = eval_instr_with("(+++)>++:", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        assert!(matches!(Interpreter::builder().build::<u8, u8>().run(&instr), Err(RunError::UnknownProcedure { value: 2, instruction: 8 })));
+
+        let instr = eval_instr_with("(:):", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        assert!(matches!(Interpreter::builder().build::<u8, u8>().run(&instr), Err(RunError::CallDepth(1))));
+        }
+
+    #[test]
+    fn start_position_run() {
+        let instr = eval_instr("+<+")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .initial_tape(&[7u8])
+            .start_position(2)
+            .capture_output()
+            .build::<u8, u8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.cells(), exact([(0, 7), (1, 1), (2, 1)]));
+
+        interp.reset();
+
+        assert_eq!(interp.position(), 2);
+
+        let interp = Interpreter::builder()
+            .tape_size(10)
+            .start_position(10)
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::StartPosition));
+        }
+
+    #[test]
+    fn fill_run() {
+        let instr = eval_instr(">+>>-")
+            .expect("Unreachable");
+
+        let mut interp = Interpreter::builder()
+            .tape_kind(TapeKind::Growable)
+            .fill(-1)
+            .initial_tape(&[2i8])
+            .capture_output()
+            .build::<u8, i8>();
+        interp.run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(interp.peek(1), Some(BigUint::ZERO));
+        assert_eq!(interp.peek(2), Some(BigUint::from(0xFF_u8)));
+        assert_eq!(interp.cells(), exact([(0, 2), (1, 0), (3, 0xFE)]));
+
+        interp.reset();
+
+        assert_eq!(interp.cells(), exact([(0, 2)]));
+
+        let interp = Interpreter::builder()
+            .fill(-1)
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::Fill));
+        }
+
+    #[test]
+    fn initial_tape_invalid() {
+        let interp = Interpreter::builder()
+            .initial_tape(&[256u16])
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::InitialTape));
+
+        let interp = Interpreter::builder()
+            .initial_tape(&[0u8; 257])
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::InitialTape));
+
+        let interp = Interpreter::builder()
+            .bit_cells(true)
+            .initial_tape(&[2u8])
+            .try_build::<u8, u8>();
+
+        assert_eq!(interp.err(), Some(BuildError::InitialTape));
+        }
+
+    /* Input, which pauses the run once it gets read */
+    struct PausingInput (
+        PauseFlag
+        );
+
+    impl Read for PausingInput {
+        fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+            self.0.pause();
+            buf[0] = 5;
+
+            Ok(1)
+            }
+        }
+
+    #[test]
+    fn pause_snapshot_resume() {
+        let instr = eval_instr("+,>++.<.")
+            .expect("Unreachable");
+        let pause = PauseFlag::default();
+        let buffer = SharedBuffer::default();
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .byte_input(true)
+            .input(Box::new(PausingInput(pause.clone())))
+            .pause_flag(pause)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+
+        assert!(matches!(interp.run(&instr), Err(RunError::Paused(2))));
+
+        let snapshot = interp.snapshot()
+            .expect("Unreachable");
+        let snapshot = Snapshot::decode(&snapshot.encode())
+            .expect("Unreachable");
+
+        assert_eq!(snapshot.instr_ptr(), 2);
+        assert_eq!(snapshot.cells(), exact([(0, 5)]));
+
+        /* Continued on another thread */
+        std::thread::spawn(move || {
+            Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .output(Box::new(buffer.clone()))
+                .build::<u8, u8>()
+                .resume(&instr, &snapshot)
+                .expect("Unreachable");
+
+            assert_eq!(buffer.take(), b"25\n");
+            })
+            .join()
+            .expect("Unreachable");
+        }
+
+    #[test]
+    fn checkpoint_resume() {
+        let instr = eval_instr("++++[>++<-]>.")
+            .expect("Unreachable");
+        let snapshots = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let stored = snapshots.clone();
+
+        let outcome = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .checkpoint(10, Box::new(move |snapshot| {
+                stored.lock()
+                    .expect("Unreachable")
+                    .push(snapshot.encode());
+                Ok(())
+                }))
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&instr)
+            .expect("Unreachable");
+        let snapshots = snapshots.lock()
+            .expect("Unreachable");
+
+        assert_eq!(outcome.output, Some(b"8\n".to_vec()));
+        assert!(! snapshots.is_empty());
+
+        /* Every checkpoint continues into the same result */
+        for bytes in snapshots.iter() {
+            let snapshot = Snapshot::decode(bytes)
+                .expect("Unreachable");
+            let buffer = SharedBuffer::default();
+
+            Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .output(Box::new(buffer.clone()))
+                .build::<u8, u8>()
+                .resume(&instr, &snapshot)
+                .expect("Unreachable");
+
+            assert_eq!(buffer.take(), b"8\n");
+            }
+        }
+
+    #[test]
+    fn step_limit() {
+        let instr = eval_instr("+[]")
+            .expect("Unreachable");
+
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .max_steps(1000)
+            .build::<u8, u8>()
+            .run(&instr);
+
+        assert!(matches!(outcome, Err(RunError::StepLimit(1000))));
+
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .max_steps(3)
+            .build::<u8, u8>()
+            .run(&eval_instr("+++").expect("Unreachable"));
+
+        assert!(outcome.is_ok());
+        }
+
+    #[test]
+    #[cfg(feature = "jit")]
+    fn step_limit_tiered() {
+        let instr = eval_instr("+[]")
+            .expect("Unreachable");
+
+        /* Hot loop stays in the interpreter, so its steps get counted */
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .jit_threshold(Some(1))
+            .max_steps(1000)
+            .build::<u8, u8>()
+            .run(&instr);
+
+        assert!(matches!(outcome, Err(RunError::StepLimit(1000))));
+        }
+
+    #[test]
+    fn time_limit() {
+        let instr = eval_instr("+[]")
+            .expect("Unreachable");
+
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .time_limit(Duration::ZERO)
+            .build::<u8, u8>()
+            .run(&instr);
+
+        assert!(matches!(outcome, Err(RunError::Timeout(Duration::ZERO))));
+        }
+
+    #[test]
+    #[cfg(feature = "jit")]
+    fn time_limit_tiered() {
+        let instr = eval_instr("+[]")
+            .expect("Unreachable");
+
+        /* Loop gets hot long before the time is up, but stays in the interpreter, so the clock gets checked */
+        let outcome = Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .jit_threshold(Some(1))
+            .time_limit(Duration::from_millis(10))
+            .build::<u8, u8>()
+            .run(&instr);
+
+        assert!(matches!(outcome, Err(RunError::Timeout(_))));
+        }
+
+    #[test]
+    fn eof_modes() {
+        let instr = eval_instr("+++,.")
+            .expect("Unreachable");
+
+        let run = |eof, byte_input| Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .input(Box::new(std::io::empty()))
+            .byte_input(byte_input)
+            .eof(eof)
+            .capture_output()
+            .build::<u8, u8>()
+            .run_consuming(&instr)
+            .map(|outcome| outcome.output);
+
+        for byte_input in [false, true] {
+            assert_eq!(run(EofMode::Zero, byte_input).ok(), Some(Some(b"0\n".to_vec())));
+            assert_eq!(run(EofMode::Max, byte_input).ok(), Some(Some(b"255\n".to_vec())));
+            assert_eq!(run(EofMode::Unchanged, byte_input).ok(), Some(Some(b"3\n".to_vec())));
+            assert!(matches!(run(EofMode::Error, byte_input), Err(RunError::IO(err)) if err.kind() == ErrorKind::UnexpectedEof));
+            }
+        }
+
+    #[test]
+    fn unbuffered_output() {
+        let instr = eval_instr("+.+.")
+            .expect("Unreachable");
+
+        let run = |unbuffered| {
+            let buffer = SharedBuffer::default();
+
+            /* Buffer gets flushed, once the Interpreter is dropped */
+            let mut interp = Interpreter::builder()
+                .display_mode(DisplayMode::Numeric)
+                .output(Box::new(buffer.clone()))
+                .unbuffered(unbuffered)
+                .max_steps(3)
+                .build::<u8, u8>();
+
+            assert!(matches!(interp.run(&instr), Err(RunError::StepLimit(3))));
+
+            buffer.take()
+            };
+
+        assert_eq!(run(true), b"1");
+        assert_eq!(run(false), b"");
+        }
+
+    #[test]
+    fn raw_output() {
+        /* Wide cells are written as their low byte, with no trailing line feed */
+        let instr = eval_instr("-.+.++.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        Interpreter::builder()
+            .display_mode(DisplayMode::Raw)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u16>()
+            .run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), [0xFF, 0x00, 0x02]);
+        }
+
+    #[test]
+    fn numeric_bases() {
+        let instr = eval_instr("-.")
+            .expect("Unreachable");
+
+        for (mode, expected) in [(DisplayMode::Hex, "FF\n"), (DisplayMode::Bin, "11111111\n"), (DisplayMode::Oct, "377\n")] {
+            let buffer = SharedBuffer::default();
+
+            Interpreter::builder()
+                .display_mode(mode)
+                .output(Box::new(buffer.clone()))
+                .build::<u8, u8>()
+                .run(&instr)
+                .expect("Unreachable");
+
+            assert_eq!(buffer.take(), expected.as_bytes());
+            }
+        }
+
+    #[test]
+    fn numeric_format() {
+        let instr = eval_instr("+.+.+.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+        let format = NumericFormat {
+            separator: b", ".to_vec(),
+            prefix: b"<".to_vec(),
+            suffix: b">".to_vec()
+            };
+
+        let mut interp = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .numeric_format(format)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u8>();
+
+        /* Separator is only written between the values, and starts over after a reset */
+        for _ in 0 .. 2 {
+            interp.run(&instr)
+                .expect("Unreachable");
+            assert_eq!(buffer.take(), b"<1>, <2>, <3>\n");
+            interp.reset();
+            }
+        }
+
+    #[test]
+    fn unicode_output() {
+        /* Printable scalar values are written as UTF-8, others with the hexadecimal fallback */
+        let instr = eval_instr("++++++++[>++++++++++++++++++++++++++<-]>+.[-]+++++.")
+            .expect("Unreachable");
+        let buffer = SharedBuffer::default();
+
+        Interpreter::builder()
+            .display_mode(DisplayMode::Unicode)
+            .output(Box::new(buffer.clone()))
+            .build::<u8, u16>()
+            .run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), "Ñ0x0005\n".as_bytes());
+        }
+
+    #[test]
+    fn arith_hook_events() {
+        let instr = eval_instr("-+<>")
+            .expect("Unreachable");
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let observed = events.clone();
+
+        Interpreter::builder()
+            .output(Box::new(SharedBuffer::default()))
+            .arith_hook(Box::new(move |event| {
+                observed.lock()
+                    .expect("Unreachable")
+                    .push(event);
+                ControlFlow::Continue(())
+                }))
+            .build::<u8, u8>()
+            .run(&instr)
+            .expect("Unreachable");
+
+        assert_eq!(*events.lock().expect("Unreachable"), vec![
+            ArithEvent::CellUnderflow,
+            ArithEvent::CellOverflow,
+            ArithEvent::PointerWrap,
+            ArithEvent::PointerWrap
+            ]);
+        }
+
+    #[test]
+    fn arith_hook_abort() {
+        let instr = eval_instr("-.+.")
+            .expect("Unreachable");
+        let mut warned = false;
+
+        let outcome = Interpreter::builder()
+            .display_mode(DisplayMode::Numeric)
+            .capture_output()
+            .arith_hook(Box::new(move |_| match warned {
+                true => ControlFlow::Break(()),
+                false => {
+                    warned = true;
+                    ControlFlow::Continue(())
+                    }
+                }))
+            .build::<u8, u8>()
+            .run(&instr);
+
+        assert!(matches!(outcome, Err(RunError::Aborted(ArithEvent::CellOverflow))));
+        }
     }
\ No newline at end of file
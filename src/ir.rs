@@ -0,0 +1,769 @@
+use {
+    std::collections::BTreeMap,
+    core::ops::{
+        Index,
+        Range
+        },
+    crate::{
+        codec::*,
+        eval::*,
+        optimizer::Optimizer,
+        rle::RLEInstructionSet,
+        utils::OptLevel
+        }
+    };
+
+
+/* Mid-level operations - runs of instructions are fused, and loops know the location of their other end */
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IrOp {
+    /* Net change of the current cell */
+    Add(i16),
+    /* Net movement of the pointer */
+    Move(isize),
+    /* Assignment of a value to the current cell */
+    Set(u64),
+    /* Addition of the current cell's value times a factor to the cell at an offset */
+    MulAdd(isize, i16),
+    /* Net change of the cell at an offset from the pointer */
+    AddAt(isize, i16),
+    /* Assignment of a value to the cell at an offset from the pointer */
+    SetAt(isize, u64),
+    Output,
+    Input,
+    Random,
+    /* Dump of the state, for debugging */
+    Dump,
+    /* Loop's start, with the location of its end */
+    LoopStart(usize),
+    /* Loop's end, with the location of its start */
+    LoopEnd(usize),
+    /* Procedure's definition, with the location of its end */
+    ProcStart(usize),
+    /* Procedure's end - returns from the call */
+    ProcEnd,
+    /* Call of the procedure defined for the current cell's value */
+    Call,
+    /* Breakpoint, and the prints of the state, for debugging */
+    Break,
+    ShowPointer,
+    ShowTape,
+    /* Switch to the tape at a distance from the current one */
+    Switch(isize)
+    }
+
+impl IrOp {
+    /* Get the operation for a run of the instruction - the loops' locations are linked later */
+    const fn run(inst: Instruction, count: usize) -> Self {
+        match inst {
+            Instruction::Increment => IrOp::Add(count as i16),
+            Instruction::Decrement => IrOp::Add(-(count as i16)),
+            Instruction::Right => IrOp::Move(count as isize),
+            Instruction::Left => IrOp::Move(-(count as isize)),
+            Instruction::Output => IrOp::Output,
+            Instruction::Input => IrOp::Input,
+            Instruction::Random => IrOp::Random,
+            Instruction::Dump => IrOp::Dump,
+            Instruction::LoopOpen => IrOp::LoopStart(0),
+            Instruction::LoopClose => IrOp::LoopEnd(0),
+            Instruction::ProcStart => IrOp::ProcStart(0),
+            Instruction::ProcEnd => IrOp::ProcEnd,
+            Instruction::ProcCall => IrOp::Call,
+            Instruction::Breakpoint => IrOp::Break,
+            Instruction::ShowPointer => IrOp::ShowPointer,
+            Instruction::ShowTape => IrOp::ShowTape,
+            Instruction::TapeUp => IrOp::Switch(-(count as isize)),
+            Instruction::TapeDown => IrOp::Switch(count as isize)
+            }
+        }
+    }
+
+/* Container for the operations, with the instructions they came from */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ir {
+    ops: Vec<IrOp>,
+    /* Location of every operation's first instruction, and the number of instructions at the end */
+    sources: Vec<usize>
+    }
+
+impl Index<usize> for Ir {
+    type Output = IrOp;
+
+    /* Index access operation */
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.ops[index]
+        }
+    }
+
+impl Ir {
+    /* Lower the instructions, fusing the runs of increments, decrements, and pointer moves, and optimising the loops with the default passes */
+    pub fn lower(instr: &InstructionSet) -> Self {
+        Optimizer::default()
+            .lower(instr)
+        }
+
+    /* Lower the instructions with the passes of the optimisation level - nothing gets fused at the lowest one */
+    pub fn lower_at(instr: &InstructionSet, level: OptLevel) -> Self {
+        match level {
+            OptLevel::O0 => Self::lower_unfused(instr),
+            _ => Optimizer::for_level(level)
+                .lower(instr)
+            }
+        }
+
+    /* Lower the instructions one by one, with the passes of the optimisation level, which keep the arbitrary-precision cells' stop at zero - `-+` isn't a no-op, and `[+]` never ends on them */
+    pub fn lower_unbounded_at(instr: &InstructionSet, level: OptLevel) -> Self {
+        let mut ir = Self::lower_unfused(instr);
+        Optimizer::for_unbounded(level)
+            .run(&mut ir);
+
+        ir
+        }
+
+    /* Lower the instructions, only fusing the runs - the passes are run on top of it */
+    pub fn lower_fused(instr: &InstructionSet) -> Self {
+        Self::lower_with(instr, true)
+        }
+
+    /* Lower the instructions one by one, so every step stays observable */
+    pub fn lower_unfused(instr: &InstructionSet) -> Self {
+        Self::lower_with(instr, false)
+        }
+
+    /* Lower the run-length encoded instructions, applying every run as a single operation - the runs are expanded, when they shouldn't be fused */
+    pub fn lower_runs(rle: &RLEInstructionSet, fuse: bool) -> Self {
+        let mut ops = Vec::with_capacity(rle.len());
+        let mut sources = Vec::with_capacity(rle.len() + 1);
+        let mut location = 0;
+
+        for (count, inst) in rle.runs() {
+            let mut remaining = usize::from(count);
+
+            /* Runs longer than a single change can hold get split */
+            while remaining > 0 {
+                let step = match (fuse, inst) {
+                    (true, Instruction::Increment | Instruction::Decrement) => remaining.min(i16::MAX as usize),
+                    (true, Instruction::Right | Instruction::Left) => remaining,
+                    _ => 1
+                    };
+
+                ops.push(IrOp::run(inst, step));
+                sources.push(location);
+                location += step;
+                remaining -= step;
+                }
+            }
+
+        sources.push(location);
+
+        let mut ir = Self { ops, sources };
+        ir.link_loops();
+
+        ir
+        }
+
+    fn lower_with(instr: &InstructionSet, fuse: bool) -> Self {
+        let mut ops = Vec::with_capacity(instr.len());
+        let mut sources = Vec::with_capacity(instr.len() + 1);
+
+        for i in 0 .. instr.len() {
+            let op = IrOp::run(instr[i], 1);
+
+            /* Merge with the previous operation of the same kind, as long as the change fits */
+            if fuse {
+                match (ops.last_mut(), op) {
+                    (Some(IrOp::Add(total)), IrOp::Add(step)) if total.checked_add(step).is_some() => {
+                        *total += step;
+                        continue;
+                        },
+                    (Some(IrOp::Move(total)), IrOp::Move(step)) => {
+                        *total += step;
+                        continue;
+                        },
+                    _ => ()
+                    }
+                }
+
+            ops.push(op);
+            sources.push(i);
+            }
+
+        sources.push(instr.len());
+
+        let mut ir = Self { ops, sources };
+        ir.link_loops();
+
+        ir
+        }
+
+    /* Point every loop's start, and end at each other, and the procedures' starts at their ends */
+    fn link_loops(&mut self) {
+        /* Locations of the open loops' starts, and of the open procedure - they can't be nested */
+        let mut loop_stack = Vec::new();
+        let mut procedure = 0;
+
+        for i in 0 .. self.ops.len() {
+            match self.ops[i] {
+                IrOp::ProcStart(_) =>
+                    procedure = i,
+                IrOp::ProcEnd =>
+                    self.ops[procedure] = IrOp::ProcStart(i),
+                IrOp::LoopStart(_) =>
+                    loop_stack.push(i),
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                IrOp::LoopEnd(_) => {
+                    let start = loop_stack.pop()
+                        .unwrap_or_default();
+                    self.ops[start] = IrOp::LoopStart(i);
+                    self.ops[i] = IrOp::LoopEnd(start);
+                    },
+                _ => ()
+                }
+            }
+        }
+
+    /* Drop the changes, and moves cancelling out, merging the runs they separated - the dropped instructions are counted by the next operation */
+    pub(crate) fn cancel_runs(&mut self) {
+        let mut ops: Vec<IrOp> = Vec::with_capacity(self.ops.len());
+        let mut sources = Vec::with_capacity(self.sources.len());
+
+        /* Location of the first dropped instructions, not counted by any operation yet */
+        let mut uncounted = None;
+
+        for i in 0 .. self.ops.len() {
+            let source = uncounted.take()
+                .unwrap_or(self.sources[i]);
+
+            match (ops.last_mut(), self.ops[i]) {
+                (_, IrOp::Add(0) | IrOp::Move(0)) => {
+                    uncounted = Some(source);
+                    continue;
+                    },
+                (Some(IrOp::Add(total)), IrOp::Add(step)) if total.checked_add(step).is_some() =>
+                    *total += step,
+                (Some(IrOp::Move(total)), IrOp::Move(step)) =>
+                    *total += step,
+                (_, op) => {
+                    ops.push(op);
+                    sources.push(source);
+                    continue;
+                    }
+                };
+
+            /* Runs merged into nothing get dropped as well */
+            if let Some(IrOp::Add(0) | IrOp::Move(0)) = ops.last() {
+                ops.pop();
+                uncounted = sources.pop();
+                }
+            }
+
+        sources.push(self.sources[self.ops.len()]);
+
+        self.ops = ops;
+        self.sources = sources;
+        self.link_loops();
+        }
+
+    /* Replace the loops, which only add to their cell, with setting it to zero - odd steps reach zero for every cell's width, as it's a power of two */
+    pub(crate) fn clear_loops(&mut self) {
+        let mut ops = Vec::with_capacity(self.ops.len());
+        let mut sources = Vec::with_capacity(self.sources.len());
+
+        let mut i = 0;
+        while i < self.ops.len() {
+            let (op, len) = match self.ops[i ..] {
+                [IrOp::LoopStart(_), IrOp::Add(value), IrOp::LoopEnd(_), ..] if value % 2 != 0 => (IrOp::Set(0), 3),
+                _ => (self.ops[i], 1)
+                };
+
+            ops.push(op);
+            sources.push(self.sources[i]);
+            i += len;
+            }
+
+        sources.push(self.sources[i]);
+
+        self.ops = ops;
+        self.sources = sources;
+        self.link_loops();
+        }
+
+    /* Replace the balanced loops, which decrement their cell once, and add to other cells, with multiplications - like `[->+>+++<<]` */
+    pub(crate) fn multiply_loops(&mut self) {
+        let mut ops = Vec::with_capacity(self.ops.len());
+        let mut sources = Vec::with_capacity(self.sources.len());
+
+        let mut i = 0;
+        while i < self.ops.len() {
+            let multiply = match self.ops[i] {
+                IrOp::LoopStart(end) => self.multiply_targets(i + 1 .. end)
+                    .map(|targets| (targets, end)),
+                _ => None
+                };
+
+            match multiply {
+                /* Every replacement starts at the loop, so the whole loop is counted by the last one */
+                Some((targets, end)) => {
+                    for (offset, factor) in targets {
+                        ops.push(IrOp::MulAdd(offset, factor));
+                        sources.push(self.sources[i]);
+                        }
+                    ops.push(IrOp::Set(0));
+                    sources.push(self.sources[i]);
+                    i = end + 1;
+                    },
+                None => {
+                    ops.push(self.ops[i]);
+                    sources.push(self.sources[i]);
+                    i += 1;
+                    }
+                }
+            }
+
+        sources.push(self.sources[i]);
+
+        self.ops = ops;
+        self.sources = sources;
+        self.link_loops();
+        }
+
+    /* Defer the pointer's moves in the straight-line code, addressing the cells by their offsets instead - the pointer is only moved before loops, input, output, and multiplications */
+    pub(crate) fn defer_moves(&mut self) {
+        let mut ops = Vec::with_capacity(self.ops.len());
+        let mut sources = Vec::with_capacity(self.sources.len());
+
+        /* Offset of the deferred location, location of the first instructions not counted by any operation yet, and whether anything was added since the last move */
+        let mut offset = 0;
+        let mut uncounted = None;
+        let mut addressed = false;
+
+        for i in 0 ..= self.ops.len() {
+            let op = match self.ops.get(i) {
+                Some(&IrOp::Move(value)) => {
+                    offset += value;
+                    uncounted.get_or_insert(self.sources[i]);
+                    continue;
+                    },
+                Some(&IrOp::Add(value)) if offset != 0 => Some(IrOp::AddAt(offset, value)),
+                Some(&IrOp::Set(value)) if offset != 0 => Some(IrOp::SetAt(offset, value)),
+                Some(&op @ (IrOp::Add(_) | IrOp::Set(_))) => Some(op),
+                /* Commit the move - the ones cancelling out are counted by the last addressing, and only kept when there's none */
+                op => {
+                    if offset != 0 || (uncounted.is_some() && ! addressed) {
+                        let source = uncounted.take()
+                            .or(sources.last().copied())
+                            .unwrap_or_default();
+                        ops.push(IrOp::Move(offset));
+                        sources.push(source);
+                        }
+                    offset = 0;
+                    uncounted = None;
+                    addressed = false;
+                    op.copied()
+                    }
+                };
+
+            let Some(op) = op else {
+                break;
+                };
+
+            if matches!(op, IrOp::Add(_) | IrOp::Set(_) | IrOp::AddAt(..) | IrOp::SetAt(..)) {
+                addressed = true;
+                }
+            ops.push(op);
+            sources.push(uncounted.take().unwrap_or(self.sources[i]));
+            }
+
+        sources.push(self.sources[self.ops.len()]);
+
+        self.ops = ops;
+        self.sources = sources;
+        self.link_loops();
+        }
+
+    /* Get the factors of a multiplying loop's body, by their offsets - if it only adds, and moves, ends where it started, and decrements its cell once */
+    fn multiply_targets(&self, body: Range<usize>) -> Option<Vec<(isize, i16)>> {
+        let mut changes = BTreeMap::new();
+        let mut offset = 0_isize;
+
+        for &op in &self.ops[body] {
+            match op {
+                IrOp::Add(value) => *changes.entry(offset).or_insert(0_i64) += i64::from(value),
+                IrOp::Move(value) => offset += value,
+                _ => return None
+                }
+            }
+
+        if offset != 0 || changes.remove(&0) != Some(-1) {
+            return None;
+            }
+
+        changes.into_iter()
+            .map(|(offset, factor)| Some((offset, i16::try_from(factor).ok()?)))
+            .collect()
+        }
+
+    /* Get number of operations */
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.ops.len()
+        }
+    /* Get whether is empty */
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+        }
+
+    /* Get all operations */
+    pub fn ops(&self) -> &[IrOp] {
+        &self.ops
+        }
+
+    /* Get the location of the operation's first instruction - the end of the operations maps to the number of instructions */
+    pub fn source(&self, index: usize) -> usize {
+        self.sources[index]
+        }
+
+    /* Get the number of instructions the operation came from */
+    pub fn span(&self, index: usize) -> usize {
+        self.sources[index + 1] - self.sources[index]
+        }
+
+    /* Check whether every operation came from a single instruction - nothing was fused, cancelled, nor rewritten then */
+    pub fn is_unfused(&self) -> bool {
+        (0 .. self.ops.len())
+            .all(|index| self.span(index) == 1)
+        }
+
+    /* Serialise the operations, with the instructions they came from - the loops get linked again, when deserialised */
+    pub(crate) fn encode(&self, output: &mut Vec<u8>) {
+        output.extend_from_slice(&(self.ops.len() as u64).to_le_bytes());
+
+        for (&op, &source) in self.ops.iter().zip(&self.sources) {
+            let tag = match op {
+                IrOp::Add(_) => 0,
+                IrOp::Move(_) => 1,
+                IrOp::Set(_) => 2,
+                IrOp::MulAdd(..) => 3,
+                IrOp::AddAt(..) => 4,
+                IrOp::SetAt(..) => 5,
+                IrOp::Output => 6,
+                IrOp::Input => 7,
+                IrOp::Random => 8,
+                IrOp::LoopStart(_) => 9,
+                IrOp::LoopEnd(_) => 10,
+                IrOp::Dump => 11,
+                IrOp::ProcStart(_) => 12,
+                IrOp::ProcEnd => 13,
+                IrOp::Call => 14,
+                IrOp::Break => 15,
+                IrOp::ShowPointer => 16,
+                IrOp::ShowTape => 17,
+                IrOp::Switch(_) => 18
+                };
+
+            output.push(tag);
+            output.extend_from_slice(&(source as u64).to_le_bytes());
+
+            match op {
+                IrOp::Add(value) =>
+                    output.extend_from_slice(&value.to_le_bytes()),
+                IrOp::Move(offset) | IrOp::Switch(offset) =>
+                    output.extend_from_slice(&(offset as i64).to_le_bytes()),
+                IrOp::Set(value) =>
+                    output.extend_from_slice(&value.to_le_bytes()),
+                IrOp::MulAdd(offset, value) | IrOp::AddAt(offset, value) => {
+                    output.extend_from_slice(&(offset as i64).to_le_bytes());
+                    output.extend_from_slice(&value.to_le_bytes());
+                    },
+                IrOp::SetAt(offset, value) => {
+                    output.extend_from_slice(&(offset as i64).to_le_bytes());
+                    output.extend_from_slice(&value.to_le_bytes());
+                    },
+                _ => ()
+                }
+            }
+
+        output.extend_from_slice(&(self.sources[self.ops.len()] as u64).to_le_bytes());
+        }
+
+    /* Deserialise the operations - the loops have to be balanced, and the sources can't go back */
+    pub(crate) fn decode(reader: &mut Reader) -> Result<Self, Malformed> {
+        let len = reader.size()?;
+        let mut ops = Vec::new();
+        let mut sources = Vec::new();
+
+        for _ in 0 .. len {
+            let tag = reader.take(1)?[0];
+            sources.push(reader.size()?);
+
+            let op = match tag {
+                0 => IrOp::Add(reader.i16()?),
+                1 => IrOp::Move(reader.i64()? as isize),
+                2 => IrOp::Set(reader.u64()?),
+                3 => IrOp::MulAdd(reader.i64()? as isize, reader.i16()?),
+                4 => IrOp::AddAt(reader.i64()? as isize, reader.i16()?),
+                5 => IrOp::SetAt(reader.i64()? as isize, reader.u64()?),
+                6 => IrOp::Output,
+                7 => IrOp::Input,
+                8 => IrOp::Random,
+                9 => IrOp::LoopStart(0),
+                10 => IrOp::LoopEnd(0),
+                11 => IrOp::Dump,
+                12 => IrOp::ProcStart(0),
+                13 => IrOp::ProcEnd,
+                14 => IrOp::Call,
+                15 => IrOp::Break,
+                16 => IrOp::ShowPointer,
+                17 => IrOp::ShowTape,
+                18 => IrOp::Switch(reader.i64()? as isize),
+                _ => return Err(Malformed)
+                };
+
+            ops.push(op);
+            }
+
+        sources.push(reader.size()?);
+
+        Self::from_parts(ops, sources)
+            .ok_or(Malformed)
+        }
+
+    /* Construct from the operations, and the location of every one's first instruction, followed by the number of instructions - none, if the loops aren't balanced, or the locations go back */
+    pub fn from_parts(ops: Vec<IrOp>, sources: Vec<usize>) -> Option<Self> {
+        let mut depth = 0_usize;
+        /* Loop depth at the open procedure's start */
+        let mut procedure = None;
+
+        for op in &ops {
+            match (op, procedure) {
+                (IrOp::LoopStart(_), _) => depth += 1,
+                (IrOp::LoopEnd(_), Some(outer)) if depth == outer => return None,
+                (IrOp::LoopEnd(_), _) => depth = depth.checked_sub(1)?,
+                (IrOp::ProcStart(_), None) => procedure = Some(depth),
+                (IrOp::ProcEnd, Some(outer)) if depth == outer => procedure = None,
+                (IrOp::ProcStart(_) | IrOp::ProcEnd, _) => return None,
+                _ => ()
+                }
+            }
+
+        if depth != 0 || procedure.is_some() || sources.len() != ops.len() + 1 || ! sources.is_sorted() {
+            return None;
+            }
+
+        let mut ir = Self { ops, sources };
+        ir.link_loops();
+
+        Some(ir)
+        }
+
+    /* Take apart into the operations, and their locations - the inverse of the construction from parts */
+    pub fn into_parts(self) -> (Vec<IrOp>, Vec<usize>) {
+        (self.ops, self.sources)
+        }
+
+    /* Get a stable hash of the operations - FNV-1a over their serialised form */
+    pub fn fingerprint(&self) -> u64 {
+        let mut bytes = Vec::new();
+        self.encode(&mut bytes);
+
+        bytes.iter()
+            .fold(0xCBF2_9CE4_8422_2325, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3))
+        }
+
+    /* Find the first operation, which starts at the given instruction - operations replacing a loop share its location */
+    pub fn find_source(&self, instr_ptr: usize) -> Option<usize> {
+        let index = self.sources.partition_point(|&source| source < instr_ptr);
+
+        (self.sources.get(index) == Some(&instr_ptr))
+            .then_some(index)
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::ir::*;
+
+    #[test]
+    fn lower_basic() {
+        let instr = eval_instr("+++-->><[-<].,")
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::Move(1),
+            IrOp::LoopStart(5),
+            IrOp::Add(-1),
+            IrOp::Move(-1),
+            IrOp::LoopEnd(2),
+            IrOp::Output,
+            IrOp::Input
+            ]);
+        assert_eq!(ir.span(0), 5);
+        assert_eq!(ir.source(2), 8);
+        assert_eq!(ir.find_source(12), Some(6));
+        assert_eq!(ir.find_source(1), None);
+        }
+
+    #[test]
+    fn lower_clear_loops() {
+        let instr = eval_instr("+[-]>[+++]<[--][[-]]")
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::Set(0),
+            IrOp::SetAt(1, 0),
+            IrOp::LoopStart(5),
+            IrOp::Add(-2),
+            IrOp::LoopEnd(3),
+            IrOp::LoopStart(8),
+            IrOp::Set(0),
+            IrOp::LoopEnd(6)
+            ]);
+        assert_eq!(ir.span(1), 3);
+        assert_eq!(ir.span(2), 7);
+        assert_eq!(ir.source(7), 16);
+        }
+
+    #[test]
+    fn lower_multiply_loops() {
+        let instr = eval_instr("+++[->++>>---<<<]>[-<+>>+<<]<[-->+<]")
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        assert_eq!(&ir.ops()[.. 6], &[
+            IrOp::Add(3),
+            IrOp::MulAdd(1, 2),
+            IrOp::MulAdd(3, -3),
+            IrOp::Set(0),
+            IrOp::Move(1),
+            IrOp::LoopStart(10)
+            ]);
+        assert_eq!(&ir.ops()[6 .. 10], &[
+            IrOp::Add(-1),
+            IrOp::AddAt(-1, 1),
+            IrOp::AddAt(1, 1),
+            IrOp::Move(-1)
+            ]);
+        assert_eq!(ir.span(1), 0);
+        assert_eq!(ir.span(3), 14);
+        assert_eq!(ir.find_source(3), Some(1));
+        assert!(matches!(ir[12], IrOp::LoopStart(_)));
+        }
+
+    #[test]
+    fn lower_deferred_moves() {
+        let instr = eval_instr("+>++>+++<<,>>[-]<")
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::AddAt(1, 2),
+            IrOp::AddAt(2, 3),
+            IrOp::Input,
+            IrOp::SetAt(2, 0),
+            IrOp::Move(1)
+            ]);
+        assert_eq!(ir.span(1), 3);
+        assert_eq!(ir.span(2), 6);
+        assert_eq!(ir.source(4), 11);
+        assert_eq!(ir.span(5), 1);
+        }
+
+    #[test]
+    fn lower_runs() {
+        let source = format!("{}>>[-]", "+".repeat(i16::MAX as usize + 2));
+        let rle = eval_instr(&source)
+            .expect("Unreachable")
+            .encode_run_length();
+        let ir = Ir::lower_runs(&rle, true);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(i16::MAX),
+            IrOp::Add(2),
+            IrOp::Move(2),
+            IrOp::LoopStart(5),
+            IrOp::Add(-1),
+            IrOp::LoopEnd(3)
+            ]);
+        assert_eq!(ir.source(3), i16::MAX as usize + 4);
+        assert_eq!(Ir::lower_runs(&rle, false).len(), i16::MAX as usize + 7);
+        }
+
+    #[test]
+    fn ir_roundtrip() {
+        let instr = eval_instr("+++[->++>>---<<<]>[-<+>>+<<]<[-]?,.")
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        let mut bytes = Vec::new();
+        ir.encode(&mut bytes);
+
+        assert_eq!(Ir::decode(&mut Reader(&bytes)), Ok(ir));
+        }
+
+    #[test]
+    fn ir_decode_unbalanced() {
+        let instr = eval_instr("+[-]")
+            .expect("Unreachable");
+        let ir = Ir::lower_unfused(&instr);
+
+        let mut bytes = Vec::new();
+        ir.encode(&mut bytes);
+        /* Turn the loop's end into an output */
+        let end = bytes.len() - 8 - 9;
+        bytes[end] = 6;
+
+        assert_eq!(Ir::decode(&mut Reader(&bytes)), Err(Malformed));
+        }
+
+    #[test]
+    fn lower_cancel_runs() {
+        let instr = eval_instr("+>+-<+[+-]")
+            .expect("Unreachable");
+        let mut ir = Ir::lower_fused(&instr);
+        ir.cancel_runs();
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(2),
+            IrOp::LoopStart(2),
+            IrOp::LoopEnd(1)
+            ]);
+        assert_eq!(ir.span(0), 6);
+        assert_eq!(ir.source(2), 7);
+        assert_eq!(ir.span(2), 3);
+        }
+
+    #[test]
+    fn lower_unfused() {
+        let instr = eval_instr("++[>]")
+            .expect("Unreachable");
+        let ir = Ir::lower_unfused(&instr);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::Add(1),
+            IrOp::LoopStart(4),
+            IrOp::Move(1),
+            IrOp::LoopEnd(2)
+            ]);
+        assert!(ir.is_unfused());
+        assert!(! Ir::lower(&instr).is_unfused());
+        }
+
+    #[test]
+    fn lower_split_add() {
+        let source = "+".repeat(i16::MAX as usize + 1);
+        let instr = eval_instr(&source)
+            .expect("Unreachable");
+        let ir = Ir::lower(&instr);
+
+        assert_eq!(ir.ops(), &[IrOp::Add(i16::MAX), IrOp::Add(1)]);
+        assert_eq!(ir.span(0), i16::MAX as usize);
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,820 @@
+use {
+    cranelift_codegen::{
+        ir::{
+            condcodes::IntCC,
+            types,
+            AbiParam,
+            Block,
+            FuncRef,
+            InstBuilder,
+            MemFlags,
+            Value
+            },
+        settings::{
+            self,
+            Configurable
+            }
+        },
+    cranelift_frontend::{
+        FunctionBuilder,
+        FunctionBuilderContext,
+        Variable
+        },
+    cranelift_jit::{
+        JITBuilder,
+        JITModule
+        },
+    cranelift_module::{
+        default_libcall_names,
+        Linkage,
+        Module
+        },
+    thiserror::Error,
+    log::info,
+    std::{
+        collections::HashMap,
+        fmt::Display,
+        io::{
+            BufWriter,
+            Error as IoError,
+            ErrorKind,
+            Read,
+            Write
+            },
+        mem::transmute
+        },
+    crate::{
+        codegen::*,
+        eval::*,
+        ir::*,
+        tape::*,
+        utils::*
+        }
+    };
+
+
+/* Statuses returned by the compiled code, and the callbacks - input callback returns them negated */
+const IO_FAILURE: i32 = 1;
+const END_OF_INPUT: i32 = 2;
+
+
+/* JIT's result output type */
+#[derive(Debug, Error)]
+pub enum JitError {
+    #[error(transparent)]
+    Compile(#[from] CompileError),
+    #[error("Failed to generate the code: {0}")]
+    Codegen(String),
+    #[error("Input, or output failed: {0}")]
+    Io(#[from] IoError),
+    #[error("Unexpected end of input")]
+    EndOfInput
+    }
+
+/* Helper function, for turning Cranelift's errors into the JIT's ones */
+fn codegen_error<E>(error: E) -> JitError
+where E: Display {
+    JitError::Codegen(error.to_string())
+    }
+
+
+/* Input, and output of the compiled code, handed to the callbacks */
+struct Context {
+    input: Box<dyn Read>,
+    output: BufWriter<Box<dyn Write>>,
+    display_mode: DisplayMode,
+    cell_bytes: usize,
+    error: Option<IoError>
+    }
+
+/* Callback writing a cell's value, according to the display mode */
+extern "C" fn put_value(context: *mut Context, value: u32) -> i32 {
+    /* Unsafe note - it is safe, because the pointer comes from the run, which outlives the compiled code */
+    let context = unsafe {
+        &mut *context
+        };
+
+    let result = match context.display_mode {
+        /* Print as ASCII if value is graphic */
+        DisplayMode::ASCII if matches!(value, 0x09 ..= 0x0A | 0x20 ..= 0x7E) =>
+            context.output.write_all(&[value as u8]),
+        /* Print fallback for ASCII */
+        DisplayMode::ASCII =>
+            write!(context.output, "{value:#0size$X}", size = 2 + 2 * context.cell_bytes),
+        /* Print raw numeric value */
+        DisplayMode::Numeric =>
+            write!(context.output, "{value}"),
+        /* Print numeric value in other bases */
+        DisplayMode::Hex =>
+            write!(context.output, "{value:X}"),
+        DisplayMode::Bin =>
+            write!(context.output, "{value:b}"),
+        DisplayMode::Oct =>
+            write!(context.output, "{value:o}"),
+        /* Write the cell's low byte as it is */
+        DisplayMode::Raw =>
+            context.output.write_all(&[value as u8]),
+        /* Print as UTF-8 if value is a printable scalar value, with the same fallback as ASCII */
+        DisplayMode::Unicode => match unicode_printable(value) {
+            Some(character) =>
+                write!(context.output, "{character}"),
+            None =>
+                write!(context.output, "{value:#0size$X}", size = 2 + 2 * context.cell_bytes)
+            }
+        };
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            context.error = Some(error);
+            IO_FAILURE
+            }
+        }
+    }
+
+/* Callback reading a single byte, or returning a negated status */
+extern "C" fn get_byte(context: *mut Context) -> i32 {
+    /* Unsafe note - it is safe, because the pointer comes from the run, which outlives the compiled code */
+    let context = unsafe {
+        &mut *context
+        };
+
+    /* Output is flushed first, so prompts show up before waiting for the input */
+    let mut byte = [0];
+    let result = context.output.flush()
+        .and_then(|_| context.input.read_exact(&mut byte));
+
+    match result {
+        Ok(()) => byte[0].into(),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => -END_OF_INPUT,
+        Err(error) => {
+            context.error = Some(error);
+            -IO_FAILURE
+            }
+        }
+    }
+
+
+/* Helper container for translating the operations into Cranelift's IR */
+struct Translator<'a> {
+    builder: FunctionBuilder<'a>,
+    options: CodegenOptions,
+    tape: Value,
+    index: Variable,
+    /* Offset of the addressed cell from the pointer */
+    offset: i64
+    }
+
+/* Values needed for the input, and output - only present in whole programs */
+struct Callbacks {
+    context: Value,
+    /* Block returning the status, passed as its parameter */
+    failure: Block,
+    put_value: FuncRef,
+    get_byte: FuncRef
+    }
+
+impl Translator<'_> {
+    /* Emit the operation on the cell at an offset from the pointer */
+    fn at(&mut self, offset: i64, operation: impl FnOnce(&mut Self)) {
+        self.offset = offset;
+        operation(self);
+        self.offset = 0;
+        }
+
+    /* Get the addressed cell's address - `tape + index * size`, with the index wrapping in the pointer's width */
+    fn address(&mut self) -> Value {
+        let index = self.builder.use_var(self.index);
+        let index = match self.offset {
+            0 => index,
+            offset => {
+                let mask = (1_i64 << self.options.pointer_bits()) - 1;
+                let index = self.builder.ins().iadd_imm(index, offset);
+                self.builder.ins().band_imm(index, mask)
+                }
+            };
+        let offset = match self.options.cell_bytes().trailing_zeros() {
+            0 => index,
+            shift => self.builder.ins().ishl_imm(index, i64::from(shift))
+            };
+
+        self.builder.ins().iadd(self.tape, offset)
+        }
+
+    /* Load the current cell, zero-extended */
+    fn load_cell(&mut self, address: Value) -> Value {
+        let flags = MemFlags::trusted();
+
+        match self.options.cell_bytes() {
+            1 => self.builder.ins().uload8(types::I32, flags, address, 0),
+            2 => self.builder.ins().uload16(types::I32, flags, address, 0),
+            _ => self.builder.ins().load(types::I32, flags, address, 0)
+            }
+        }
+
+    /* Store a value into the current cell - truncated to the cell's width */
+    fn store_cell(&mut self, address: Value, value: Value) {
+        let flags = MemFlags::trusted();
+
+        match self.options.cell_bytes() {
+            1 => self.builder.ins().istore8(flags, value, address, 0),
+            2 => self.builder.ins().istore16(flags, value, address, 0),
+            _ => self.builder.ins().store(flags, value, address, 0)
+            };
+        }
+
+    /* Continue in a fresh block, when the status is zero - otherwise return it */
+    fn check_status(&mut self, callbacks: &Callbacks, status: Value) {
+        let next = self.builder.create_block();
+
+        self.builder.ins().brif(status, callbacks.failure, &[status], next, &[]);
+        self.builder.switch_to_block(next);
+        }
+
+    fn add_cell(&mut self, value: i64) {
+        let value = match self.options.cell_size {
+            CellSize::U1 => value % 2,
+            CellSize::U8 => value as u8 as i64,
+            CellSize::U16 => value as u16 as i64,
+            CellSize::U32 => value as u32 as i64,
+            /* 64-bit, arbitrary-precision, and signed cells were rejected while validating */
+            CellSize::U64 | CellSize::Big | CellSize::I8 | CellSize::I16 | CellSize::I32 => return
+            };
+
+        if value != 0 {
+            let address = self.address();
+            let cell = self.load_cell(address);
+            let cell = match self.options.cell_size {
+                /* Adding an odd value to a single bit flips it */
+                CellSize::U1 => self.builder.ins().bxor_imm(cell, 1),
+                _ => self.builder.ins().iadd_imm(cell, value)
+                };
+            self.store_cell(address, cell);
+            }
+        }
+
+    /* Set the current cell to a value, truncated to the cell's width */
+    fn set_cell(&mut self, value: u64) {
+        let value = self.builder.ins().iconst(types::I32, (value & self.options.cell_mask()) as u32 as i64);
+        let address = self.address();
+        self.store_cell(address, value);
+        }
+
+    /* Add the current cell times a factor to the cell at an offset - the pointer is moved there, and back */
+    fn mul_add(&mut self, offset: i64, factor: i16) {
+        /* Bits are flipped by odd factors only, and the cell is the product itself */
+        let skip = match self.options.cell_size {
+            CellSize::U1 => factor % 2 == 0,
+            _ => factor == 0
+            };
+        if skip {
+            return;
+            }
+
+        let address = self.address();
+        let cell = self.load_cell(address);
+
+        self.move_pointer(offset);
+        let address = self.address();
+        let target = self.load_cell(address);
+        let target = match self.options.cell_size {
+            CellSize::U1 => self.builder.ins().bxor(target, cell),
+            _ => {
+                let product = self.builder.ins().imul_imm(cell, i64::from(factor));
+                self.builder.ins().iadd(target, product)
+                }
+            };
+        self.store_cell(address, target);
+        self.move_pointer(-offset);
+        }
+
+    /* Move the pointer - wrapping, by masking it to the pointer's width */
+    fn move_pointer(&mut self, value: i64) {
+        let mask = (1_i64 << self.options.pointer_bits()) - 1;
+
+        if value & mask != 0 {
+            let index = self.builder.use_var(self.index);
+            let index = self.builder.ins().iadd_imm(index, value);
+            let index = self.builder.ins().band_imm(index, mask);
+            self.builder.def_var(self.index, index);
+            }
+        }
+
+    fn output(&mut self, callbacks: &Callbacks) {
+        let address = self.address();
+        let cell = self.load_cell(address);
+
+        let call = self.builder.ins().call(callbacks.put_value, &[callbacks.context, cell]);
+        let status = self.builder.inst_results(call)[0];
+        self.check_status(callbacks, status);
+        }
+
+    fn input(&mut self, callbacks: &Callbacks) {
+        let call = self.builder.ins().call(callbacks.get_byte, &[callbacks.context]);
+        let byte = self.builder.inst_results(call)[0];
+
+        /* Negative results are negated statuses */
+        let failed = self.builder.ins().icmp_imm(IntCC::SignedLessThan, byte, 0);
+        let status = self.builder.ins().ineg(byte);
+        let next = self.builder.create_block();
+        self.builder.ins().brif(failed, callbacks.failure, &[status], next, &[]);
+        self.builder.switch_to_block(next);
+
+        /* Only the lowest bit is kept for 1-bit cells */
+        let byte = match self.options.cell_size {
+            CellSize::U1 => self.builder.ins().band_imm(byte, 1),
+            _ => byte
+            };
+        let address = self.address();
+        self.store_cell(address, byte);
+        }
+    }
+
+
+/* Helper function, for getting the JIT's builder targeting the host, and optimising for speed */
+fn host_builder() -> Result<JITBuilder, JitError> {
+    let mut flags = settings::builder();
+    flags.set("opt_level", "speed")
+        .map_err(codegen_error)?;
+    let isa = cranelift_native::builder()
+        .map_err(codegen_error)?
+        .finish(settings::Flags::new(flags))
+        .map_err(codegen_error)?;
+
+    Ok(JITBuilder::with_isa(isa, default_libcall_names()))
+    }
+
+/* Function for compiling the instructions into native code in memory, and running it right away - the output ends with a line feed, like after the Interpreter's run */
+pub fn run_jit(instr: &InstructionSet, options: CodegenOptions, input: Box<dyn Read>, output: Box<dyn Write>) -> Result<(), JitError> {
+    options.validate()?;
+
+    /* Cells are handled as 32-bit values in the compiled code */
+    if options.cell_size == CellSize::U64 {
+        return Err(CompileError::WideCells.into());
+        }
+
+    let ir = lower_checked(instr, options.opt_level)?;
+
+    let mut jit_builder = host_builder()?;
+    jit_builder.symbol("braincooker_put_value", put_value as *const u8);
+    jit_builder.symbol("braincooker_get_byte", get_byte as *const u8);
+    let mut module = JITModule::new(jit_builder);
+
+    /* Signatures - `fn(tape, context) -> status`, and the callbacks' ones */
+    let pointer = module.target_config()
+        .pointer_type();
+
+    let mut put_value_signature = module.make_signature();
+    put_value_signature.params.extend([AbiParam::new(pointer), AbiParam::new(types::I32)]);
+    put_value_signature.returns.push(AbiParam::new(types::I32));
+    let put_value_id = module.declare_function("braincooker_put_value", Linkage::Import, &put_value_signature)
+        .map_err(codegen_error)?;
+
+    let mut get_byte_signature = module.make_signature();
+    get_byte_signature.params.push(AbiParam::new(pointer));
+    get_byte_signature.returns.push(AbiParam::new(types::I32));
+    let get_byte_id = module.declare_function("braincooker_get_byte", Linkage::Import, &get_byte_signature)
+        .map_err(codegen_error)?;
+
+    let mut context = module.make_context();
+    context.func.signature.params.extend([AbiParam::new(pointer), AbiParam::new(pointer)]);
+    context.func.signature.returns.push(AbiParam::new(types::I32));
+    let program_id = module.declare_function("braincooker_program", Linkage::Export, &context.func.signature)
+        .map_err(codegen_error)?;
+
+    let mut builder_context = FunctionBuilderContext::new();
+    let mut builder = FunctionBuilder::new(&mut context.func, &mut builder_context);
+
+    let put_value_ref = module.declare_func_in_func(put_value_id, builder.func);
+    let get_byte_ref = module.declare_func_in_func(get_byte_id, builder.func);
+
+    let entry = builder.create_block();
+    builder.append_block_params_for_function_params(entry);
+    builder.switch_to_block(entry);
+
+    let failure = builder.create_block();
+    builder.append_block_param(failure, types::I32);
+
+    let index = Variable::new(0);
+    builder.declare_var(index, pointer);
+    let zero = builder.ins().iconst(pointer, 0);
+    builder.def_var(index, zero);
+
+    let callbacks = Callbacks {
+        context: builder.block_params(entry)[1],
+        failure,
+        put_value: put_value_ref,
+        get_byte: get_byte_ref
+        };
+    let mut translator = Translator {
+        tape: builder.block_params(entry)[0],
+        builder,
+        options,
+        index,
+        offset: 0
+        };
+
+    /* Headers, and exits of the open loops */
+    let mut loop_stack = Vec::new();
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) =>
+                translator.add_cell(value.into()),
+            IrOp::Move(value) =>
+                translator.move_pointer(value as i64),
+            IrOp::Set(value) =>
+                translator.set_cell(value),
+            IrOp::MulAdd(offset, factor) =>
+                translator.mul_add(offset as i64, factor),
+            IrOp::AddAt(offset, value) =>
+                translator.at(offset as i64, |translator| translator.add_cell(value.into())),
+            IrOp::SetAt(offset, value) =>
+                translator.at(offset as i64, |translator| translator.set_cell(value)),
+            IrOp::Output =>
+                translator.output(&callbacks),
+            IrOp::Input =>
+                translator.input(&callbacks),
+            /* Check the cell in the header, entering the body, or leaving the loop */
+            IrOp::LoopStart(_) => {
+                let header = translator.builder.create_block();
+                let body = translator.builder.create_block();
+                let exit = translator.builder.create_block();
+
+                translator.builder.ins().jump(header, &[]);
+                translator.builder.switch_to_block(header);
+
+                let address = translator.address();
+                let cell = translator.load_cell(address);
+                translator.builder.ins().brif(cell, body, &[], exit, &[]);
+                translator.builder.switch_to_block(body);
+
+                loop_stack.push((header, exit));
+                },
+            /* Go back to the header */
+            IrOp::LoopEnd(_) => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some((header, exit)) = loop_stack.pop() else {
+                    continue;
+                    };
+
+                translator.builder.ins().jump(header, &[]);
+                translator.builder.switch_to_block(exit);
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    let Translator { mut builder, .. } = translator;
+
+    let success = builder.ins().iconst(types::I32, 0);
+    builder.ins().return_(&[success]);
+
+    builder.switch_to_block(failure);
+    let status = builder.block_params(failure)[0];
+    builder.ins().return_(&[status]);
+
+    builder.seal_all_blocks();
+    builder.finalize();
+
+    module.define_function(program_id, &mut context)
+        .map_err(codegen_error)?;
+    module.clear_context(&mut context);
+    module.finalize_definitions()
+        .map_err(codegen_error)?;
+
+    /* Unsafe note - it is safe, because the function was defined with this exact signature, in the platform's calling convention */
+    let program: extern "C" fn(*mut u8, *mut Context) -> i32 = unsafe {
+        transmute(module.get_finalized_function(program_id))
+        };
+
+    /* The tape spans the whole range of the pointer, so the masked index never goes out of bounds */
+    let mut tape = vec![0_u8; (options.tape_len() * options.cell_bytes()) as usize];
+    let mut context = Context {
+        input,
+        output: BufWriter::new(output),
+        display_mode: options.display_mode,
+        cell_bytes: options.cell_bytes() as usize,
+        error: None
+        };
+
+    let status = program(tape.as_mut_ptr(), &mut context);
+
+    /* Unsafe note - it is safe, because the compiled code isn't used anymore */
+    unsafe {
+        module.free_memory();
+        }
+
+    match (status, context.error) {
+        (_, Some(error)) => Err(JitError::Io(error)),
+        (END_OF_INPUT, None) => {
+            context.output.flush()?;
+            Err(JitError::EndOfInput)
+            },
+        _ => {
+            if context.display_mode != DisplayMode::Raw {
+                context.output.write_all(b"\n")?;
+                }
+            context.output.flush()?;
+            Ok(())
+            }
+        }
+    }
+
+/* Default number of iterations, after which the Interpreter compiles a loop */
+pub const JIT_THRESHOLD: u32 = 1000;
+
+/* Loop compiled to native code - `fn(tape, index, executed) -> index`, running until the loop ends */
+pub(crate) struct HotLoop {
+    module: Option<JITModule>,
+    options: CodegenOptions,
+    function: extern "C" fn(*mut u8, usize, *mut u64) -> usize
+    }
+
+/* Unsafe note - it is safe, because the compiled code, and its memory are owned only by this container */
+unsafe impl Send for HotLoop {}
+
+impl Drop for HotLoop {
+    fn drop(&mut self) {
+        if let Some(module) = self.module.take() {
+            /* Unsafe note - it is safe, because the function can't be called after the drop */
+            unsafe {
+                module.free_memory();
+                }
+            }
+        }
+    }
+
+impl HotLoop {
+    /* Run the loop from its body, on a tape spanning the whole range of the pointer - returns the pointer's new location */
+    pub(crate) fn run<U>(&self, tape: &mut [U], index: usize, executed: &mut u64) -> usize {
+        assert!(
+            tape.len() as u64 == self.options.tape_len() && size_of::<U>() as u64 == self.options.cell_bytes(),
+            "Tape doesn't match the compiled loop"
+            );
+
+        (self.function)(tape.as_mut_ptr().cast(), index, executed)
+        }
+    }
+
+/* Function for compiling a loop's operations into native code, entered from its body - the loop can't contain input, output, or random instructions */
+fn compile_loop(ir: &Ir, open: usize, close: usize, options: CodegenOptions) -> Result<HotLoop, JitError> {
+    let mut module = JITModule::new(host_builder()?);
+
+    /* Signature - `fn(tape, index, executed) -> index` */
+    let pointer = module.target_config()
+        .pointer_type();
+
+    let mut context = module.make_context();
+    context.func.signature.params.extend([AbiParam::new(pointer); 3]);
+    context.func.signature.returns.push(AbiParam::new(pointer));
+    let loop_id = module.declare_function("braincooker_loop", Linkage::Export, &context.func.signature)
+        .map_err(codegen_error)?;
+
+    let mut builder_context = FunctionBuilderContext::new();
+    let mut builder = FunctionBuilder::new(&mut context.func, &mut builder_context);
+
+    let entry = builder.create_block();
+    builder.append_block_params_for_function_params(entry);
+    builder.switch_to_block(entry);
+
+    let [tape, start, executed] = [0, 1, 2].map(|i| builder.block_params(entry)[i]);
+
+    let index = Variable::new(0);
+    builder.declare_var(index, pointer);
+    builder.def_var(index, start);
+
+    /* Executed instructions are counted in a variable, and written back on exit */
+    let count = Variable::new(1);
+    builder.declare_var(count, types::I64);
+    let initial = builder.ins().load(types::I64, MemFlags::trusted(), executed, 0);
+    builder.def_var(count, initial);
+
+    let mut translator = Translator {
+        builder,
+        options,
+        tape,
+        index,
+        offset: 0
+        };
+
+    /* Bodies, and exits of the open loops - the compiled loop is always entered with a non-zero cell */
+    let body = translator.builder.create_block();
+    let exit = translator.builder.create_block();
+    translator.builder.ins().jump(body, &[]);
+    translator.builder.switch_to_block(body);
+
+    let mut loop_stack = vec![(body, exit)];
+
+    /* Instructions counted since the last update of the variable */
+    let mut pending = 0;
+
+    for op_ptr in open + 1 ..= close {
+        let span = ir.span(op_ptr) as i64;
+
+        match ir[op_ptr] {
+            IrOp::Add(value) => {
+                translator.add_cell(value.into());
+                pending += span;
+                },
+            IrOp::Move(value) => {
+                translator.move_pointer(value as i64);
+                pending += span;
+                },
+            IrOp::Set(value) => {
+                translator.set_cell(value);
+                pending += span;
+                },
+            IrOp::MulAdd(offset, factor) => {
+                translator.mul_add(offset as i64, factor);
+                pending += span;
+                },
+            IrOp::AddAt(offset, value) => {
+                translator.at(offset as i64, |translator| translator.add_cell(value.into()));
+                pending += span;
+                },
+            IrOp::SetAt(offset, value) => {
+                translator.at(offset as i64, |translator| translator.set_cell(value));
+                pending += span;
+                },
+            /* Enter the body, or skip the loop */
+            IrOp::LoopStart(_) => {
+                let body = translator.builder.create_block();
+                let exit = translator.builder.create_block();
+
+                update_count(&mut translator.builder, count, pending + span);
+                pending = 0;
+
+                let address = translator.address();
+                let cell = translator.load_cell(address);
+                translator.builder.ins().brif(cell, body, &[], exit, &[]);
+                translator.builder.switch_to_block(body);
+
+                loop_stack.push((body, exit));
+                },
+            /* Go back to the body, or leave the loop */
+            IrOp::LoopEnd(_) => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some((body, exit)) = loop_stack.pop() else {
+                    break;
+                    };
+
+                update_count(&mut translator.builder, count, pending + span);
+                pending = 0;
+
+                let address = translator.address();
+                let cell = translator.load_cell(address);
+                translator.builder.ins().brif(cell, body, &[], exit, &[]);
+                translator.builder.switch_to_block(exit);
+                },
+            IrOp::Output =>
+                return Err(CompileError::Unsupported(Instruction::Output.symbol()).into()),
+            IrOp::Input =>
+                return Err(CompileError::Unsupported(Instruction::Input.symbol()).into()),
+            IrOp::Random =>
+                return Err(CompileError::Unsupported(Instruction::Random.symbol()).into()),
+            IrOp::Dump =>
+                return Err(CompileError::Unsupported(Instruction::Dump.symbol()).into()),
+            IrOp::ProcStart(_) =>
+                return Err(CompileError::Unsupported(Instruction::ProcStart.symbol()).into()),
+            IrOp::ProcEnd =>
+                return Err(CompileError::Unsupported(Instruction::ProcEnd.symbol()).into()),
+            IrOp::Call =>
+                return Err(CompileError::Unsupported(Instruction::ProcCall.symbol()).into()),
+            IrOp::Break =>
+                return Err(CompileError::Unsupported(Instruction::Breakpoint.symbol()).into()),
+            IrOp::ShowPointer =>
+                return Err(CompileError::Unsupported(Instruction::ShowPointer.symbol()).into()),
+            IrOp::ShowTape =>
+                return Err(CompileError::Unsupported(Instruction::ShowTape.symbol()).into()),
+            IrOp::Switch(offset) =>
+                return Err(CompileError::Unsupported(match offset < 0 {
+                    true => Instruction::TapeUp,
+                    false => Instruction::TapeDown
+                    }.symbol()).into())
+            }
+        }
+
+    let Translator { mut builder, .. } = translator;
+
+    /* Write the count back, and return the pointer's location */
+    let total = builder.use_var(count);
+    builder.ins().store(MemFlags::trusted(), total, executed, 0);
+    let location = builder.use_var(index);
+    builder.ins().return_(&[location]);
+
+    builder.seal_all_blocks();
+    builder.finalize();
+
+    module.define_function(loop_id, &mut context)
+        .map_err(codegen_error)?;
+    module.clear_context(&mut context);
+    module.finalize_definitions()
+        .map_err(codegen_error)?;
+
+    /* Unsafe note - it is safe, because the function was defined with this exact signature, in the platform's calling convention */
+    let function = unsafe {
+        transmute::<*const u8, extern "C" fn(*mut u8, usize, *mut u64) -> usize>(module.get_finalized_function(loop_id))
+        };
+
+    Ok(HotLoop {
+        module: Some(module),
+        options,
+        function
+        })
+    }
+
+/* Helper function, for adding the counted instructions to the variable */
+fn update_count(builder: &mut FunctionBuilder, count: Variable, value: i64) {
+    if value != 0 {
+        let total = builder.use_var(count);
+        let total = builder.ins().iadd_imm(total, value);
+        builder.def_var(count, total);
+        }
+    }
+
+
+/* Interpreter's state of the tiered execution - counting iterations, and keeping the compiled loops */
+pub(crate) struct Tiering {
+    threshold: u32,
+    program: u64,
+    counters: HashMap<usize, u32>,
+    /* Compiled loops, by their opening operation - empty for the loops which can't be compiled */
+    loops: HashMap<usize, Option<HotLoop>>
+    }
+
+impl Tiering {
+    pub(crate) fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            program: 0,
+            counters: HashMap::new(),
+            loops: HashMap::new()
+            }
+        }
+
+    /* Forget the loops of the previous program, when given a different one */
+    pub(crate) fn prepare(&mut self, program: u64) {
+        if program != self.program {
+            self.program = program;
+            self.counters.clear();
+            self.loops.clear();
+            }
+        }
+
+    /* Count an iteration of the loop, and get its native code once it got hot - the loop is given by its operations */
+    pub(crate) fn hot_loop<T, U>(&mut self, ir: &Ir, open: usize, close: usize) -> Option<&HotLoop>
+    where T: TapePointer, U: TapeCell {
+        let counter = self.counters.entry(open)
+            .or_default();
+        *counter = counter.saturating_add(1);
+
+        if *counter < self.threshold {
+            return None;
+            }
+
+        self.loops.entry(open)
+            .or_insert_with(|| {
+                let compiled = native_options::<T, U>()
+                    .ok_or(JitError::Codegen("Tape's layout is not supported".to_string()))
+                    .and_then(|options| compile_loop(ir, open, close, options));
+
+                match compiled {
+                    Ok(hot_loop) => {
+                        info!("Compiled the loop at instruction: {}", ir.source(open));
+                        Some(hot_loop)
+                        },
+                    Err(error) => {
+                        info!("Couldn't compile the loop at instruction {}: {error}", ir.source(open));
+                        None
+                        }
+                    }
+                })
+            .as_ref()
+        }
+    }
+
+/* Helper function, for getting the layout of a fixed tape with the given types - the cells are told apart by their bound, as signed ones aren't compiled */
+fn native_options<T, U>() -> Option<CodegenOptions>
+where U: TapeCell {
+    let pointer_size = match size_of::<T>() {
+        1 => DataSize::U8,
+        2 => DataSize::U16,
+        4 => DataSize::U32,
+        _ => return None
+        };
+    let cell_size = match U::BOUND.as_ref().map(TapeCell::widen) {
+        Some(0xFF) => CellSize::U8,
+        Some(0xFFFF) => CellSize::U16,
+        Some(0xFFFF_FFFF) => CellSize::U32,
+        _ => return None
+        };
+
+    Some(CodegenOptions {
+        pointer_size,
+        cell_size,
+        display_mode: DisplayMode::default(),
+        opt_level: OptLevel::default()
+        })
+    }
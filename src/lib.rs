@@ -1,22 +1,233 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+/* Without the standard library, the helpers used only by its modules are left unused */
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+extern crate alloc;
+
 /* Modules declaration */
+#[cfg(feature = "std")]
+mod aarch64;
+#[cfg(feature = "std")]
+mod analysis;
+#[cfg(feature = "async")]
+mod async_interp;
+#[cfg(feature = "std")]
+mod bundle;
+#[cfg(feature = "std")]
+mod bytecode;
+#[cfg(feature = "std")]
+mod codec;
+#[cfg(feature = "std")]
+mod codegen;
+#[cfg(feature = "std")]
+mod debugger;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod elf;
 mod eval;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "std")]
 mod interp;
+#[cfg(feature = "std")]
+mod ir;
+#[cfg(feature = "jit")]
+mod jit;
+#[cfg(feature = "std")]
+mod lint;
+mod machine;
+#[cfg(feature = "std")]
+mod macho;
+#[cfg(feature = "std")]
+mod names;
+mod ook;
+#[cfg(feature = "std")]
+mod optimizer;
+#[cfg(feature = "std")]
+mod profile;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod riscv;
 mod rle;
+#[cfg(feature = "std")]
+mod rng;
+#[cfg(feature = "std")]
+mod snapshot;
 mod tape;
+#[cfg(feature = "std")]
+mod transpile;
 mod utils;
+#[cfg(feature = "std")]
+mod wasm;
+#[cfg(feature = "web")]
+mod web;
+#[cfg(feature = "std")]
+mod x86;
 
 /* Lib re-export */
 pub use {
+    eval::{
+        eval_instr,
+        eval_instr_mapped,
+        eval_instr_strict,
+        eval_instr_with,
+        eval_tokens,
+        source_location,
+        EvalError,
+        Instruction,
+        InstructionSet,
+        SourceMap,
+        TokenMap,
+        TokenMapError
+        },
+    machine::{
+        Machine,
+        MachineError
+        },
+    ook::eval_ook,
+    rle::{
+        RLEInstructionSet,
+        RLE
+        },
+    tape::{
+        MultiTape,
+        Tape,
+        TapeBackend,
+        TapeCell,
+        TapePointer
+        },
+    utils::{
+        CellOverflow,
+        CellSize,
+        CommentMode,
+        DataSize,
+        Dialect,
+        DisplayMode,
+        EofMode,
+        Lang,
+        NumericFormat,
+        OptLevel,
+        PointerOverflow,
+        TapeKind
+        }
+    };
+
+#[cfg(feature = "std")]
+pub use {
+    aarch64::{
+        compile_aarch64,
+        compile_aarch64_macho
+        },
+    analysis::{
+        Balance,
+        LoopInfo
+        },
+    bundle::{
+        Bundle,
+        BundleError
+        },
+    bytecode::{
+        Bytecode,
+        BytecodeError
+        },
+    codegen::{
+        CodegenOptions,
+        CompileError
+        },
+    debugger::{
+        Debugger,
+        StepEvent,
+        Stepper,
+        StopReason
+        },
+    diff::{
+        diff_instr,
+        Change
+        },
     interp::{
+        ArithEvent,
+        ArithHook,
+        BuildError,
+        CheckpointHook,
         InterpRun,
         Interpreter,
-        InterpreterBuilder
+        InterpreterBuilder,
+        PauseFlag,
+        RunError,
+        RunOutcome,
+        RunReport,
+        RunStats
         },
-    eval::{
-        eval_instr,
-        EvalError,
-        InstructionSet
+    ir::{
+        Ir,
+        IrOp
+        },
+    lint::{
+        check_source,
+        Lint
+        },
+    names::{
+        Annotated,
+        AnnotationError,
+        CellNames
+        },
+    optimizer::{
+        CancelRuns,
+        ClearLoops,
+        DeferMoves,
+        MultiplyLoops,
+        Optimizer,
+        OptimizerPass
         },
-    rle::RLEInstructionSet,
-    utils::DisplayMode
+    profile::{
+        LoopProfile,
+        Profile
+        },
+    riscv::compile_riscv64,
+    rng::clock_seed,
+    snapshot::{
+        Snapshot,
+        SnapshotError
+        },
+    tape::SharedTape,
+    utils::SharedBuffer,
+    transpile::{
+        transpile_c,
+        transpile_rust
+        },
+    wasm::compile_wasm,
+    x86::{
+        compile_x86_64,
+        compile_x86_64_macho
+        }
+    };
+
+#[cfg(feature = "async")]
+pub use async_interp::AsyncInterpreter;
+
+#[cfg(feature = "ffi")]
+pub use ffi::{
+    bc_eval,
+    bc_free,
+    bc_run,
+    bc_status_message,
+    BcOutput,
+    BcProgram,
+    BcStatus
+    };
+
+#[cfg(feature = "web")]
+pub use web::{
+    eval,
+    optimize,
+    Playground
+    };
+
+#[cfg(feature = "jit")]
+pub use jit::{
+    run_jit,
+    JitError,
+    JIT_THRESHOLD
     };
\ No newline at end of file
@@ -0,0 +1,178 @@
+use core::fmt::{
+    Display,
+    Formatter,
+    Result as FmtResult
+    };
+
+
+/* Suspicious constructs found in the source code - locations are character indices, like in the evaluation errors */
+#[derive(Clone, PartialEq, Debug)]
+pub enum Lint {
+    /* Loop, which moves the pointer by a non-zero number of cells on every iteration */
+    UnbalancedLoop {
+        start: usize,
+        end: usize,
+        drift: isize
+        },
+    /* Neighbouring instructions, which undo each other - like '+-', or '><' */
+    DeadPair {
+        start: usize,
+        end: usize
+        },
+    /* Loop right after the end of another one - like the one after '[-]', as the cell is always zero there */
+    DeadLoop {
+        start: usize,
+        end: usize
+        },
+    /* Loop without a body, which never ends, when the cell isn't zero */
+    EmptyLoop {
+        start: usize,
+        end: usize
+        },
+    /* Program without a single output instruction */
+    NoOutput
+    }
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Lint::UnbalancedLoop { start, end, drift } =>
+                write!(f, "Loop at {start}..={end} moves the pointer by {drift:+} cell(s) on every iteration"),
+            Lint::DeadPair { start, end } =>
+                write!(f, "Instructions at {start}, and {end} undo each other"),
+            Lint::DeadLoop { start, end } =>
+                write!(f, "Loop at {start}..={end} is never entered, as it follows the end of another loop"),
+            Lint::EmptyLoop { start, end } =>
+                write!(f, "Empty loop at {start}..={end} never ends, when it's entered with a non-zero cell"),
+            Lint::NoOutput =>
+                write!(f, "Program never writes any output")
+            }
+        }
+    }
+
+
+/* Function for finding suspicious constructs in the source code */
+pub fn check_source(instr_str: &str) -> Vec<Lint> {
+    let mut output = Vec::new();
+
+    /* Open loops - their starts, net pointer movement so far, or None if it can't be known, and whether they are never entered */
+    let mut loop_stack: Vec<(usize, Option<isize>, bool)> = Vec::new();
+    /* Previous instruction, with its index - cleared after a dead pair, so the pairs don't overlap */
+    let mut previous: Option<(usize, char)> = None;
+    let mut has_instr = false;
+    let mut has_output = false;
+
+    for (i, chr) in instr_str.chars().enumerate() {
+        match chr {
+            '>' | '<' => {
+                let step = match chr {
+                    '>' => 1,
+                    _ => -1
+                    };
+
+                if let Some((_, Some(drift), _)) = loop_stack.last_mut() {
+                    *drift += step;
+                    }
+                },
+            '[' => {
+                let dead = matches!(previous, Some((_, ']')));
+                loop_stack.push((i, Some(0), dead));
+                },
+            ']' => {
+                /* Unmatched brackets are reported by the evaluation */
+                let Some((start, drift, dead)) = loop_stack.pop() else {
+                    continue;
+                    };
+
+                /* Balanced loop leaves the outer one's movement intact, any other makes it unknown */
+                match drift {
+                    Some(0) => (),
+                    _ => if let Some((_, outer, _)) = loop_stack.last_mut() {
+                        *outer = None;
+                        }
+                    };
+
+                if let Some(drift) = drift && drift != 0 {
+                    output.push(Lint::UnbalancedLoop { start, end: i, drift });
+                    }
+
+                /* Loop, which is never entered, can't get stuck either */
+                match (dead, previous) {
+                    (true, _) => output.push(Lint::DeadLoop { start, end: i }),
+                    (false, Some((_, '['))) => output.push(Lint::EmptyLoop { start, end: i }),
+                    _ => ()
+                    }
+                },
+            '.' =>
+                has_output = true,
+            '+' | '-' | ',' | '?' | '#' => (),
+            /* Comments */
+            _ => continue
+            }
+
+        has_instr = true;
+        previous = match (previous, chr) {
+            (Some((start, '+')), '-') | (Some((start, '-')), '+') | (Some((start, '>')), '<') | (Some((start, '<')), '>') => {
+                output.push(Lint::DeadPair { start, end: i });
+                None
+                },
+            _ => Some((i, chr))
+            };
+        }
+
+    if has_instr && ! has_output {
+        output.push(Lint::NoOutput);
+        }
+
+    output
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::lint::*;
+
+    #[test]
+    fn check_balanced() {
+        assert!(check_source("++[->+<]>.").is_empty());
+        }
+
+    #[test]
+    fn check_unbalanced() {
+        assert_eq!(check_source("+[->+]"), vec![
+            Lint::UnbalancedLoop { start: 1, end: 5, drift: 1 },
+            Lint::NoOutput
+            ]);
+        }
+
+    #[test]
+    fn check_unbalanced_nested() {
+        /* The outer loop's movement can't be known, because of the inner one */
+        assert_eq!(check_source("[<[<<]>]"), vec![
+            Lint::UnbalancedLoop { start: 2, end: 5, drift: -2 },
+            Lint::NoOutput
+            ]);
+        }
+
+    #[test]
+    fn check_comments() {
+        assert_eq!(check_source("a [ b > c ] d"), vec![
+            Lint::UnbalancedLoop { start: 2, end: 10, drift: 1 },
+            Lint::NoOutput
+            ]);
+        }
+
+    #[test]
+    fn check_patterns() {
+        assert_eq!(check_source("+ -+-+.>a<"), vec![
+            Lint::DeadPair { start: 0, end: 2 },
+            Lint::DeadPair { start: 3, end: 4 },
+            Lint::DeadPair { start: 7, end: 9 }
+            ]);
+        assert_eq!(check_source("+[-] [>.<-]+[]."), vec![
+            Lint::DeadLoop { start: 5, end: 10 },
+            Lint::EmptyLoop { start: 12, end: 13 }
+            ]);
+        assert!(check_source("no instructions").is_empty());
+        }
+    }
\ No newline at end of file
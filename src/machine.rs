@@ -0,0 +1,175 @@
+use {
+    thiserror::Error,
+    alloc::{
+        boxed::Box,
+        vec::Vec
+        },
+    crate::{
+        eval::{
+            Instruction,
+            InstructionSet,
+            ProcedureTable
+            },
+        tape::*,
+        utils::{
+            EofMode,
+            TapeKind
+            }
+        }
+    };
+
+
+/* Machine's run result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum MachineError {
+    #[error("Input has ended at instruction: {0}")]
+    EndOfInput(usize),
+    #[error("Undefined procedure was called at instruction: {0}")]
+    UnknownProcedure(usize)
+    }
+
+/* Interpreter core, running the instructions one by one - the input, and output go through the callbacks, so it works without the standard library */
+pub struct Machine<U> {
+    tape: Box<dyn TapeBackend<U>>,
+    procedures: ProcedureTable,
+    eof: EofMode
+    }
+
+impl<U> Machine<U>
+where U: TapeCell + 'static {
+    /* Constructor function, for the tape of the given layout - the end of input reads as zero */
+    pub fn new<T>(kind: TapeKind) -> Self
+    where T: TapePointer + 'static {
+        let tape: Box<dyn TapeBackend<U>> = match kind {
+            TapeKind::Fixed => Box::new(Tape::<T, U>::default()),
+            TapeKind::Infinite => Box::new(InfiniteTape::<U>::default()),
+            TapeKind::Growable => Box::new(GrowableTape::<T, U>::default())
+            };
+
+        Self {
+            tape,
+            procedures: ProcedureTable::default(),
+            eof: EofMode::Zero
+            }
+        }
+
+    /* Setter - the maximum value is left unchanged on the unbounded cells */
+    pub const fn eof(mut self, value: EofMode) -> Self {
+        self.eof = value;
+        self
+        }
+
+    /* Run the instructions - the input callback gives none at the end of input, while the '?', '#', '!', '@', and '$' extensions are skipped, and the procedures are run */
+    pub fn run(&mut self, instr: &InstructionSet, mut input: impl FnMut() -> Option<U>, mut output: impl FnMut(U)) -> Result<(), MachineError> {
+        let jumps = instr.build_jump_table();
+        let mut ptr = 0;
+
+        /* Procedures are only known to the run defining them */
+        self.procedures.clear();
+
+        while ptr < instr.len() {
+            match instr[ptr] {
+                Instruction::Right => self.tape.right(),
+                Instruction::Left => self.tape.left(),
+                Instruction::Increment => self.tape.increment(),
+                Instruction::Decrement => self.tape.decrement(),
+                Instruction::LoopOpen => if self.tape.is_zero() {
+                    ptr = jumps[ptr];
+                    },
+                Instruction::LoopClose => if ! self.tape.is_zero() {
+                    ptr = jumps[ptr];
+                    },
+                Instruction::Output => output(self.tape.get()),
+                Instruction::Input => match (input(), self.eof) {
+                    (Some(value), _) => self.tape.set(value),
+                    (None, EofMode::Zero) => self.tape.set(U::ZERO),
+                    (None, EofMode::Max) => if let Some(max) = U::BOUND {
+                        self.tape.set(max);
+                        },
+                    (None, EofMode::Unchanged) => (),
+                    (None, EofMode::Error) => return Err(MachineError::EndOfInput(ptr))
+                    },
+                /* Definition only gets remembered, the body is skipped */
+                Instruction::ProcStart => {
+                    self.procedures.define(self.tape.get().widen(), ptr);
+                    ptr = jumps[ptr];
+                    },
+                Instruction::ProcEnd => if let Some(call) = self.procedures.ret() {
+                    ptr = call;
+                    },
+                Instruction::ProcCall => ptr = self.procedures.call(self.tape.get().widen(), ptr)
+                    .ok_or(MachineError::UnknownProcedure(ptr))?,
+                /* Single tape stays where it is */
+                Instruction::TapeUp => self.tape.switch(-1),
+                Instruction::TapeDown => self.tape.switch(1),
+                Instruction::Random | Instruction::Dump | Instruction::Breakpoint | Instruction::ShowPointer | Instruction::ShowTape => ()
+                }
+
+            ptr += 1;
+            }
+
+        Ok(())
+        }
+
+    /* Bring the tape back to its starting state */
+    pub fn reset(&mut self) {
+        self.tape.clear();
+        }
+
+    /* Getters */
+    pub fn position(&self) -> isize {
+        self.tape.position()
+        }
+    /* Get all cells holding something else than zero, with their locations */
+    pub fn cells(&self) -> Vec<(isize, U)> {
+        self.tape.cells()
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        eval::{
+            eval_instr,
+            eval_instr_with
+            },
+        machine::*,
+        utils::Dialect
+        };
+
+    #[test]
+    fn machine_run() {
+        let instr = eval_instr(",[.,]>,")
+            .expect("Unreachable");
+
+        let mut input = b"HAL".iter()
+            .copied();
+        let mut output = Vec::new();
+
+        let mut machine = Machine::<u8>::new::<u16>(TapeKind::Fixed);
+        machine.run(&instr, || input.next(), |value| output.push(value))
+            .expect("Unreachable");
+
+        assert_eq!(output, b"HAL");
+        assert_eq!(machine.position(), 1);
+
+        let mut machine = Machine::<u8>::new::<u16>(TapeKind::Infinite)
+            .eof(EofMode::Error);
+
+        assert_eq!(machine.run(&instr, || None, drop), Err(MachineError::EndOfInput(0)));
+        }
+
+    #[test]
+    fn machine_procedures() {
+        let instr = eval_instr_with("(+++)>+(..)<:>:>++:", &[Dialect::Pbrain])
+            .expect("Unreachable");
+
+        let mut output = Vec::new();
+        let mut machine = Machine::<u8>::new::<u16>(TapeKind::Fixed);
+
+        assert_eq!(machine.run(&instr, || None, |value| output.push(value)), Err(MachineError::UnknownProcedure(18)));
+        assert_eq!(output, [1, 1]);
+        assert_eq!(machine.cells(), [(0, 3), (1, 1), (2, 2)]);
+        }
+    }
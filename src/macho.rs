@@ -0,0 +1,341 @@
+use {
+    sha2::{
+        Digest,
+        Sha256
+        },
+    crate::codegen::*
+    };
+
+
+/* Address the headers, and the code get loaded at - everything below is unmapped, as required on arm64 */
+pub const TEXT_ADDRESS: u64 = 0x1_0000_0000;
+/* Address of the zero-initialised memory */
+pub const BSS_ADDRESS: u64 = TEXT_ADDRESS + 0x1000_0000;
+
+/* Processor types, and subtypes */
+pub struct Cpu(pub u32, pub u32);
+pub const CPU_X86_64: Cpu = Cpu(0x0100_0007, 3);
+pub const CPU_ARM64: Cpu = Cpu(0x0100_000C, 0);
+
+/* Size of the segments' pages - the bigger, arm64 one works for both */
+const PAGE_SIZE: u64 = 0x4000;
+/* Size of the pages hashed by the code signature */
+const HASH_PAGE_SIZE: u64 = 0x1000;
+
+/* Sizes of the headers */
+const HEADER_SIZE: u64 = 32;
+const SEGMENT_SIZE: u64 = 72;
+const SECTION_SIZE: u64 = 80;
+const CODE_DIRECTORY_SIZE: u64 = 88;
+/* Offset of the code in the file - headers are padded, so the tools have room for more load commands */
+const CODE_OFFSET: u64 = 0x800;
+
+/* Load commands' identifiers */
+const LC_SEGMENT_64: u32 = 0x19;
+const LC_SYMTAB: u32 = 0x2;
+const LC_DYSYMTAB: u32 = 0xB;
+const LC_LOAD_DYLINKER: u32 = 0xE;
+const LC_LOAD_DYLIB: u32 = 0xC;
+const LC_MAIN: u32 = 0x8000_0028;
+const LC_BUILD_VERSION: u32 = 0x32;
+const LC_CODE_SIGNATURE: u32 = 0x1D;
+
+/* Paths of the dynamic linker, and the system library every executable has to link */
+const DYLINKER: &[u8] = b"/usr/lib/dyld";
+const LIB_SYSTEM: &[u8] = b"/usr/lib/libSystem.B.dylib";
+/* Identifier of the signed code */
+const IDENTIFIER: &[u8] = b"braincooker";
+
+
+/* Placement of the executable in memory, for the code generation - it gets slid as a whole */
+pub(crate) const LAYOUT: Layout = Layout {
+    platform: Platform::MacOs,
+    code_address: TEXT_ADDRESS + CODE_OFFSET,
+    bss_address: BSS_ADDRESS
+    };
+
+
+/* Segment's load command, with its sections */
+struct Segment {
+    name: &'static [u8],
+    address: u64,
+    memory_size: u64,
+    offset: u64,
+    file_size: u64,
+    protection: u32,
+    sections: Vec<Section>
+    }
+
+/* Section's header - without relocations */
+struct Section {
+    name: &'static [u8],
+    segment: &'static [u8],
+    address: u64,
+    size: u64,
+    offset: u32,
+    align: u32,
+    flags: u32
+    }
+
+impl Segment {
+    /* Write the load command */
+    fn push(&self, output: &mut Vec<u8>) {
+        let size = SEGMENT_SIZE + SECTION_SIZE * self.sections.len() as u64;
+
+        output.extend_from_slice(&LC_SEGMENT_64.to_le_bytes());
+        output.extend_from_slice(&(size as u32).to_le_bytes());
+        push_name(output, self.name);
+        output.extend_from_slice(&self.address.to_le_bytes());
+        output.extend_from_slice(&self.memory_size.to_le_bytes());
+        output.extend_from_slice(&self.offset.to_le_bytes());
+        output.extend_from_slice(&self.file_size.to_le_bytes());
+        output.extend_from_slice(&self.protection.to_le_bytes());
+        output.extend_from_slice(&self.protection.to_le_bytes());
+        output.extend_from_slice(&(self.sections.len() as u32).to_le_bytes());
+        output.extend_from_slice(&0_u32.to_le_bytes());
+
+        for section in &self.sections {
+            push_name(output, section.name);
+            push_name(output, section.segment);
+            output.extend_from_slice(&section.address.to_le_bytes());
+            output.extend_from_slice(&section.size.to_le_bytes());
+            output.extend_from_slice(&section.offset.to_le_bytes());
+            output.extend_from_slice(&section.align.to_le_bytes());
+            output.extend_from_slice(&[0; 8]);
+            output.extend_from_slice(&section.flags.to_le_bytes());
+            output.extend_from_slice(&[0; 12]);
+            }
+        }
+    }
+
+/* Helper function, for writing a segment's, or a section's name - padded to 16 bytes */
+fn push_name(output: &mut Vec<u8>, name: &[u8]) {
+    let mut padded = [0; 16];
+    padded[.. name.len()].copy_from_slice(name);
+    output.extend_from_slice(&padded);
+    }
+
+/* Helper function, for writing a load command with a path - padded to 8 bytes */
+fn push_path_command(output: &mut Vec<u8>, command: u32, fields: &[u32], path: &[u8]) {
+    let offset = 8 + 4 * (fields.len() as u32 + 1);
+    let size = (offset as usize + path.len() + 1).next_multiple_of(8);
+
+    output.extend_from_slice(&command.to_le_bytes());
+    output.extend_from_slice(&(size as u32).to_le_bytes());
+    output.extend_from_slice(&offset.to_le_bytes());
+    for field in fields {
+        output.extend_from_slice(&field.to_le_bytes());
+        }
+    output.extend_from_slice(path);
+    output.resize(output.len() + size - offset as usize - path.len(), 0);
+    }
+
+/* Helper function, for writing a load command made of 32-bit fields */
+fn push_command(output: &mut Vec<u8>, command: u32, fields: &[u32]) {
+    output.extend_from_slice(&command.to_le_bytes());
+    output.extend_from_slice(&(8 + 4 * fields.len() as u32).to_le_bytes());
+    for field in fields {
+        output.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+
+
+/* Function for wrapping the code into a macOS executable - loaded by the dynamic linker, with the zero-initialised memory, and signed ad-hoc, as required on arm64 */
+pub fn executable(cpu: Cpu, code: &[u8], entry: u64, bss_size: u64) -> Vec<u8> {
+    /* Headers, and the code, then the symbol table's placeholder, and the code signature */
+    let text_size = (CODE_OFFSET + code.len() as u64).next_multiple_of(PAGE_SIZE);
+    let bss_memory_size = bss_size.next_multiple_of(PAGE_SIZE);
+    let strings_offset = text_size;
+    let signature_offset = strings_offset + 16;
+    let signature_size = signature_size(signature_offset);
+    let linkedit_size = signature_offset + signature_size - text_size;
+
+    let segments = [
+        /* Unmapped memory, catching null pointers */
+        Segment {
+            name: b"__PAGEZERO",
+            address: 0,
+            memory_size: TEXT_ADDRESS,
+            offset: 0,
+            file_size: 0,
+            protection: 0,
+            sections: Vec::new()
+            },
+        /* Readable, and executable code, loaded together with the headers */
+        Segment {
+            name: b"__TEXT",
+            address: TEXT_ADDRESS,
+            memory_size: text_size,
+            offset: 0,
+            file_size: text_size,
+            protection: 0b101,
+            sections: vec![Section {
+                name: b"__text",
+                segment: b"__TEXT",
+                address: TEXT_ADDRESS + CODE_OFFSET,
+                size: code.len() as u64,
+                offset: CODE_OFFSET as u32,
+                align: 4,
+                flags: 0x8000_0400
+                }]
+            },
+        /* Readable, and writable memory, not backed by the file */
+        Segment {
+            name: b"__DATA",
+            address: BSS_ADDRESS,
+            memory_size: bss_memory_size,
+            offset: text_size,
+            file_size: 0,
+            protection: 0b011,
+            sections: vec![Section {
+                name: b"__bss",
+                segment: b"__DATA",
+                address: BSS_ADDRESS,
+                size: bss_size,
+                offset: 0,
+                align: 4,
+                flags: 0x1
+                }]
+            },
+        /* Read-only data for the dynamic linker, and the kernel - it has to come last */
+        Segment {
+            name: b"__LINKEDIT",
+            address: BSS_ADDRESS + bss_memory_size,
+            memory_size: linkedit_size.next_multiple_of(PAGE_SIZE),
+            offset: text_size,
+            file_size: linkedit_size,
+            protection: 0b001,
+            sections: Vec::new()
+            }
+        ];
+
+    let mut commands = Vec::new();
+    for segment in &segments {
+        segment.push(&mut commands);
+        }
+
+    /* No symbols, and an empty string table */
+    push_command(&mut commands, LC_SYMTAB, &[0, 0, strings_offset as u32, 16]);
+    push_command(&mut commands, LC_DYSYMTAB, &[0; 18]);
+    push_path_command(&mut commands, LC_LOAD_DYLINKER, &[], DYLINKER);
+
+    /* Entry point's offset in the file, and the default stack size */
+    commands.extend_from_slice(&LC_MAIN.to_le_bytes());
+    commands.extend_from_slice(&24_u32.to_le_bytes());
+    commands.extend_from_slice(&(CODE_OFFSET + entry).to_le_bytes());
+    commands.extend_from_slice(&0_u64.to_le_bytes());
+
+    /* Timestamp, current, and compatibility versions - 1.0.0 is accepted by every version */
+    push_path_command(&mut commands, LC_LOAD_DYLIB, &[2, 0x1_0000, 0x1_0000], LIB_SYSTEM);
+    /* macOS 11.0, the first one on arm64, with no tools */
+    push_command(&mut commands, LC_BUILD_VERSION, &[1, 0xB_0000, 0xB_0000, 0]);
+    push_command(&mut commands, LC_CODE_SIGNATURE, &[signature_offset as u32, signature_size as u32]);
+
+    /* Commands always fit into the padding */
+    debug_assert!(HEADER_SIZE + commands.len() as u64 <= CODE_OFFSET);
+
+    let mut output = Vec::with_capacity((signature_offset + signature_size) as usize);
+
+    /* Magic number, processor, executable file type, and the load commands */
+    output.extend_from_slice(&0xFEED_FACF_u32.to_le_bytes());
+    output.extend_from_slice(&cpu.0.to_le_bytes());
+    output.extend_from_slice(&cpu.1.to_le_bytes());
+    output.extend_from_slice(&2_u32.to_le_bytes());
+    output.extend_from_slice(&11_u32.to_le_bytes());
+    output.extend_from_slice(&(commands.len() as u32).to_le_bytes());
+
+    /* No undefined symbols, dynamically linked, two-level namespace, and position-independent */
+    output.extend_from_slice(&0x0020_0085_u32.to_le_bytes());
+    output.extend_from_slice(&0_u32.to_le_bytes());
+
+    output.extend_from_slice(&commands);
+    output.resize(CODE_OFFSET as usize, 0);
+    output.extend_from_slice(code);
+    output.resize(signature_offset as usize, 0);
+
+    let signature = code_signature(&output, text_size);
+    output.extend_from_slice(&signature);
+
+    output
+    }
+
+/* Helper function, for getting the code signature's size - the blob's header, the code directory, and a hash of every page */
+const fn signature_size(code_limit: u64) -> u64 {
+    12 + 8 + CODE_DIRECTORY_SIZE + IDENTIFIER.len() as u64 + 1 + 32 * code_limit.div_ceil(HASH_PAGE_SIZE)
+    }
+
+/* Function for signing the file ad-hoc - a super blob, with a single code directory of SHA-256 hashes */
+fn code_signature(file: &[u8], text_size: u64) -> Vec<u8> {
+    let code_limit = file.len() as u64;
+    let pages = code_limit.div_ceil(HASH_PAGE_SIZE);
+    let identifier_offset = CODE_DIRECTORY_SIZE;
+    let hash_offset = identifier_offset + IDENTIFIER.len() as u64 + 1;
+    let directory_size = hash_offset + 32 * pages;
+
+    let mut output = Vec::with_capacity(signature_size(code_limit) as usize);
+
+    /* Super blob, with the code directory's slot */
+    output.extend_from_slice(&0xFADE_0CC0_u32.to_be_bytes());
+    output.extend_from_slice(&(signature_size(code_limit) as u32).to_be_bytes());
+    output.extend_from_slice(&1_u32.to_be_bytes());
+    output.extend_from_slice(&0_u32.to_be_bytes());
+    output.extend_from_slice(&20_u32.to_be_bytes());
+
+    /* Code directory - magic number, size, version, and ad-hoc signed */
+    output.extend_from_slice(&0xFADE_0C02_u32.to_be_bytes());
+    output.extend_from_slice(&(directory_size as u32).to_be_bytes());
+    output.extend_from_slice(&0x2_0400_u32.to_be_bytes());
+    output.extend_from_slice(&0x2_u32.to_be_bytes());
+
+    /* Locations of the hashes, and the identifier, no special slots, and the number of the code ones */
+    output.extend_from_slice(&(hash_offset as u32).to_be_bytes());
+    output.extend_from_slice(&(identifier_offset as u32).to_be_bytes());
+    output.extend_from_slice(&0_u32.to_be_bytes());
+    output.extend_from_slice(&(pages as u32).to_be_bytes());
+    output.extend_from_slice(&(code_limit as u32).to_be_bytes());
+
+    /* Hash's size, SHA-256, no platform, 4 KiB pages, and no scatter, team, nor 64-bit limit */
+    output.extend_from_slice(&[32, 2, 0, HASH_PAGE_SIZE.trailing_zeros() as u8]);
+    output.extend_from_slice(&[0; 24]);
+
+    /* Executable segment - the whole text, of the main binary */
+    output.extend_from_slice(&0_u64.to_be_bytes());
+    output.extend_from_slice(&text_size.to_be_bytes());
+    output.extend_from_slice(&1_u64.to_be_bytes());
+
+    output.extend_from_slice(IDENTIFIER);
+    output.push(0);
+
+    for page in file.chunks(HASH_PAGE_SIZE as usize) {
+        output.extend_from_slice(&Sha256::digest(page));
+        }
+
+    output
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::macho::*;
+
+    #[test]
+    fn macho_layout() {
+        let code = [0xC3];
+        let output = executable(CPU_X86_64, &code, 0, 16);
+        let commands = u32::from_le_bytes([output[20], output[21], output[22], output[23]]);
+
+        assert_eq!(&output[.. 4], &0xFEED_FACF_u32.to_le_bytes());
+        assert!(HEADER_SIZE + commands as u64 <= CODE_OFFSET);
+        assert_eq!(output[CODE_OFFSET as usize], 0xC3);
+        }
+
+    #[test]
+    fn macho_signature_size() {
+        let code = vec![0x90; 0x5000];
+        let output = executable(CPU_ARM64, &code, 0, 16);
+        let text_size = (CODE_OFFSET + 0x5000).next_multiple_of(PAGE_SIZE);
+
+        assert_eq!(output.len() as u64, text_size + 16 + signature_size(text_size + 16));
+        assert_eq!(&output[text_size as usize + 16 ..][.. 4], &0xFADE_0CC0_u32.to_be_bytes());
+        }
+    }
\ No newline at end of file
@@ -1,32 +1,101 @@
 /* Modules declaration */
 mod args;
+mod bench;
+mod debug;
+mod matrix;
+mod pack;
+mod pipe;
+mod repl;
+mod runner;
+#[cfg(feature = "tui")]
+mod tui;
+mod warm;
 
 use {
-    anyhow::Result as DynResult,
+    anyhow::{
+        anyhow,
+        bail,
+        Result as DynResult
+        },
     clap::Parser,
+    ctrlc::set_handler,
     env_logger::builder as logger_build,
     log::*,
+    num_bigint::BigUint,
     std::{
         fs::{
+            read,
             read_to_string,
             File
             },
-        io::Write
+        io::{
+            Cursor,
+            Write
+            },
+        path::{
+            Path,
+            PathBuf
+            },
+        process::exit,
+        sync::{
+            atomic::{
+                AtomicBool,
+                Ordering
+                },
+            Arc
+            },
+        thread::{
+            sleep,
+            spawn
+            },
+        time::Duration
         },
     core::hint::unreachable_unchecked,
-    crate::args::*,
+    crate::{
+        args::*,
+        bench::*,
+        debug::*,
+        matrix::*,
+        pack::*,
+        pipe::*,
+        repl::*,
+        runner::*,
+        warm::*
+        },
     braincooker::*
     };
 
+#[cfg(unix)]
+use std::{
+    fs::Permissions,
+    os::unix::fs::PermissionsExt
+    };
+
+#[cfg(feature = "tui")]
+use crate::tui::*;
+
+#[cfg(feature = "jit")]
+use std::io::{
+    stdin,
+    stdout
+    };
+
 
 /* Main entrypoint */
 fn main() -> DynResult<()> {
     /* Parse CLI arguments */
     let Args { command } = Args::parse();
 
+    /* Bundles get unpacked into the interpreter command, with the settings they were packed with */
+    let command = match command {
+        CMD::Run { bundle, settings } =>
+            unpack_bundle(&bundle, &settings)?,
+        command => command
+        };
+
     /* Unpack basic arguments */
-    let Inputs { input, input_file } = command.get_inputs();
-    let &Settings { debug_display, loop_prune } = command.get_settings();
+    let settings = command.get_settings();
+    let Settings { debug_display, quiet, loop_prune, dialect, .. } = settings;
 
     /* Init the logger */
     logger_build()
@@ -35,9 +104,110 @@ fn main() -> DynResult<()> {
             let style = buf.default_level_style(level);
             writeln!(buf, "{style}{level}{style:#}: {}", record.args())
             })
-        .filter_level(select!(debug_display, LevelFilter::Info, LevelFilter::Error))
+        .filter_level(match (*debug_display, *quiet) {
+            (_, true) => LevelFilter::Off,
+            (true, false) => LevelFilter::Info,
+            (false, false) => LevelFilter::Error
+            })
         .init();
 
+    /* Execute commands, which don't work with a single source */
+    match &command {
+        /* Compare two programs */
+        CMD::Diff { old, new, .. } => {
+            let (old, _) = settings.eval(&read_to_string(old)?)?;
+            let (new, _) = settings.eval(&read_to_string(new)?)?;
+
+            let changes = diff_instr(&old, &new);
+
+            for change in &changes {
+                println!("{change}");
+                }
+
+            info!("{} structural change(s) were found", changes.len());
+
+            return Ok(());
+            },
+        /* Run the programs connected one after another */
+        CMD::Pipe { programs, pointer_size, cell_size, tape, display_mode, eof, .. } => {
+            let builder = || Interpreter::builder()
+                .display_mode(*display_mode)
+                .tape_kind(*tape)
+                .eof(*eof);
+
+            return run_pipeline(programs, builder, *pointer_size, *cell_size, settings);
+            },
+        /* Run a directory of programs */
+        CMD::Test { dir, max_steps, timeout, .. } =>
+            return run_tests(dir, settings, *max_steps, *timeout),
+        /* Run the built-in programs under every configuration */
+        CMD::Bench { programs, tape, opt_level, runs, .. } =>
+            return run_bench(programs, tape, opt_level, *runs),
+        /* Run the entered lines on a persistent tape */
+        CMD::Repl { pointer_size, cell_size, tape, display_mode, seed, .. } => {
+            let builder = interactive_builder(*cell_size, *tape, *display_mode, *seed, None)
+                .tapes(settings.tape_count(None));
+
+            return run_repl(construct_interp(builder, *pointer_size, *cell_size)?, dialect);
+            },
+        /* Run compiled bytecode - it was parsed, and optimised already */
+        CMD::Interp { inputs: Inputs { input_file: Some(path), .. }, bounds, display_mode, matrix, warm_start, dump_tape, tape_in, tape_out, checkpoint_every, restore, timeout, output_file, expect_output, update_expected, .. } if path.extension().is_some_and(|ext| ext == "bfc") => {
+            /* Sizes are fixed by the bytecode, and only the runs of the source code's instructions can be snapshotted */
+            if *matrix || *warm_start || checkpoint_every.is_some() || restore.is_some() {
+                bail!("Bytecode can't be run with the matrix, warm starts, checkpoints, nor snapshots - run its source code instead");
+                }
+
+            let bytecode = Bytecode::decode(&read(path)?)?;
+            let random = bytecode.ir.ops().contains(&IrOp::Random);
+            let (_, interp_build) = interp_builder(&command, random);
+
+            /* Capture the output, when it gets compared with a golden file, or redirect it into a file */
+            let buffer = SharedBuffer::default();
+            let mut builder = match (expect_output, output_file) {
+                (Some(_), _) => interp_build(bytecode.cell_size)
+                    .output(Box::new(buffer.clone())),
+                (None, Some(path)) => interp_build(bytecode.cell_size)
+                    .output(Box::new(File::create(path)?)),
+                (None, None) => interp_build(bytecode.cell_size)
+                };
+
+            if let Some(path) = tape_in {
+                builder = builder.initial_tape(&read_tape_image(path, bytecode.cell_size)?);
+                }
+
+            let (pause, timed_out) = pause_on_timeout(*timeout)?;
+            builder = builder.pause_flag(pause);
+
+            let mut interp = construct_interp(builder, bytecode.pointer_size, bytecode.cell_size)?;
+            let outcome = interp.run_ir(&bytecode.ir);
+
+            /* Report the tape, even when the run failed - the bytecode has no cell names */
+            write_tape(&*interp, *dump_tape, tape_out, settings.tape_count(bounds.tapes) > 1, &CellNames::default())?;
+
+            if let Err(RunError::Paused(instr_ptr)) = outcome {
+                let reason = select!(timed_out.load(Ordering::Relaxed), "timed out", "was interrupted");
+
+                bail!("Run {reason} at instruction {instr_ptr}");
+                }
+
+            outcome?;
+
+            if let Some(path) = expect_output {
+                check_golden(path, buffer.take(), *display_mode, *update_expected)?;
+                }
+
+            return Ok(());
+            },
+        _ => ()
+        };
+
+    /* Unsafe note - it is safe, because the commands without a single source were handled above, and bundles were unpacked */
+    let Some(Inputs { input, input_file }) = command.get_inputs() else {
+        unsafe {
+            unreachable_unchecked()
+            }
+        };
+
     /* Match correct source code input - Option::xor with Option::and_then */
     let instr_str = match (input, input_file) {
         /* Raw text input */
@@ -52,10 +222,27 @@ fn main() -> DynResult<()> {
             }
         };
 
-    /* Get sanitised instructions */
-    let mut instr = eval_instr(instr_str)?;
+    /* Get human-readable names of cells, and take their annotations out of the source code - the malformed ones are left as the plain comments */
+    let Annotated { names: cell_names, source, malformed } = CellNames::parse(instr_str)?;
+    let instr_str = source.as_str();
+
+    for warning in malformed {
+        warn!("{warning}, it's read as a comment");
+        }
+    for (index, _) in cell_names.iter() {
+        info!("Named cell: {}", cell_names.label(index));
+        }
+
+    /* Get sanitised instructions, with their positions - the check command also points at the error's line, and column */
+    let (mut instr, mut source_map) = match (settings.eval(instr_str), &command) {
+        (Err(err), CMD::Check { .. }) =>
+            bail!(diagnose(instr_str, &err)),
+        (result, _) =>
+            result?
+        };
 
-    /* Prune comment loops according to the settings */
+    /* Prune comment loops according to the settings - the positions of the pruned instructions get dropped */
+    let len = instr.len();
     match loop_prune {
         Some(LoopPrune::One) => {
             let was_prunned = instr.prune_comment_loop();
@@ -69,55 +256,505 @@ fn main() -> DynResult<()> {
             },
         None => ()
         };
+    source_map.skip(len - instr.len());
 
     /* Execute matching command */
     match command {
-        CMD::Interp { pointer_size, cell_size, display_mode, .. } => {
-            /* Construct a builder, and pass the settings */
-            let interp_build = Interpreter::builder()
-                .display_mode(display_mode);
-
-            /* Construct a fitting Interpreter, based on arguments */
-            let mut interp: Box<dyn InterpRun> = match (pointer_size, cell_size) {
-                (DataSize::U8, DataSize::U8) =>
-                    Box::new(interp_build.build::<u8, u8>()),
-                (DataSize::U8, DataSize::U16) =>
-                    Box::new(interp_build.build::<u8, u16>()),
-                (DataSize::U8, DataSize::U32) =>
-                    Box::new(interp_build.build::<u8, u32>()),
-
-                (DataSize::U16, DataSize::U8) =>
-                    Box::new(interp_build.build::<u16, u8>()),
-                (DataSize::U16, DataSize::U16) =>
-                    Box::new(interp_build.build::<u16, u16>()),
-                (DataSize::U16, DataSize::U32) =>
-                    Box::new(interp_build.build::<u16, u32>()),
-
-                (DataSize::U32, DataSize::U8) =>
-                    Box::new(interp_build.build::<u32, u8>()),
-                (DataSize::U32, DataSize::U16) =>
-                    Box::new(interp_build.build::<u32, u16>()),
-                (DataSize::U32, DataSize::U32) =>
-                    Box::new(interp_build.build::<u32, u32>()),
+        CMD::Interp { pointer_size, cell_size, tape, ref bounds, display_mode, ref formatting, opt_level, ref stdin, ref input_data, max_output, matrix, warm_start, dump_tape, ref tape_in, ref tape_out, checkpoint_every, ref checkpoint_file, ref restore, timeout, ref output_file, ref expect_output, update_expected, .. } => {
+            let (seed, interp_build) = interp_builder(&command, dialect.contains(&Dialect::ExtRand));
+
+            /* Tape image, and the checkpoints only hold a single tape */
+            if settings.tape_count(bounds.tapes) > 1 && (tape_out.is_some() || checkpoint_every.is_some() || warm_start || restore.is_some()) {
+                bail!("Tape image, checkpoints, and snapshots can only hold a single tape");
+                }
+
+            /* Initial tape only gets read for a single cell size, as it conflicts with the matrix */
+            let initial_tape = tape_in.as_ref()
+                .map(|path| read_tape_image(path, cell_size))
+                .transpose()?;
+
+            /* Key of the cached warm start - sizes get added for each run */
+            let warm_key = warm_start
+                .then(|| cache_key((instr.fingerprint(), (tape as u8, bounds.tape_size, bounds.start_cell, bounds.fill, bounds.pointer_overflow as u8, bounds.cell_overflow as u8), display_mode.map(|mode| mode as u8), formatting, opt_level as u8, seed, max_output, stdin.is_some() || input_data.is_some(), &initial_tape)));
+
+            match matrix {
+                /* Run under every size combination, and compare the outputs */
+                true =>
+                    run_matrix(&instr, interp_build, warm_key, stdin.as_ref().or(input_data.as_ref()))?,
+                /* Execute instructions */
+                false => {
+                    /* Capture the output, when it gets compared with a golden file, or redirect it into a file */
+                    let buffer = SharedBuffer::default();
+                    let mut builder = match (expect_output, output_file) {
+                        (Some(_), _) => interp_build(cell_size)
+                            .output(Box::new(buffer.clone())),
+                        (None, Some(path)) => interp_build(cell_size)
+                            .output(Box::new(File::create(path)?)),
+                        (None, None) => interp_build(cell_size)
+                        };
+
+                    if let Some(cells) = &initial_tape {
+                        builder = builder.initial_tape(cells);
+                        }
+                    if let Some(every) = checkpoint_every {
+                        builder = builder.checkpoint(every, checkpoint_hook(checkpoint_file));
+                        }
+                    let (pause, timed_out) = pause_on_timeout(timeout)?;
+                    builder = builder.pause_flag(pause);
+
+                    let mut interp = construct_interp(builder, pointer_size, cell_size)?;
+
+                    let outcome = match (warm_key, restore) {
+                        (Some(key), _) =>
+                            run_warm(&mut *interp, &instr, cache_key((key, pointer_size as u8, cell_size as u8))),
+                        (None, Some(path)) =>
+                            run_restored(&mut *interp, &instr, path),
+                        (None, None) =>
+                            interp.run_with_report(&instr)
+                                .map(|report| info!("Run took {:?}, wrote {} byte(s), read {} byte(s), and left {} cell(s) set", report.elapsed, report.written, report.read, report.set_cells))
+                                .map_err(Into::into)
+                        };
+
+                    /* Report the profile, and the tape, even when the run failed */
+                    if let Some(profile) = interp.take_profile() {
+                        eprint!("{}", profile.report(&instr, 10));
+                        }
+                    write_tape(&*interp, dump_tape, tape_out, settings.tape_count(bounds.tapes) > 1, &cell_names)?;
+
+                    /* Only the timeout, and the interruption pause the run */
+                    if let Err(err) = &outcome && let Some(&RunError::Paused(instr_ptr)) = err.downcast_ref() {
+                        let executed = interp.snapshot()
+                            .map_or(0, |snapshot| snapshot.executed());
+                        let reason = select!(timed_out.load(Ordering::Relaxed), "timed out", "was interrupted");
+
+                        bail!("Run {reason} at instruction {instr_ptr}{}, after {executed} executed instruction(s)", locate(instr_str, &source_map, instr_ptr));
+                        }
+
+                    /* Errors pointing at an instruction also get its line, and column */
+                    outcome.map_err(|err| match err.downcast_ref::<RunError>().and_then(RunError::instruction) {
+                        Some(index) => anyhow!("{err}{}", locate(instr_str, &source_map, index)),
+                        None => err
+                        })?;
+
+                    if let Some(path) = expect_output {
+                        check_golden(path, buffer.take(), display_mode, update_expected)?;
+                        }
+                    }
+                }
+            },
+        CMD::Debug { pointer_size, cell_size, tape, display_mode, seed, ref stdin, .. } => {
+            let builder = interactive_builder(cell_size, tape, display_mode, seed, stdin.as_ref())
+                .tapes(settings.tape_count(None));
+            let interp = construct_interp(builder, pointer_size, cell_size)?;
+
+            run_debugger(Debugger::new(interp, &instr), &instr)?;
+            },
+        #[cfg(feature = "tui")]
+        CMD::Tui { pointer_size, cell_size, tape, display_mode, seed, ref stdin, .. } => {
+            /* Output, and the dumps are shown in the interface, and the terminal can't be used for the input */
+            let buffer = SharedBuffer::default();
+            let mut builder = interactive_builder(cell_size, tape, display_mode, seed, stdin.as_ref())
+                .tapes(settings.tape_count(None))
+                .output(Box::new(buffer.clone()))
+                .dump_output(Box::new(buffer.clone()));
+
+            if stdin.is_none() {
+                builder = builder
+                    .input(Box::new(Cursor::new(Vec::new())));
+                }
+
+            let interp = construct_interp(builder, pointer_size, cell_size)?;
+
+            run_tui(Debugger::new(interp, &instr), &instr, buffer)?;
+            },
+        CMD::Check { .. } => {
+            let lints = lint_source(instr_str, &instr, settings);
+
+            for lint in &lints {
+                println!("warning: {lint}");
+                }
+
+            if ! lints.is_empty() {
+                bail!("{} lint(s) were found", lints.len());
+                }
+            },
+        CMD::Analyze { json, .. } => {
+            let stats = instr.stats();
+
+            match json {
+                true => println!("{}", stats.to_json()),
+                false => print!("{stats}")
+                }
+
+            /* Lints go to the error stream, so the JSON stays clean */
+            for lint in lint_source(instr_str, &instr, settings) {
+                eprintln!("warning: {lint}");
+                }
+            },
+        CMD::Pack { ref output_file, .. } =>
+            pack_bundle(&command, &instr, output_file)?,
+        /* Unsafe note - it is safe, because the commands without a single source return early, and bundles get unpacked */
+        CMD::Diff { .. } | CMD::Pipe { .. } | CMD::Test { .. } | CMD::Bench { .. } | CMD::Repl { .. } | CMD::Run { .. } => unsafe {
+            unreachable_unchecked()
+            },
+        #[cfg(feature = "jit")]
+        CMD::Jit { pointer_size, cell_size, display_mode, .. } => {
+            let options = CodegenOptions {
+                pointer_size,
+                cell_size,
+                display_mode: display_mode
+                    .unwrap_or(select!(cell_size == CellSize::U1, DisplayMode::Numeric, DisplayMode::ASCII)),
+                opt_level: OptLevel::default()
                 };
 
-            /* Execute instructions */
-            interp.run(&instr)?;
+            run_jit(&instr, options, Box::new(stdin()), Box::new(stdout()))?;
             },
-        CMD::Comp { output_file, .. } => {
-            let mut file = File::create(output_file)?;
+        CMD::Comp { pointer_size, cell_size, display_mode, opt_level, arch, emit, checked, output_file, .. } => {
+            let options = CodegenOptions {
+                pointer_size,
+                cell_size,
+                display_mode: display_mode
+                    .unwrap_or(select!(cell_size == CellSize::U1, DisplayMode::Numeric, DisplayMode::ASCII)),
+                opt_level
+                };
+
+            match emit {
+                Emit::Binary => {
+                    let binary = match arch {
+                        Arch::X86_64 => compile_x86_64(&instr, options)?,
+                        Arch::Arm64 => compile_aarch64(&instr, options)?,
+                        Arch::RiscV64 => compile_riscv64(&instr, options)?
+                        };
+
+                    let mut file = File::create(&output_file)?;
+                    file.write_all(&binary)?;
+
+                    /* Mark the file as executable */
+                    #[cfg(unix)]
+                    file.set_permissions(Permissions::from_mode(0o755))?;
+                    },
+                Emit::MachO => {
+                    let binary = match arch {
+                        Arch::X86_64 => compile_x86_64_macho(&instr, options)?,
+                        Arch::Arm64 => compile_aarch64_macho(&instr, options)?,
+                        Arch::RiscV64 => bail!("Mach-O executables are not supported for RISC-V")
+                        };
+
+                    let mut file = File::create(&output_file)?;
+                    file.write_all(&binary)?;
+
+                    /* Mark the file as executable */
+                    #[cfg(unix)]
+                    file.set_permissions(Permissions::from_mode(0o755))?;
+                    },
+                /* Source code is portable, so the architecture doesn't matter */
+                Emit::C =>
+                    File::create(&output_file)?
+                        .write_all(transpile_c(&instr, options)?.as_bytes())?,
+                Emit::Rust =>
+                    File::create(&output_file)?
+                        .write_all(transpile_rust(&instr, options, checked)?.as_bytes())?,
+                /* Modules are run by their host, so they aren't executable */
+                Emit::Wasm =>
+                    File::create(&output_file)?
+                        .write_all(&compile_wasm(&instr, options)?)?,
+                /* Bytecode is run by the interpreter, so the display mode is picked there */
+                Emit::Bytecode =>
+                    File::create(&output_file)?
+                        .write_all(&Bytecode::compile(&instr, pointer_size, cell_size, opt_level).encode())?
+                }
+
+            info!("Compiled into: {}", output_file.display());
+            }
+        }
+
+    Ok(())
+    }
+
+
+/* Function for describing the evaluation error with its line, and column - followed by the line itself, with a marker below the character */
+fn diagnose(instr_str: &str, err: &EvalError) -> String {
+    let Some(index) = err.location(instr_str) else {
+        return err.to_string();
+        };
+
+    let (line, column) = source_location(instr_str, index);
+    let text = instr_str.lines()
+        .nth(line - 1)
+        .unwrap_or_default();
+
+    format!("{err} (line {line}, column {column})\n{text}\n{:>column$}", "^")
+    }
+
+/* Function for finding the suspicious constructs - the other languages get checked in their Brainfuck form, as the lints point at its characters */
+fn lint_source(instr_str: &str, instr: &InstructionSet, settings: &Settings) -> Vec<Lint> {
+    match settings.is_brainfuck() {
+        true => check_source(instr_str),
+        false => check_source(&instr.to_source())
+        }
+    }
+
+/* Function for describing the instruction's line, and column in the source code - empty, when it's unknown */
+fn locate(instr_str: &str, source_map: &SourceMap, index: usize) -> String {
+    source_map.location(instr_str, index)
+        .map(|(line, column)| format!(" (line {line}, column {column})"))
+        .unwrap_or_default()
+    }
+
+/* Function for getting the random seed, and a builder of the interpreter command's settings for the given cell size - the seed is only picked, when the program can use it */
+fn interp_builder(command: &CMD, random: bool) -> (Option<u64>, impl Fn(CellSize) -> InterpreterBuilder + '_) {
+    /* Unsafe note - it is safe, because it's only called for the interpreter command */
+    let CMD::Interp { settings, tape, bounds, display_mode, formatting, seed, opt_level, stdin, input_data, eof, max_output, max_steps, profile, flush_every_output, .. } = command else {
+        unsafe {
+            unreachable_unchecked()
+            }
+        };
+
+    /* Pick a seed for the random number extension, and report it for reproducibility */
+    let seed = random
+        .then(|| {
+            let seed = seed.unwrap_or_else(clock_seed);
+
+            info!("Random seed: {seed}");
+
+            seed
+            });
+
+    /* Missing parts of the numeric formatting are left empty */
+    let bytes_of = |value: &Option<ByteString>| value.as_ref()
+        .map(|ByteString(bytes)| bytes.clone())
+        .unwrap_or_default();
+
+    /* Construct a builder for the given cell size, and pass the settings */
+    let interp_build = move |cell_size| {
+        /* 1-bit cells have no meaningful ASCII representation, so they're displayed as numbers by default */
+        let display_mode = display_mode
+            .unwrap_or(select!(cell_size == CellSize::U1, DisplayMode::Numeric, DisplayMode::ASCII));
+
+        let mut builder = Interpreter::builder()
+            .display_mode(display_mode)
+            .tape_kind(*tape)
+            .tapes(settings.tape_count(bounds.tapes))
+            .opt_level(*opt_level)
+            .eof(*eof)
+            .start_position(bounds.start_cell)
+            .fill(bounds.fill)
+            .pointer_overflow(bounds.pointer_overflow)
+            .cell_overflow(bounds.cell_overflow)
+            .numeric_format(NumericFormat {
+                separator: bytes_of(&formatting.numeric_separator),
+                prefix: bytes_of(&formatting.numeric_prefix),
+                suffix: bytes_of(&formatting.numeric_suffix)
+                })
+            .unbuffered(*flush_every_output)
+            .profile(*profile);
+
+        if let Some(tape_size) = bounds.tape_size {
+            builder = builder.tape_size(tape_size);
+            }
+        if let Some(seed) = seed {
+            builder = builder.seed(seed);
+            }
+        if let Some(max_output) = *max_output {
+            builder = builder.max_output(max_output);
+            }
+        if let Some(max_steps) = *max_steps {
+            builder = builder.max_steps(max_steps);
+            }
+        /* Literal, and file input is read byte by byte */
+        if let Some(ByteString(bytes)) = stdin.as_ref().or(input_data.as_ref()) {
+            builder = builder
+                .input(Box::new(Cursor::new(bytes.clone())))
+                .byte_input(true);
+            }
+
+        builder
+        };
+
+    (seed, interp_build)
+    }
+
+/* Function for getting a builder of the interactive commands' settings */
+fn interactive_builder(cell_size: CellSize, tape: TapeKind, display_mode: Option<DisplayMode>, seed: Option<u64>, stdin: Option<&ByteString>) -> InterpreterBuilder {
+    /* 1-bit cells have no meaningful ASCII representation, so they're displayed as numbers by default */
+    let display_mode = display_mode
+        .unwrap_or(select!(cell_size == CellSize::U1, DisplayMode::Numeric, DisplayMode::ASCII));
+
+    let mut builder = Interpreter::builder()
+        .display_mode(display_mode)
+        .tape_kind(tape);
 
-            info!("Compiled :P");
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+        }
+    /* Literal input is read byte by byte */
+    if let Some(ByteString(bytes)) = stdin {
+        builder = builder
+            .input(Box::new(Cursor::new(bytes.clone())))
+            .byte_input(true);
+        }
+
+    builder
+    }
+
+/* Function for reading a tape image - every cell as little-endian bytes of its width, with 1-bit cells taking a byte each, and arbitrary-precision ones eight */
+fn read_tape_image(path: &Path, cell_size: CellSize) -> DynResult<Vec<BigUint>> {
+    let bytes = read(path)?;
+    let width = match cell_size {
+        CellSize::U1 | CellSize::U8 | CellSize::I8 => 1,
+        CellSize::U16 | CellSize::I16 => 2,
+        CellSize::U32 | CellSize::I32 => 4,
+        CellSize::U64 => 8,
+        /* Arbitrary-precision cells are written as decimal lines, so they keep every digit */
+        CellSize::Big => return String::from_utf8(bytes)?
+            .lines()
+            .map(|line| line.parse()
+                .map_err(|_| anyhow!("Tape image has a line, which isn't a decimal cell: {line}"))
+                )
+            .collect()
+        };
+
+    if bytes.len() % width != 0 {
+        bail!("Tape image's length isn't a multiple of the cell's width: {width} byte(s)");
+        }
+
+    let cells = bytes.chunks_exact(width)
+        .map(BigUint::from_bytes_le)
+        .collect();
+
+    Ok(cells)
+    }
 
-            let buf = "nani ga tigaimasu yo".as_bytes();
+/* Function for constructing a fitting Interpreter, based on the pointer, and cell sizes - 1-bit cells are stored packed, and exposed as u8 */
+/* Pause flag, which gets raised once the time is up, or the run gets interrupted - pressing Ctrl-C again exits right away, as the program may be waiting for the input */
+fn pause_on_timeout(timeout: Option<Duration>) -> DynResult<(PauseFlag, Arc<AtomicBool>)> {
+    let pause = PauseFlag::default();
+    let timed_out = Arc::new(AtomicBool::new(false));
 
-            file.write_all(buf)?;
+    if let Some(timeout) = timeout {
+        let (pause, timed_out) = (pause.clone(), timed_out.clone());
+
+        spawn(move || {
+            sleep(timeout);
+            timed_out.store(true, Ordering::Relaxed);
+            pause.pause();
+            });
+        }
+
+    let handler_pause = pause.clone();
+    let mut interrupted = false;
+    set_handler(move || {
+        if interrupted {
+            exit(130);
+            }
+
+        interrupted = true;
+        handler_pause.pause();
+        })?;
+
+    Ok((pause, timed_out))
+    }
+
+/* Dump the cells into the error stream, and write the tape image - cells of several tapes are tagged with their tape's index, and the image only holds the tapes starting at the first cell, as it's read back from there */
+fn write_tape(interp: &dyn InterpRun, dump_tape: bool, tape_out: &Option<PathBuf>, tagged: bool, cell_names: &CellNames) -> DynResult<()> {
+    if dump_tape {
+        for (tape, index, value) in interp.all_cells() {
+            match tagged {
+                true => eprintln!("{tape}/{}: {value}", cell_names.label(index)),
+                false => eprintln!("{}: {value}", cell_names.label(index))
+                }
+            }
+        }
+    if let Some(path) = tape_out {
+        let (start, bytes) = interp.tape_image();
+        if start < 0 {
+            bail!("Tape reaches cell {start}, left of the first one, so it can't be written into a tape image - dump it with --dump-tape instead");
             }
+
+        File::create(path)?
+            .write_all(&bytes)?;
+
+        info!("Tape was written into: {}", path.display());
         }
 
     Ok(())
     }
 
+pub fn construct_interp(builder: InterpreterBuilder, pointer_size: DataSize, cell_size: CellSize) -> Result<Box<dyn InterpRun>, BuildError> {
+    let builder = builder
+        .bit_cells(cell_size == CellSize::U1);
+
+    Ok(match (pointer_size, cell_size) {
+        (DataSize::U8, CellSize::U1 | CellSize::U8) =>
+            Box::new(builder.try_build::<u8, u8>()?),
+        (DataSize::U8, CellSize::U16) =>
+            Box::new(builder.try_build::<u8, u16>()?),
+        (DataSize::U8, CellSize::U32) =>
+            Box::new(builder.try_build::<u8, u32>()?),
+        (DataSize::U8, CellSize::U64) =>
+            Box::new(builder.try_build::<u8, u64>()?),
+        (DataSize::U8, CellSize::Big) =>
+            Box::new(builder.try_build::<u8, BigUint>()?),
+        (DataSize::U8, CellSize::I8) =>
+            Box::new(builder.try_build::<u8, i8>()?),
+        (DataSize::U8, CellSize::I16) =>
+            Box::new(builder.try_build::<u8, i16>()?),
+        (DataSize::U8, CellSize::I32) =>
+            Box::new(builder.try_build::<u8, i32>()?),
+
+        (DataSize::U16, CellSize::U1 | CellSize::U8) =>
+            Box::new(builder.try_build::<u16, u8>()?),
+        (DataSize::U16, CellSize::U16) =>
+            Box::new(builder.try_build::<u16, u16>()?),
+        (DataSize::U16, CellSize::U32) =>
+            Box::new(builder.try_build::<u16, u32>()?),
+        (DataSize::U16, CellSize::U64) =>
+            Box::new(builder.try_build::<u16, u64>()?),
+        (DataSize::U16, CellSize::Big) =>
+            Box::new(builder.try_build::<u16, BigUint>()?),
+        (DataSize::U16, CellSize::I8) =>
+            Box::new(builder.try_build::<u16, i8>()?),
+        (DataSize::U16, CellSize::I16) =>
+            Box::new(builder.try_build::<u16, i16>()?),
+        (DataSize::U16, CellSize::I32) =>
+            Box::new(builder.try_build::<u16, i32>()?),
+
+        (DataSize::U32, CellSize::U1 | CellSize::U8) =>
+            Box::new(builder.try_build::<u32, u8>()?),
+        (DataSize::U32, CellSize::U16) =>
+            Box::new(builder.try_build::<u32, u16>()?),
+        (DataSize::U32, CellSize::U32) =>
+            Box::new(builder.try_build::<u32, u32>()?),
+        (DataSize::U32, CellSize::U64) =>
+            Box::new(builder.try_build::<u32, u64>()?),
+        (DataSize::U32, CellSize::Big) =>
+            Box::new(builder.try_build::<u32, BigUint>()?),
+        (DataSize::U32, CellSize::I8) =>
+            Box::new(builder.try_build::<u32, i8>()?),
+        (DataSize::U32, CellSize::I16) =>
+            Box::new(builder.try_build::<u32, i16>()?),
+        (DataSize::U32, CellSize::I32) =>
+            Box::new(builder.try_build::<u32, i32>()?),
+
+        (DataSize::U64, CellSize::U1 | CellSize::U8) =>
+            Box::new(builder.try_build::<u64, u8>()?),
+        (DataSize::U64, CellSize::U16) =>
+            Box::new(builder.try_build::<u64, u16>()?),
+        (DataSize::U64, CellSize::U32) =>
+            Box::new(builder.try_build::<u64, u32>()?),
+        (DataSize::U64, CellSize::U64) =>
+            Box::new(builder.try_build::<u64, u64>()?),
+        (DataSize::U64, CellSize::Big) =>
+            Box::new(builder.try_build::<u64, BigUint>()?),
+        (DataSize::U64, CellSize::I8) =>
+            Box::new(builder.try_build::<u64, i8>()?),
+        (DataSize::U64, CellSize::I16) =>
+            Box::new(builder.try_build::<u64, i16>()?),
+        (DataSize::U64, CellSize::I32) =>
+            Box::new(builder.try_build::<u64, i32>()?),
+        })
+    }
+
 
 /* Macro for cleaner if-else statements */
 #[macro_export]
@@ -0,0 +1,88 @@
+use {
+    anyhow::Result as DynResult,
+    clap::ValueEnum,
+    log::info,
+    std::io::{
+        stdin,
+        Cursor,
+        Read
+        },
+    crate::{
+        args::*,
+        warm::*,
+        construct_interp
+        },
+    braincooker::*
+    };
+
+
+/* Pointer sizes covered by the matrix - u32 pointers would allocate gigabytes for every run */
+const MATRIX_POINTER_SIZES: [DataSize; 2] = [DataSize::U8, DataSize::U16];
+
+
+/* Function for running the program under every pointer, and cell size combination, and grouping identical outputs */
+pub fn run_matrix<F>(instr: &InstructionSet, interp_build: F, warm_key: Option<u64>, literal: Option<&ByteString>) -> DynResult<()>
+where F: Fn(CellSize) -> InterpreterBuilder {
+    /* Use the literal input, or read the whole input once, so every run gets the identical script */
+    let script = match literal {
+        Some(ByteString(bytes)) =>
+            bytes.clone(),
+        None => {
+            let mut script = Vec::new();
+            stdin().read_to_end(&mut script)?;
+
+            script
+            }
+        };
+
+    /* Distinct outcomes, with combinations that produced them, in order of first appearance */
+    let mut outcomes: Vec<(String, Vec<String>)> = Vec::new();
+
+    for &pointer_size in MATRIX_POINTER_SIZES.iter() {
+        for &cell_size in CellSize::value_variants() {
+            let name = format!("{}/{}", value_name(&pointer_size), value_name(&cell_size));
+
+            info!("Running with pointer/cell size: {name}");
+
+            /* Run with a captured output, and fresh copy of the input */
+            let buffer = SharedBuffer::default();
+            let builder = interp_build(cell_size)
+                .output(Box::new(buffer.clone()))
+                .input(Box::new(Cursor::new(script.clone())));
+
+            let outcome = construct_interp(builder, pointer_size, cell_size)
+                .map_err(Into::into)
+                .and_then(|mut interp| match warm_key {
+                    Some(key) =>
+                        run_warm(&mut *interp, instr, cache_key((key, pointer_size as u8, cell_size as u8))),
+                    None =>
+                        Ok(interp.run(instr)?)
+                    });
+
+            /* Summarise the run, keeping any output written before a failure */
+            let output = format!("{:?}", String::from_utf8_lossy(&buffer.take()));
+            let summary = match outcome {
+                Ok(()) =>
+                    output,
+                Err(err) =>
+                    format!("{output} (error: {err})")
+                };
+
+            /* Group with an identical outcome, or start a new group */
+            match outcomes.iter_mut().find(|(other, _)| *other == summary) {
+                Some((_, names)) =>
+                    names.push(name),
+                None =>
+                    outcomes.push((summary, vec![name]))
+                }
+            }
+        }
+
+    /* Report the groups */
+    for (i, (summary, names)) in outcomes.iter().enumerate() {
+        println!("Outcome #{} - pointer/cell size: {}", i + 1, names.join(", "));
+        println!("    {summary}");
+        }
+
+    Ok(())
+    }
\ No newline at end of file
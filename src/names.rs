@@ -0,0 +1,229 @@
+use {
+    thiserror::Error,
+    std::collections::BTreeMap
+    };
+
+
+/* Prefix of a line binding a name to a cell */
+const ANNOTATION: &str = ";; name ";
+
+
+/* Annotations parsing's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum AnnotationError {
+    #[error("Malformed cell name annotation was found at line: {0}")]
+    Malformed(usize),
+    #[error("Cell name was bound more than once: {0}")]
+    DuplicateName(String),
+    #[error("Cell was named more than once: {0}")]
+    DuplicateCell(isize)
+    }
+
+/* Source code with its annotations taken out - their lines are left empty, so every instruction keeps its line, and column */
+#[derive(Default, PartialEq, Debug)]
+pub struct Annotated {
+    pub names: CellNames,
+    pub source: String,
+    /* Lines, which looked like annotations, but were malformed - they're left in the source as the plain comments */
+    pub malformed: Vec<AnnotationError>
+    }
+
+/* Container for human-readable names of cells, bound with `;; name <name> @<index>` annotations */
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct CellNames (
+    BTreeMap<isize, String>
+    );
+
+impl CellNames {
+    /* Function for collecting the annotations from the source code, and taking them out of it, so their characters never run as instructions */
+    pub fn parse(instr_str: &str) -> Result<Annotated, AnnotationError> {
+        let mut output = BTreeMap::new();
+        let mut source = String::with_capacity(instr_str.len());
+        let mut malformed = Vec::new();
+
+        for (i, line) in instr_str.split_inclusive('\n').enumerate() {
+            /* Keep lines without annotations, and the malformed ones, which are just comments - lines are counted from one */
+            let Some((name, index)) = Self::annotation(line) else {
+                if line.trim_start().starts_with(ANNOTATION) {
+                    malformed.push(AnnotationError::Malformed(i + 1));
+                    }
+
+                source.push_str(line);
+                continue;
+                };
+
+            /* Check for repeated bindings */
+            if output.values().any(|other| other == name) {
+                return Err(AnnotationError::DuplicateName(name.to_owned()));
+                }
+            if output.insert(index, name.to_owned()).is_some() {
+                return Err(AnnotationError::DuplicateCell(index));
+                }
+
+            /* Leave only the line break */
+            source.push_str(&line[line.trim_end_matches(['\r', '\n']).len() ..]);
+            }
+
+        Ok(Annotated {
+            names: Self(output),
+            source,
+            malformed
+            })
+        }
+
+    /* Function for splitting the annotation into the name, and index - none for the other lines */
+    fn annotation(line: &str) -> Option<(&str, isize)> {
+        let annotation = line.trim_start()
+            .strip_prefix(ANNOTATION)?;
+
+        /* Split into the name, and '@' prefixed index */
+        let [name, index] = annotation.split_whitespace().collect::<Vec<_>>()[..] else {
+            return None;
+            };
+
+        /* Names are limited to characters, which can't be mistaken for instructions */
+        if ! name.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        || name.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+            }
+
+        index.strip_prefix('@')?
+            .parse()
+            .ok()
+            .map(|index| (name, index))
+        }
+
+    /* Get whether is empty */
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+        }
+
+    /* Get the name of a cell */
+    pub fn name(&self, index: isize) -> Option<&str> {
+        self.0.get(&index)
+            .map(String::as_str)
+        }
+
+    /* Get the index of a named cell */
+    pub fn index(&self, name: &str) -> Option<isize> {
+        self.0.iter()
+            .find(|(_, other)| *other == name)
+            .map(|(&index, _)| index)
+        }
+
+    /* Resolve a reference to a cell - either a name, or an index with an optional '@' prefix */
+    pub fn resolve(&self, reference: &str) -> Option<isize> {
+        let reference = reference.trim();
+
+        self.index(reference)
+            .or_else(|| reference.strip_prefix('@')
+                .unwrap_or(reference)
+                .parse()
+                .ok()
+                )
+        }
+
+    /* Get a label for displaying a cell - its name, and index if named */
+    pub fn label(&self, index: isize) -> String {
+        match self.name(index) {
+            Some(name) => format!("{name} (@{index})"),
+            None => format!("@{index}")
+            }
+        }
+
+    /* Iterate over the named cells, in order of their indices */
+    pub fn iter(&self) -> impl Iterator<Item = (isize, &str)> {
+        self.0.iter()
+            .map(|(&index, name)| (index, name.as_str()))
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        eval::eval_instr,
+        names::*
+        };
+
+    #[test]
+    fn names_basic() {
+        let names = CellNames::parse(
+";; name counter @5
+++++[>+<-]
+    ;; name result @0
+;; not an annotation @1")
+            .expect("Unreachable")
+            .names;
+
+        assert_eq!(names.name(5), Some("counter"));
+        assert_eq!(names.name(0), Some("result"));
+        assert_eq!(names.name(1), None);
+        assert_eq!(names.index("counter"), Some(5));
+        assert_eq!(names.iter().collect::<Vec<_>>(), vec![(0, "result"), (5, "counter")]);
+        }
+
+    #[test]
+    fn names_negative() {
+        let Annotated { names, source, .. } = CellNames::parse(";; name scratch @-3\n+++.")
+            .expect("Unreachable");
+
+        /* The annotation's '-' doesn't run */
+        let instr = eval_instr(&source)
+            .expect("Unreachable");
+
+        assert_eq!(names.index("scratch"), Some(-3));
+        assert_eq!(source, "\n+++.");
+        assert_eq!(instr.to_source(), "+++.");
+        }
+
+    #[test]
+    fn names_resolve() {
+        let names = CellNames::parse(";; name counter @5")
+            .expect("Unreachable")
+            .names;
+
+        assert_eq!(names.resolve("counter"), Some(5));
+        assert_eq!(names.resolve(" counter "), Some(5));
+        assert_eq!(names.resolve("@7"), Some(7));
+        assert_eq!(names.resolve("7"), Some(7));
+        assert_eq!(names.resolve("missing"), None);
+        }
+
+    #[test]
+    fn names_label() {
+        let names = CellNames::parse(";; name counter @5")
+            .expect("Unreachable")
+            .names;
+
+        assert_eq!(names.label(5), "counter (@5)");
+        assert_eq!(names.label(6), "@6");
+        }
+
+    #[test]
+    fn names_malformed() {
+        let malformed = |instr_str| CellNames::parse(instr_str)
+            .expect("Unreachable");
+
+        assert_eq!(malformed(";; name counter").malformed, [AnnotationError::Malformed(1)]);
+        assert_eq!(malformed("\n;; name counter 5").malformed, [AnnotationError::Malformed(2)]);
+        assert_eq!(malformed(";; name 12 @5").malformed, [AnnotationError::Malformed(1)]);
+        assert_eq!(malformed(";; name counter @five").malformed, [AnnotationError::Malformed(1)]);
+        assert_eq!(malformed(";; name counter @5 extra").malformed, [AnnotationError::Malformed(1)]);
+
+        /* Malformed ones stay in the source as the plain comments */
+        let annotated = malformed(";; name my-counter @5");
+        assert!(annotated.names.is_empty());
+        assert_eq!(annotated.source, ";; name my-counter @5");
+        }
+
+    #[test]
+    fn names_duplicates() {
+        let name = CellNames::parse(";; name counter @5\n;; name counter @6");
+        let cell = CellNames::parse(";; name counter @5\n;; name other @5");
+
+        assert_eq!(name, Err(AnnotationError::DuplicateName("counter".to_owned())));
+        assert_eq!(cell, Err(AnnotationError::DuplicateCell(5)));
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use {
+    alloc::vec::Vec,
+    core::hint::unreachable_unchecked,
+    crate::{
+        eval::*,
+        utils::CommentMode
+        }
+    };
+
+
+/* Function for evaluation of the Ook! source code - its words go in pairs, each one standing for a single instruction, and the positions are the pairs' first words */
+pub fn eval_ook(instr_str: &str, comments: CommentMode) -> Result<(InstructionSet, SourceMap), EvalError> {
+    let chars: Vec<char> = instr_str.chars()
+        .collect();
+
+    /* Find the words, with their punctuation - anything else is a comment, or the whitespace between them */
+    let mut words = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i ..] {
+            ['O', 'o', 'k', mark @ ('.' | '?' | '!'), ..] => {
+                words.push((i, mark));
+                i += 4;
+                },
+            _ => {
+                let chr = chars[i];
+                match comments {
+                    CommentMode::Any => (),
+                    _ if chr.is_whitespace() => (),
+                    _ => return Err(EvalError::UnknownCharacter(chr, i))
+                    };
+                i += 1;
+                }
+            }
+        }
+
+    let tokens = words.chunks(2)
+        .map(|pair| match *pair {
+            [(i, first), (_, second)] => match (first, second) {
+                ('.', '?') => Ok((i, Instruction::Right)),
+                ('?', '.') => Ok((i, Instruction::Left)),
+                ('.', '.') => Ok((i, Instruction::Increment)),
+                ('!', '!') => Ok((i, Instruction::Decrement)),
+                ('!', '?') => Ok((i, Instruction::LoopOpen)),
+                ('?', '!') => Ok((i, Instruction::LoopClose)),
+                ('!', '.') => Ok((i, Instruction::Output)),
+                ('.', '!') => Ok((i, Instruction::Input)),
+                _ => Err(EvalError::UnknownOok(i))
+                },
+            /* Last word without its pair */
+            [(i, _)] => Err(EvalError::UnknownOok(i)),
+            /* Unsafe note - it is safe, because the chunks have either one, or two words */
+            _ => unsafe {
+                unreachable_unchecked()
+                }
+            });
+
+    collect_instr(tokens, words.len() / 2)
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        eval::eval_instr,
+        ook::*
+        };
+
+    #[test]
+    fn ook_basic() {
+        let (instr, map) = eval_ook("Ook. Ook. Ook! Ook?\nOok! Ook! Ook? Ook! Ook! Ook.", CommentMode::Any)
+            .expect("Unreachable");
+
+        assert_eq!(Ok(instr), eval_instr("+[-]."));
+        assert_eq!(map.offset(1), Some(10));
+        }
+
+    #[test]
+    fn ook_errors() {
+        assert_eq!(eval_ook("Ook? Ook? Ook. Ook.", CommentMode::Any), Err(EvalError::UnknownOok(0)));
+        assert_eq!(eval_ook("Ook. Ook. Ook.", CommentMode::Any), Err(EvalError::UnknownOok(10)));
+        assert_eq!(eval_ook("Ook! Ook?", CommentMode::Any), Err(EvalError::UnclosedBracket(1)));
+        assert_eq!(eval_ook("Ook. Ook. banana", CommentMode::Whitespace), Err(EvalError::UnknownCharacter('b', 10)));
+        }
+    }
@@ -0,0 +1,222 @@
+use crate::{
+    eval::InstructionSet,
+    ir::Ir,
+    utils::OptLevel
+    };
+
+
+/* Trait for a single optimisation of the operations - it has to keep the instructions' locations in order, so the executed ones stay counted */
+pub trait OptimizerPass {
+    fn name(&self) -> &'static str;
+    fn run(&self, ir: &mut Ir);
+    }
+
+/* Changes, and moves cancelling out, such as `+-`, or `><`, are dropped, and the runs around them merged */
+pub struct CancelRuns;
+
+impl OptimizerPass for CancelRuns {
+    fn name(&self) -> &'static str {
+        "cancel-runs"
+        }
+
+    fn run(&self, ir: &mut Ir) {
+        ir.cancel_runs();
+        }
+    }
+
+/* Clearing loops, such as `[-]`, become a single assignment */
+pub struct ClearLoops;
+
+impl OptimizerPass for ClearLoops {
+    fn name(&self) -> &'static str {
+        "clear-loops"
+        }
+
+    fn run(&self, ir: &mut Ir) {
+        ir.clear_loops();
+        }
+    }
+
+/* Copying loops, such as `[->+>+++<<]`, become multiplications done in a single step */
+pub struct MultiplyLoops;
+
+impl OptimizerPass for MultiplyLoops {
+    fn name(&self) -> &'static str {
+        "multiply-loops"
+        }
+
+    fn run(&self, ir: &mut Ir) {
+        ir.multiply_loops();
+        }
+    }
+
+/* Pointer moves in the straight-line code are deferred, with the cells addressed by their offsets */
+pub struct DeferMoves;
+
+impl OptimizerPass for DeferMoves {
+    fn name(&self) -> &'static str {
+        "defer-moves"
+        }
+
+    fn run(&self, ir: &mut Ir) {
+        ir.defer_moves();
+        }
+    }
+
+
+/* Pass manager, running the passes in the order they were added */
+pub struct Optimizer {
+    passes: Vec<Box<dyn OptimizerPass>>
+    }
+
+impl Default for Optimizer {
+    /* Every built-in pass, in the order they work best in */
+    fn default() -> Self {
+        Optimizer::new()
+            .pass(CancelRuns)
+            .pass(ClearLoops)
+            .pass(MultiplyLoops)
+            .pass(DeferMoves)
+        }
+    }
+
+impl Optimizer {
+    /* Constructor function - with no passes */
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            passes: Vec::new()
+            }
+        }
+
+    /* Constructor function - with the passes of the optimisation level, the lowest ones only fusing the runs */
+    pub fn for_level(level: OptLevel) -> Self {
+        match level {
+            OptLevel::O0 | OptLevel::O1 => Optimizer::new(),
+            OptLevel::O2 => Optimizer::new()
+                .pass(CancelRuns)
+                .pass(ClearLoops)
+                .pass(MultiplyLoops),
+            OptLevel::O3 => Optimizer::default()
+            }
+        }
+
+    /* Constructor function - with the passes of the optimisation level, which keep the arbitrary-precision cells' stop at zero, so nothing gets cancelled, nor cleared */
+    pub fn for_unbounded(level: OptLevel) -> Self {
+        match level {
+            OptLevel::O0 | OptLevel::O1 => Optimizer::new(),
+            OptLevel::O2 => Optimizer::new()
+                .pass(MultiplyLoops),
+            OptLevel::O3 => Optimizer::new()
+                .pass(MultiplyLoops)
+                .pass(DeferMoves)
+            }
+        }
+
+    /* Add the pass after the ones added so far */
+    pub fn pass<P>(mut self, pass: P) -> Self
+    where P: OptimizerPass + 'static {
+        self.passes.push(Box::new(pass));
+        self
+        }
+
+    /* Get the names of the passes, in order */
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.passes.iter()
+            .map(|pass| pass.name())
+        }
+
+    /* Run every pass over the operations */
+    pub fn run(&self, ir: &mut Ir) {
+        for pass in &self.passes {
+            pass.run(ir);
+            }
+        }
+
+    /* Lower the instructions with the runs fused, and run every pass over them */
+    pub fn lower(&self, instr: &InstructionSet) -> Ir {
+        let mut ir = Ir::lower_fused(instr);
+        self.run(&mut ir);
+
+        ir
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        eval::eval_instr,
+        ir::IrOp,
+        optimizer::*
+        };
+
+    /* Pass dropping every output, as a user-defined one */
+    struct DropOutput;
+
+    impl OptimizerPass for DropOutput {
+        fn name(&self) -> &'static str {
+            "drop-output"
+            }
+
+        fn run(&self, ir: &mut Ir) {
+            let (ops, sources) = ir.clone()
+                .into_parts();
+            let (ops, mut kept): (Vec<_>, Vec<_>) = ops.into_iter()
+                .zip(sources.iter().copied())
+                .filter(|&(op, _)| op != IrOp::Output)
+                .unzip();
+            kept.push(sources[sources.len() - 1]);
+
+            *ir = Ir::from_parts(ops, kept)
+                .expect("Unreachable");
+            }
+        }
+
+    #[test]
+    fn optimizer_default() {
+        let instr = eval_instr("+[-]>++[->+<].")
+            .expect("Unreachable");
+
+        assert_eq!(Optimizer::default().lower(&instr), Ir::lower(&instr));
+        assert_eq!(Optimizer::default().names().collect::<Vec<_>>(), vec!["cancel-runs", "clear-loops", "multiply-loops", "defer-moves"]);
+        }
+
+    #[test]
+    fn optimizer_selected() {
+        let instr = eval_instr("+[-]>++[->+<].")
+            .expect("Unreachable");
+
+        let ir = Optimizer::new()
+            .pass(ClearLoops)
+            .pass(DropOutput)
+            .lower(&instr);
+
+        assert_eq!(ir.ops(), &[
+            IrOp::Add(1),
+            IrOp::Set(0),
+            IrOp::Move(1),
+            IrOp::Add(2),
+            IrOp::LoopStart(9),
+            IrOp::Add(-1),
+            IrOp::Move(1),
+            IrOp::Add(1),
+            IrOp::Move(-1),
+            IrOp::LoopEnd(4)
+            ]);
+        assert_eq!(ir.source(9), 12);
+        assert_eq!(ir.span(9), 2);
+        }
+
+    #[test]
+    fn optimizer_levels() {
+        let instr = eval_instr("+[-]>++[->+<].")
+            .expect("Unreachable");
+
+        assert_eq!(Optimizer::for_level(OptLevel::O3).lower(&instr), Ir::lower(&instr));
+        assert_eq!(Optimizer::for_level(OptLevel::O2).names().collect::<Vec<_>>(), vec!["cancel-runs", "clear-loops", "multiply-loops"]);
+        assert_eq!(Optimizer::for_level(OptLevel::O1).names().count(), 0);
+        assert_eq!(Ir::lower_at(&instr, OptLevel::O0).len(), instr.len());
+        assert_eq!(Ir::lower_at(&instr, OptLevel::O1), Ir::lower_fused(&instr));
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,165 @@
+use {
+    anyhow::Result as DynResult,
+    clap::Parser,
+    log::info,
+    std::{
+        fs::{
+            read,
+            write
+            },
+        path::Path
+        },
+    core::hint::unreachable_unchecked,
+    crate::args::*,
+    braincooker::*
+    };
+
+
+/* Function for packing the program, and the settings of the interpreter command into a bundle file */
+pub fn pack_bundle(command: &CMD, instr: &InstructionSet, path: &Path) -> DynResult<()> {
+    /* Unsafe note - it is safe, because it's only called for the pack command */
+    let CMD::Pack { settings, pointer_size, cell_size, tape, tape_size, start_cell, fill, pointer_overflow, cell_overflow, display_mode, seed, stdin, eof, max_output, max_steps, meta, .. } = command else {
+        unsafe {
+            unreachable_unchecked()
+            }
+        };
+
+    /* Settings are stored as options of the interpreter command */
+    let mut options = vec![
+        ("pointer-size", value_name(pointer_size)),
+        ("cell-size", value_name(cell_size)),
+        ("tape", value_name(tape)),
+        ("pointer-overflow", value_name(pointer_overflow)),
+        ("cell-overflow", value_name(cell_overflow)),
+        ("eof", value_name(eof))
+        ];
+
+    /* Starting state of the tape is only stored, when it differs from the default */
+    if let Some(tape_size) = tape_size {
+        options.push(("tape-size", tape_size.to_string()));
+        }
+    if *start_cell != 0 {
+        options.push(("start-cell", start_cell.to_string()));
+        }
+    if *fill != 0 {
+        options.push(("fill", fill.to_string()));
+        }
+    if let Some(display_mode) = display_mode {
+        options.push(("display-mode", value_name(display_mode)));
+        }
+    for dialect in settings.dialect.iter() {
+        options.push(("dialect", value_name(dialect)));
+        }
+    if let Some(seed) = seed {
+        options.push(("seed", seed.to_string()));
+        }
+    if let Some(max_output) = max_output {
+        options.push(("max-output", max_output.to_string()));
+        }
+    if let Some(max_steps) = max_steps {
+        options.push(("max-steps", max_steps.to_string()));
+        }
+
+    let bundle = Bundle {
+        /* Comment loops were already pruned, so the program is stored as it will be run */
+        program: instr.to_source(),
+        settings: options.into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect(),
+        input: stdin.as_ref()
+            .map(|ByteString(bytes)| bytes.clone()),
+        metadata: meta.clone()
+        };
+
+    write(path, bundle.encode())?;
+
+    info!("Packed the bundle: {}", path.display());
+
+    Ok(())
+    }
+
+/* Function for turning a bundle file back into the interpreter command, with the settings it was packed with */
+pub fn unpack_bundle(path: &Path, settings: &Settings) -> DynResult<CMD> {
+    let bundle = Bundle::decode(&read(path)?)?;
+
+    let mut argv = vec!["braincooker".to_owned(), "interp".to_owned()];
+
+    for (key, value) in bundle.settings {
+        argv.push(format!("--{key}"));
+        argv.push(value);
+        }
+    if let Some(input) = bundle.input {
+        argv.push("--stdin".to_owned());
+        argv.push(input.escape_ascii().to_string());
+        }
+
+    /* General settings of the run are passed along */
+    if settings.debug_display {
+        argv.push("--debug-display".to_owned());
+        }
+    if settings.quiet {
+        argv.push("--quiet".to_owned());
+        }
+    if let Some(loop_prune) = &settings.loop_prune {
+        argv.push("--loop-prune".to_owned());
+        argv.push(value_name(loop_prune));
+        }
+    for dialect in settings.dialect.iter() {
+        argv.push("--dialect".to_owned());
+        argv.push(value_name(dialect));
+        }
+
+    /* Program goes last, after the separator, so it can't be mistaken for an option */
+    argv.push("--".to_owned());
+    argv.push(bundle.program);
+
+    /* Metadata is only informative - the logger isn't running yet */
+    if settings.debug_display {
+        for (key, value) in bundle.metadata {
+            eprintln!("Bundle {key}: {value}");
+            }
+        }
+
+    Ok(Args::try_parse_from(argv)?.command)
+    }
+
+#[cfg(test)]
+mod test {
+    use {
+        std::{
+            env::temp_dir,
+            fs::remove_file,
+            process::id
+            },
+        crate::pack::*
+        };
+
+    #[test]
+    fn pack_roundtrip() {
+        let path = temp_dir()
+            .join(format!("braincooker-pack-{}.bcb", id()));
+        let Args { command, .. } = Args::try_parse_from([
+            "braincooker", "pack", "-o", "unused",
+            "--tape", "growable", "--tape-size", "300", "--start-cell", "5", "--fill", "-1",
+            "--pointer-overflow", "error", "--cell-overflow", "saturate", "--", "+."
+            ])
+            .expect("Unreachable");
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+
+        pack_bundle(&command, &instr, &path)
+            .expect("Unreachable");
+        let unpacked = unpack_bundle(&path, command.get_settings());
+        remove_file(&path)
+            .expect("Unreachable");
+
+        let Ok(CMD::Interp { tape, bounds, .. }) = unpacked else {
+            panic!("Bundle wasn't unpacked into the interpreter command");
+            };
+
+        assert!(tape == TapeKind::Growable);
+        assert_eq!((bounds.tape_size, bounds.start_cell, bounds.fill), (Some(300), 5, -1));
+        assert!(bounds.pointer_overflow == PointerOverflow::Error);
+        assert!(bounds.cell_overflow == CellOverflow::Saturate);
+        }
+    }
@@ -0,0 +1,69 @@
+use {
+    anyhow::{
+        anyhow,
+        Result as DynResult
+        },
+    log::info,
+    std::{
+        fs::read_to_string,
+        io::{
+            empty,
+            pipe,
+            stdin,
+            stdout,
+            Read,
+            Write
+            },
+        path::PathBuf,
+        thread::spawn
+        },
+    core::mem::replace,
+    crate::{
+        args::Settings,
+        construct_interp
+        },
+    braincooker::*
+    };
+
+
+/* Function for running the programs at once, with the output of each one connected to the input of the next - the first one reads the standard input, and the last one writes the standard output */
+pub fn run_pipeline(programs: &[PathBuf], builder: impl Fn() -> InterpreterBuilder, pointer_size: DataSize, cell_size: CellSize, settings: &Settings) -> DynResult<()> {
+    /* Parse every program, before any of them starts */
+    let instrs = programs.iter()
+        .map(|path| Ok(settings.eval(&read_to_string(path)?)?.0))
+        .collect::<DynResult<Vec<_>>>()?;
+
+    let mut input: Box<dyn Read + Send> = Box::new(stdin());
+    let mut runs = Vec::with_capacity(instrs.len());
+
+    for (i, instr) in instrs.into_iter().enumerate() {
+        /* Closing the writer, once the program ends, is the end of input for the next one */
+        let (next_input, output): (Box<dyn Read + Send>, Box<dyn Write + Send>) = match i + 1 == programs.len() {
+            true => (Box::new(empty()), Box::new(stdout())),
+            false => {
+                let (reader, writer) = pipe()?;
+
+                (Box::new(reader), Box::new(writer))
+                }
+            };
+
+        let builder = builder()
+            .byte_input(true)
+            .input(replace(&mut input, next_input))
+            .output(output);
+        let mut interp = construct_interp(builder, pointer_size, cell_size)?;
+
+        runs.push(spawn(move || interp.run(&instr)));
+        }
+
+    /* Report the first program, which failed */
+    for (run, path) in runs.into_iter().zip(programs) {
+        run.join()
+            .map_err(|_| anyhow!("Program panicked: {}", path.display()))?
+            .map_err(|err| anyhow!("Program failed: {} - {err}", path.display()))?;
+        }
+
+    info!("{} program(s) were run", programs.len());
+
+    Ok(())
+    }
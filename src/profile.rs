@@ -0,0 +1,139 @@
+use {
+    core::cmp::Reverse,
+    crate::{
+        eval::*,
+        ir::*
+        }
+    };
+
+
+/* Longest excerpt of a loop's source code in the report */
+const EXCERPT_LEN: usize = 40;
+
+
+/* Execution counts of a single loop - locations are instruction indices */
+#[derive(Clone, PartialEq, Debug)]
+pub struct LoopProfile {
+    pub start: usize,
+    pub end: usize,
+    /* Number of times the loop was reached */
+    pub entries: u64,
+    /* Number of times its body was run */
+    pub iterations: u64
+    }
+
+/* Execution counts of the program's instructions, and loops */
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Profile {
+    counts: Vec<u64>,
+    loops: Vec<LoopProfile>
+    }
+
+impl Profile {
+    /* Constructor function - from the number of times every operation was executed, spread over the instructions they came from */
+    pub(crate) fn new(ir: &Ir, op_counts: &[u64]) -> Self {
+        let mut counts = vec![0; ir.source(ir.len())];
+        let mut loops = Vec::new();
+
+        for (i, &count) in op_counts.iter().enumerate() {
+            counts[ir.source(i) .. ir.source(i + 1)].fill(count);
+
+            /* Every execution of the loop's end closes an iteration */
+            if let IrOp::LoopStart(end) = ir[i] {
+                loops.push(LoopProfile {
+                    start: ir.source(i),
+                    end: ir.source(end),
+                    entries: count,
+                    iterations: op_counts[end]
+                    });
+                }
+            }
+
+        Self { counts, loops }
+        }
+
+    /* Get the number of times every instruction was executed, by its location */
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+        }
+
+    /* Get the loops, ordered by their starts */
+    pub fn loops(&self) -> &[LoopProfile] {
+        &self.loops
+        }
+
+    /* Get a report of the hottest loops, and instructions - at most the limit of each, with the loops' source code */
+    pub fn report(&self, instr: &InstructionSet, limit: usize) -> String {
+        let mut output = String::new();
+
+        let mut loops: Vec<&LoopProfile> = self.loops.iter()
+            .filter(|info| info.iterations > 0)
+            .collect();
+        loops.sort_by_key(|info| Reverse(info.iterations));
+
+        output.push_str("Hot loops:\n");
+        for (rank, info) in loops.iter().take(limit).enumerate() {
+            let excerpt: String = (info.start ..= info.end)
+                .map(|i| instr[i].symbol())
+                .take(EXCERPT_LEN)
+                .collect();
+            let ellipsis = select_ellipsis(info.end - info.start + 1);
+
+            output.push_str(&format!("  #{:<3} {}..={}  {} iteration(s), entered {} time(s)  {excerpt}{ellipsis}\n",
+                rank + 1, info.start, info.end, info.iterations, info.entries));
+            }
+
+        let mut hottest: Vec<(usize, u64)> = self.counts.iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        hottest.sort_by_key(|&(i, count)| (Reverse(count), i));
+
+        output.push_str("Hot instructions:\n");
+        for (rank, (i, count)) in hottest.into_iter().take(limit).enumerate() {
+            output.push_str(&format!("  #{:<3} {i}  '{}'  {count}\n", rank + 1, instr[i].symbol()));
+            }
+
+        output
+        }
+    }
+
+/* Helper function, for marking the excerpts cut short */
+const fn select_ellipsis(len: usize) -> &'static str {
+    match len > EXCERPT_LEN {
+        true => "...",
+        false => ""
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::profile::*;
+
+    #[test]
+    fn profile_counts() {
+        let instr = eval_instr("+++[->+<]")
+            .expect("Unreachable");
+        let ir = Ir::lower_fused(&instr);
+        /* Add, loop's start, and its body run three times */
+        let profile = Profile::new(&ir, &[1, 1, 3, 3, 3, 3, 3]);
+
+        assert_eq!(profile.counts(), &[1, 1, 1, 1, 3, 3, 3, 3, 3]);
+        assert_eq!(profile.loops(), &[LoopProfile { start: 3, end: 8, entries: 1, iterations: 3 }]);
+        }
+
+    #[test]
+    fn profile_report() {
+        let instr = eval_instr("+++[->+<]")
+            .expect("Unreachable");
+        let ir = Ir::lower_fused(&instr);
+        let report = Profile::new(&ir, &[1, 1, 3, 3, 3, 3, 3])
+            .report(&instr, 2);
+
+        assert!(report.contains("#1   3..=8  3 iteration(s), entered 1 time(s)  [->+<]\n"));
+        assert!(report.contains("#1   4  '-'  3\n"));
+        assert!(! report.contains("#3"));
+        }
+    }
\ No newline at end of file
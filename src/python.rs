@@ -0,0 +1,76 @@
+use {
+    pyo3::{
+        exceptions::{
+            PyRuntimeError,
+            PyValueError
+            },
+        prelude::*
+        },
+    crate::{
+        eval::eval_instr,
+        interp::*,
+        ir::Ir,
+        utils::{
+            DisplayMode,
+            EofMode,
+            OptLevel
+            }
+        }
+    };
+
+
+/* Function for parsing the source code, and getting its instructions back, without the comments */
+#[pyfunction]
+#[pyo3(name = "eval_instr")]
+fn eval(code: &str) -> PyResult<String> {
+    eval_instr(code)
+        .map(|instr| instr.to_source())
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+/* Function for getting the optimised operations of the source code */
+#[pyfunction]
+#[pyo3(signature = (code, level = 3))]
+fn optimize(code: &str, level: u8) -> PyResult<Vec<String>> {
+    let instr = eval_instr(code)
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    Ok(Ir::lower_at(&instr, OptLevel::from_number(level))
+        .ops()
+        .iter()
+        .map(|op| format!("{op:?}"))
+        .collect())
+    }
+
+/* Function for running the source code on the given input, with byte cells, and the end of input read as zero - the written bytes come back as the string */
+#[pyfunction]
+#[pyo3(signature = (code, input = "", max_steps = None))]
+fn run(code: &str, input: &str, max_steps: Option<u64>) -> PyResult<String> {
+    let instr = eval_instr(code)
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    let mut builder = Interpreter::builder()
+        .display_mode(DisplayMode::Raw)
+        .byte_input(true)
+        .eof(EofMode::Zero);
+    if let Some(steps) = max_steps {
+        builder = builder.max_steps(steps);
+        }
+
+    let output = builder.try_build::<u16, u8>()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?
+        .run_with_io(&instr, input.as_bytes())
+        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+
+    Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+/* Python module declaration */
+#[pymodule]
+fn braincooker(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(eval, module)?)?;
+    module.add_function(wrap_pyfunction!(optimize, module)?)?;
+    module.add_function(wrap_pyfunction!(run, module)?)?;
+
+    Ok(())
+    }
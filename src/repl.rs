@@ -0,0 +1,131 @@
+use {
+    anyhow::Result as DynResult,
+    clap::ValueEnum,
+    std::io::{
+        stdin,
+        stdout,
+        BufRead,
+        Write
+        },
+    braincooker::*,
+    crate::args::value_name
+    };
+
+
+/* Number of cells shown on each side of the pointer, when no range was given */
+const TAPE_RADIUS: isize = 5;
+
+const HELP: &str = "\
+Lines of code are run one after another, on the same tape - loops can span multiple lines
+Commands:
+  :tape [FROM TO]          print the cells in the range [default: around the pointer]
+  :reset                   zero the tape, and move the pointer back to the start
+  :display ascii|numeric   change the way of displaying the cells
+  :help                    print this message
+  :quit                    leave the REPL";
+
+
+/* Function for running the entered lines one by one, with the state kept between them */
+pub fn run_repl(mut interp: Box<dyn InterpRun>, dialect: &[Dialect]) -> DynResult<()> {
+    let mut line = String::new();
+    /* Code entered so far, while its loops are still open */
+    let mut pending = String::new();
+
+    println!("Type code to run it, or `:help` for the commands");
+
+    loop {
+        print!("{}", select_prompt(pending.is_empty()));
+        stdout().flush()?;
+
+        /* End of the input leaves the REPL */
+        line.clear();
+        if stdin().lock().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+            }
+
+        /* Meta-commands are only recognised at the start of a snippet */
+        if pending.is_empty() && let Some(command) = line.trim().strip_prefix(':') {
+            match run_command(&mut *interp, command) {
+                true => continue,
+                false => return Ok(())
+                }
+            }
+
+        pending.push_str(&line);
+
+        match eval_instr_with(&pending, dialect) {
+            /* Keep reading, until the loops, and the procedures get closed */
+            Err(EvalError::UnclosedBracket(_) | EvalError::UnclosedProcedure(_)) =>
+                continue,
+            Err(err) =>
+                println!("error: {err}"),
+            Ok(instr) => if let Err(err) = interp.run(&instr) {
+                println!("error: {err}");
+                }
+            }
+
+        pending.clear();
+        }
+    }
+
+/* Function for executing a meta-command - false, when the REPL should be left */
+fn run_command(interp: &mut dyn InterpRun, command: &str) -> bool {
+    let words: Vec<&str> = command.split_whitespace()
+        .collect();
+
+    match words.as_slice() {
+        ["tape"] => {
+            let position = interp.position();
+            print_cells(interp, position - TAPE_RADIUS, position + TAPE_RADIUS);
+            },
+        ["tape", from, to] => match (from.parse(), to.parse()) {
+            (Ok(from), Ok(to)) => print_cells(interp, from, to),
+            _ => println!("error: Invalid range: {from} {to}")
+            },
+        ["reset"] => {
+            interp.reset();
+            println!("Tape was reset");
+            },
+        ["display", mode] => match DisplayMode::from_str(mode, true) {
+            Ok(mode) => match interp.set_display_mode(mode) {
+                Ok(()) => println!("Display mode: {}", value_name(&mode)),
+                Err(err) => println!("error: {err}")
+                },
+            Err(err) => println!("error: {err}")
+            },
+        ["help"] =>
+            println!("{HELP}"),
+        ["quit"] =>
+            return false,
+        _ =>
+            println!("error: Unknown command, or wrong arguments: :{command}")
+        }
+
+    true
+    }
+
+/* Function for printing the cells in the range, with the pointer marked */
+fn print_cells(interp: &mut dyn InterpRun, from: isize, to: isize) {
+    let position = interp.position();
+
+    for index in from ..= to {
+        let marker = match index == position {
+            true => ">",
+            false => " "
+            };
+
+        /* Cells off the tape are skipped */
+        if let Some(value) = interp.peek(index) {
+            println!("{marker} {index:>6}: {value}");
+            }
+        }
+    }
+
+/* Helper function, for the prompt of a new snippet, or its continuation */
+const fn select_prompt(is_new: bool) -> &'static str {
+    match is_new {
+        true => "bf> ",
+        false => "... "
+        }
+    }
\ No newline at end of file
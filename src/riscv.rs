@@ -0,0 +1,634 @@
+use crate::{
+    codegen::*,
+    elf::*,
+    eval::*,
+    ir::*,
+    utils::*
+    };
+
+
+/* Registers with a fixed purpose - tape's address, pointer, output buffer's address, and its length */
+const TAPE: u32 = 9;
+const INDEX: u32 = 18;
+const BUFFER: u32 = 19;
+const LENGTH: u32 = 20;
+/* Scratch registers, for the cell's value, constants, addresses, products, and the index of a cell at an offset */
+const VALUE: u32 = 5;
+const CONSTANT: u32 = 6;
+const ADDRESS: u32 = 7;
+const PRODUCT: u32 = 13;
+const OFFSET: u32 = 14;
+/* Registers of the calling convention */
+const ZERO: u32 = 0;
+const RA: u32 = 1;
+const SP: u32 = 2;
+const A0: u32 = 10;
+const A1: u32 = 11;
+const A2: u32 = 12;
+const A7: u32 = 17;
+/* Registers of the runtime routines, not touched by `put_byte`, and `flush` */
+const T3: u32 = 28;
+const T4: u32 = 29;
+const T5: u32 = 30;
+const T6: u32 = 31;
+
+/* Major opcodes */
+const LOAD: u32 = 0x03;
+const OP_IMM: u32 = 0x13;
+const AUIPC: u32 = 0x17;
+const OP_IMM_32: u32 = 0x1B;
+const STORE: u32 = 0x23;
+const OP: u32 = 0x33;
+const LUI: u32 = 0x37;
+const BRANCH: u32 = 0x63;
+const JALR: u32 = 0x67;
+
+/* Branch conditions */
+const BEQ: u32 = 0;
+const BNE: u32 = 1;
+const BGE: u32 = 5;
+const BLTU: u32 = 6;
+const BGEU: u32 = 7;
+
+const ECALL: u32 = 0x00000073;
+const RET: u32 = 0x00008067;
+
+
+/* Instruction formats */
+const fn r_type(opcode: u32, funct3: u32, funct7: u32, rd: u32, rs1: u32, rs2: u32) -> u32 {
+    funct7 << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+    }
+
+const fn i_type(opcode: u32, funct3: u32, rd: u32, rs1: u32, imm: i32) -> u32 {
+    (imm as u32 & 0xFFF) << 20 | rs1 << 15 | funct3 << 12 | rd << 7 | opcode
+    }
+
+const fn s_type(funct3: u32, rs1: u32, rs2: u32, imm: i32) -> u32 {
+    let imm = imm as u32;
+    (imm >> 5 & 0x7F) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | (imm & 0x1F) << 7 | STORE
+    }
+
+const fn b_type(funct3: u32, rs1: u32, rs2: u32, imm: i32) -> u32 {
+    let imm = imm as u32;
+    (imm >> 12 & 1) << 31 | (imm >> 5 & 0x3F) << 25 | rs2 << 20 | rs1 << 15 | funct3 << 12 | (imm >> 1 & 0xF) << 8 | (imm >> 11 & 1) << 7 | BRANCH
+    }
+
+const fn u_type(opcode: u32, rd: u32, imm: i32) -> u32 {
+    (imm as u32) << 12 | rd << 7 | opcode
+    }
+
+/* Common instructions */
+const fn addi(rd: u32, rs1: u32, imm: i32) -> u32 {
+    i_type(OP_IMM, 0, rd, rs1, imm)
+    }
+
+const fn add(rd: u32, rs1: u32, rs2: u32) -> u32 {
+    r_type(OP, 0, 0, rd, rs1, rs2)
+    }
+
+/* Split an offset into the upper 20 bits, and the lower 12 bits - the lower ones are sign-extended, so the upper ones get rounded */
+const fn split(offset: i32) -> (i32, i32) {
+    let upper = offset.wrapping_add(0x800) >> 12;
+    (upper, offset.wrapping_sub(upper << 12))
+    }
+
+
+/* Helper container for assembling machine code - every instruction is a single 32-bit word */
+struct Asm {
+    code: Vec<u8>,
+    options: CodegenOptions,
+    /* Register holding the addressed cell's index */
+    index: u32
+    }
+
+impl Asm {
+    /* Get the current offset */
+    const fn pos(&self) -> usize {
+        self.code.len()
+        }
+
+    fn emit(&mut self, words: &[u32]) {
+        for word in words {
+            self.code.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+
+    fn word(&self, at: usize) -> u32 {
+        u32::from_le_bytes([self.code[at], self.code[at + 1], self.code[at + 2], self.code[at + 3]])
+        }
+
+    fn set_word(&mut self, at: usize, word: u32) {
+        self.code[at .. at + 4].copy_from_slice(&word.to_le_bytes());
+        }
+
+    /* Emit a jump, or a call to the target, through `auipc`, and `jalr` - it reaches the whole code, unlike `jal` */
+    fn jump(&mut self, link: u32, target: usize) {
+        let (upper, lower) = split(target as i32 - self.pos() as i32);
+        let temp = match link {
+            ZERO => ADDRESS,
+            _ => link
+            };
+
+        self.emit(&[
+            u_type(AUIPC, temp, upper),
+            i_type(JALR, 0, link, temp, lower)
+            ]);
+        }
+
+    /* Emit a conditional branch to the target */
+    fn branch(&mut self, cond: u32, rs1: u32, rs2: u32, target: usize) {
+        let offset = target as i32 - self.pos() as i32;
+        self.emit(&[b_type(cond, rs1, rs2, offset)]);
+        }
+
+    /* Emit a branch, or a jump with a placeholder offset, returns its location */
+    fn forward(&mut self, words: &[u32]) -> usize {
+        let at = self.pos();
+        self.emit(words);
+        at
+        }
+
+    /* Patch a branch, or a jump, emitted earlier, to point at the target */
+    fn patch(&mut self, at: usize, target: usize) {
+        let offset = target as i32 - at as i32;
+        let word = self.word(at);
+
+        match word & 0x7F {
+            BRANCH => self.set_word(at, word | b_type(0, 0, 0, offset) & !BRANCH),
+            /* Jumps are a pair of `auipc`, and `jalr` */
+            _ => {
+                let (upper, lower) = split(offset);
+                let next = self.word(at + 4);
+
+                self.set_word(at, word | u_type(0, 0, upper));
+                self.set_word(at + 4, next | i_type(0, 0, 0, 0, lower));
+                }
+            }
+        }
+
+    /* Load a 32-bit constant into a register - `lui`, and `addiw`, or just `addi` for the small ones */
+    fn move_constant(&mut self, reg: u32, value: i32) {
+        match value {
+            -0x800 ..= 0x7FF => self.emit(&[addi(reg, ZERO, value)]),
+            _ => {
+                let (upper, lower) = split(value);
+                self.emit(&[
+                    u_type(LUI, reg, upper),
+                    i_type(OP_IMM_32, 0, reg, reg, lower)
+                    ]);
+                }
+            }
+        }
+
+    /* Add a value to a register - `addi`, or `add` with a constant loaded into a register */
+    fn add_constant(&mut self, reg: u32, value: i32) {
+        match value {
+            -0x800 ..= 0x7FF => self.emit(&[addi(reg, reg, value)]),
+            _ => {
+                self.move_constant(CONSTANT, value);
+                self.emit(&[add(reg, reg, CONSTANT)]);
+                }
+            }
+        }
+
+    /* Emit the operation on the cell at an offset from the pointer - its index is computed into a4 */
+    fn at(&mut self, offset: i64, operation: impl FnOnce(&mut Self)) {
+        if offset != 0 {
+            self.emit(&[addi(OFFSET, INDEX, 0)]);
+            self.advance(OFFSET, offset);
+            self.index = OFFSET;
+            }
+
+        operation(self);
+        self.index = INDEX;
+        }
+
+    /* Compute the addressed cell's address into t2 - `s1 + s2 * size` */
+    fn cell_address(&mut self) {
+        match self.options.cell_bytes().trailing_zeros() {
+            0 => self.emit(&[add(ADDRESS, TAPE, self.index)]),
+            shift => self.emit(&[
+                i_type(OP_IMM, 1, ADDRESS, self.index, shift as i32),
+                add(ADDRESS, TAPE, ADDRESS)
+                ])
+            }
+        }
+
+    /* Load the current cell into t0, zero-extended - its address is left in t2 */
+    fn load_cell(&mut self) {
+        let funct3 = match self.options.cell_bytes() {
+            1 => 4,
+            2 => 5,
+            _ => 6
+            };
+
+        self.cell_address();
+        self.emit(&[i_type(LOAD, funct3, VALUE, ADDRESS, 0)]);
+        }
+
+    /* Store t0 into the current cell, at the address left in t2 */
+    fn store_cell(&mut self) {
+        let funct3 = self.options.cell_bytes().trailing_zeros();
+        self.emit(&[s_type(funct3, ADDRESS, VALUE, 0)]);
+        }
+
+    /* Set the current cell to a value, truncated to the cell's width */
+    fn set_cell(&mut self, value: u64) {
+        self.move_constant(VALUE, (value & self.options.cell_mask()) as u32 as i32);
+        self.cell_address();
+        self.store_cell();
+        }
+
+    /* Add the current cell times a factor to the cell at an offset - the pointer is moved there, and back */
+    fn mul_add(&mut self, offset: i64, factor: i16) {
+        /* Bits are flipped by odd factors only, and the cell is the product itself */
+        let (skip, funct3, factor) = match self.options.cell_size {
+            CellSize::U1 => (factor % 2 == 0, 4, 1),    /* xor */
+            _ => (factor == 0, 0, factor)               /* add */
+            };
+        if skip {
+            return;
+            }
+
+        self.load_cell();
+        match factor {
+            1 => self.emit(&[addi(PRODUCT, VALUE, 0)]),
+            _ => {
+                self.move_constant(CONSTANT, factor.into());
+                self.emit(&[r_type(OP, 0, 1, PRODUCT, VALUE, CONSTANT)]);  /* mul a3, t0, t1 */
+                }
+            }
+        self.move_pointer(offset);
+        self.load_cell();
+        self.emit(&[r_type(OP, funct3, 0, VALUE, VALUE, PRODUCT)]);
+        self.store_cell();
+        self.move_pointer(-offset);
+        }
+
+    /* Add a value to the current cell - wrapping, as the store truncates it to the cell's width */
+    fn add_cell(&mut self, value: i64) {
+        let value = match self.options.cell_size {
+            /* Adding an odd value to a single bit flips it */
+            CellSize::U1 => {
+                if value % 2 != 0 {
+                    self.load_cell();
+                    self.emit(&[i_type(OP_IMM, 4, VALUE, VALUE, 1)]);   /* xori t0, t0, 1 */
+                    self.store_cell();
+                    }
+                return;
+                },
+            CellSize::U8 => value as i8 as i32,
+            CellSize::U16 => value as i16 as i32,
+            CellSize::U32 => value as i32,
+            /* 64-bit, arbitrary-precision, and signed cells were rejected while validating */
+            CellSize::U64 | CellSize::Big | CellSize::I8 | CellSize::I16 | CellSize::I32 => return
+            };
+
+        if value != 0 {
+            self.load_cell();
+            self.add_constant(VALUE, value);
+            self.store_cell();
+            }
+        }
+
+    /* Move the pointer - wrapping, by clearing the bits above the pointer's width */
+    fn move_pointer(&mut self, value: i64) {
+        self.advance(INDEX, value);
+        }
+
+    /* Add a distance to an index register - wrapping, by clearing the bits above the pointer's width */
+    fn advance(&mut self, reg: u32, value: i64) {
+        let value = match self.options.pointer_size {
+            DataSize::U8 => value as i8 as i32,
+            DataSize::U16 => value as i16 as i32,
+            DataSize::U32 => value as i32,
+            /* 64-bit pointers were rejected while validating */
+            DataSize::U64 => return
+            };
+
+        if value != 0 {
+            let shift = 64 - self.options.pointer_bits() as i32;
+
+            self.add_constant(reg, value);
+            self.emit(&[
+                i_type(OP_IMM, 1, reg, reg, shift),         /* slli */
+                i_type(OP_IMM, 5, reg, reg, shift)          /* srli */
+                ]);
+            }
+        }
+    }
+
+
+/* Addresses of the runtime routines */
+struct Runtime {
+    flush: usize,
+    put_byte: usize,
+    get_byte: usize,
+    put_value: usize
+    }
+
+/* Function for emitting the runtime routines - s4 holds the buffered output's length, and s3 the buffer's address */
+fn emit_runtime(asm: &mut Asm) -> Runtime {
+    /* Write out the buffered output - `write(1, s3, s4)` */
+    let flush = asm.pos();
+    asm.emit(&[
+        b_type(BEQ, LENGTH, ZERO, 28),  /* beqz s4, .ret */
+        addi(A0, ZERO, 1),              /* li a0, 1 */
+        addi(A1, BUFFER, 0),            /* mv a1, s3 */
+        addi(A2, LENGTH, 0),            /* mv a2, s4 */
+        addi(A7, ZERO, 64),             /* li a7, 64 */
+        ECALL,
+        addi(LENGTH, ZERO, 0),          /* li s4, 0 */
+        RET                             /* .ret: ret */
+        ]);
+
+    /* Buffer the byte in a0, and flush once the buffer gets full */
+    let put_byte = asm.pos();
+    asm.emit(&[
+        add(ADDRESS, BUFFER, LENGTH),   /* add t2, s3, s4 */
+        s_type(0, ADDRESS, A0, 0),      /* sb a0, 0(t2) */
+        addi(LENGTH, LENGTH, 1)         /* addi s4, s4, 1 */
+        ]);
+    asm.move_constant(CONSTANT, BUFFER_SIZE as i32);
+    asm.emit(&[b_type(BNE, LENGTH, CONSTANT, 12)]);     /* bne s4, t1, .ret */
+    asm.jump(ZERO, flush);              /* tail flush */
+    asm.emit(&[RET]);                   /* .ret: ret */
+
+    /* Exit with a failure, keeping the output written so far - `exit(1)` */
+    let exit_failure = asm.pos();
+    asm.jump(RA, flush);
+    asm.emit(&[
+        addi(A0, ZERO, 1),              /* li a0, 1 */
+        addi(A7, ZERO, 93),             /* li a7, 93 */
+        ECALL
+        ]);
+
+    /* Read a single byte into a0 - `read(0, sp, 1)`, the end of input is a failure */
+    let get_byte = asm.pos();
+    asm.emit(&[
+        addi(SP, SP, -16),              /* addi sp, sp, -16 */
+        s_type(3, SP, RA, 8)            /* sd ra, 8(sp) */
+        ]);
+    asm.jump(RA, flush);
+    asm.emit(&[
+        addi(A0, ZERO, 0),              /* li a0, 0 */
+        addi(A1, SP, 0),                /* mv a1, sp */
+        addi(A2, ZERO, 1),              /* li a2, 1 */
+        addi(A7, ZERO, 63),             /* li a7, 63 */
+        ECALL,
+        addi(CONSTANT, ZERO, 1)         /* li t1, 1 */
+        ]);
+    asm.branch(BNE, A0, CONSTANT, exit_failure);        /* bne a0, t1, exit_failure */
+    asm.emit(&[
+        i_type(LOAD, 4, A0, SP, 0),     /* lbu a0, 0(sp) */
+        i_type(LOAD, 3, RA, SP, 8),     /* ld ra, 8(sp) */
+        addi(SP, SP, 16),               /* addi sp, sp, 16 */
+        RET
+        ]);
+
+    /* Write the value in a0, according to the display mode */
+    let put_value = asm.pos();
+    match asm.options.display_mode {
+        DisplayMode::ASCII => {
+            /* Printable characters are written as they are - tab, line feed, and graphic characters */
+            asm.emit(&[addi(CONSTANT, ZERO, b'~' as i32)]);
+            let above_tilde = asm.forward(&[b_type(BLTU, CONSTANT, A0, 0)]);   /* bgtu a0, '~', .hex */
+            asm.emit(&[addi(CONSTANT, ZERO, b' ' as i32)]);
+            let graphic = asm.forward(&[b_type(BGEU, A0, CONSTANT, 0)]);       /* bgeu a0, ' ', .byte */
+            asm.emit(&[addi(CONSTANT, ZERO, b'\t' as i32)]);
+            let below_tab = asm.forward(&[b_type(BLTU, A0, CONSTANT, 0)]);     /* bltu a0, '\t', .hex */
+            asm.emit(&[addi(CONSTANT, ZERO, b'\n' as i32)]);
+            let above_feed = asm.forward(&[b_type(BLTU, CONSTANT, A0, 0)]);    /* bgtu a0, '\n', .hex */
+
+            let byte = asm.pos();
+            asm.patch(graphic, byte);
+            asm.jump(ZERO, put_byte);           /* .byte: tail put_byte */
+
+            /* Fallback - `0x` prefixed, zero padded, uppercase hexadecimal */
+            let hex = asm.pos();
+            for at in [above_tilde, below_tab, above_feed] {
+                asm.patch(at, hex);
+                }
+
+            asm.emit(&[
+                addi(SP, SP, -16),              /* .hex: addi sp, sp, -16 */
+                s_type(3, SP, RA, 8),           /* sd ra, 8(sp) */
+                addi(T3, A0, 0),                /* mv t3, a0 */
+                addi(A0, ZERO, b'0' as i32)     /* li a0, '0' */
+                ]);
+            asm.jump(RA, put_byte);
+            asm.emit(&[addi(A0, ZERO, b'x' as i32)]);   /* li a0, 'x' */
+            asm.jump(RA, put_byte);
+
+            /* Shift of the most significant digit */
+            let shift = (asm.options.cell_bytes() * 2 - 1) * 4;
+            asm.emit(&[addi(T4, ZERO, shift as i32)]);  /* li t4, shift */
+
+            let digit = asm.pos();
+            asm.emit(&[
+                r_type(OP, 5, 0, A0, T3, T4),   /* .digit: srl a0, t3, t4 */
+                i_type(OP_IMM, 7, A0, A0, 15),  /* andi a0, a0, 15 */
+                addi(CONSTANT, ZERO, 10),       /* li t1, 10 */
+                b_type(BLTU, A0, CONSTANT, 8),  /* bltu a0, t1, .decimal */
+                addi(A0, A0, 7),                /* addi a0, a0, 'A' - '0' - 10 */
+                addi(A0, A0, b'0' as i32)       /* .decimal: addi a0, a0, '0' */
+                ]);
+            asm.jump(RA, put_byte);
+            asm.emit(&[addi(T4, T4, -4)]);      /* addi t4, t4, -4 */
+            asm.branch(BGE, T4, ZERO, digit);   /* bgez t4, .digit */
+            asm.emit(&[
+                i_type(LOAD, 3, RA, SP, 8),     /* ld ra, 8(sp) */
+                addi(SP, SP, 16),               /* addi sp, sp, 16 */
+                RET
+                ]);
+            },
+        DisplayMode::Numeric | DisplayMode::Hex | DisplayMode::Bin | DisplayMode::Oct => {
+            /* Push the digits in the display mode's base from the least significant, then pop them in order */
+            let base = asm.options.display_mode.radix();
+            asm.emit(&[
+                addi(SP, SP, -16),              /* addi sp, sp, -16 */
+                s_type(3, SP, RA, 8),           /* sd ra, 8(sp) */
+                addi(T3, A0, 0),                /* mv t3, a0 */
+                addi(T5, ZERO, base as i32),    /* li t5, base */
+                addi(T4, ZERO, 0)               /* li t4, 0 */
+                ]);
+
+            let divide = asm.pos();
+            asm.emit(&[
+                r_type(OP, 7, 1, T6, T3, T5),   /* .divide: remu t6, t3, t5 */
+                r_type(OP, 5, 1, T3, T3, T5)    /* divu t3, t3, t5 */
+                ]);
+            if base > 10 {
+                asm.emit(&[
+                    addi(CONSTANT, ZERO, 10),   /* li t1, 10 */
+                    b_type(BLTU, T6, CONSTANT, 8),  /* bltu t6, t1, .decimal */
+                    addi(T6, T6, 7)             /* addi t6, t6, 'A' - '0' - 10 */
+                    ]);
+                }
+            asm.emit(&[
+                addi(T6, T6, b'0' as i32),      /* .decimal: addi t6, t6, '0' */
+                addi(SP, SP, -16),              /* addi sp, sp, -16 */
+                s_type(3, SP, T6, 0),           /* sd t6, 0(sp) */
+                addi(T4, T4, 1)                 /* addi t4, t4, 1 */
+                ]);
+            asm.branch(BNE, T3, ZERO, divide);  /* bnez t3, .divide */
+
+            let digit = asm.pos();
+            asm.emit(&[
+                i_type(LOAD, 3, A0, SP, 0),     /* .digit: ld a0, 0(sp) */
+                addi(SP, SP, 16)                /* addi sp, sp, 16 */
+                ]);
+            asm.jump(RA, put_byte);
+            asm.emit(&[addi(T4, T4, -1)]);      /* addi t4, t4, -1 */
+            asm.branch(BNE, T4, ZERO, digit);   /* bnez t4, .digit */
+            asm.emit(&[
+                i_type(LOAD, 3, RA, SP, 8),     /* ld ra, 8(sp) */
+                addi(SP, SP, 16),               /* addi sp, sp, 16 */
+                RET
+                ]);
+            },
+        DisplayMode::Raw =>
+            /* The low byte is written as it is */
+            asm.jump(ZERO, put_byte),           /* tail put_byte */
+        /* Unicode display was rejected while validating */
+        DisplayMode::Unicode => ()
+        }
+
+    Runtime { flush, put_byte, get_byte, put_value }
+    }
+
+/* Function for compiling the instructions into a static RISC-V 64 Linux executable */
+pub fn compile_riscv64(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    options.validate_native()?;
+
+    let ir = lower_checked(instr, options.opt_level)?;
+    let mut asm = Asm {
+        code: Vec::with_capacity(256 + 16 * ir.len()),
+        options,
+        index: INDEX
+        };
+
+    let runtime = emit_runtime(&mut asm);
+
+    /* Entry point - the tape comes right after the output buffer, both fit into `lui` */
+    let entry = asm.pos();
+    asm.move_constant(TAPE, (BSS_ADDRESS + BUFFER_SIZE) as i32);
+    asm.move_constant(BUFFER, BSS_ADDRESS as i32);
+    asm.emit(&[
+        addi(INDEX, ZERO, 0),           /* li s2, 0 */
+        addi(LENGTH, ZERO, 0)           /* li s4, 0 */
+        ]);
+
+    /* Locations of the open loops' forward jumps */
+    let mut loop_stack = Vec::new();
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) =>
+                asm.add_cell(value.into()),
+            IrOp::Move(value) =>
+                asm.move_pointer(value as i64),
+            IrOp::Set(value) =>
+                asm.set_cell(value),
+            IrOp::MulAdd(offset, factor) =>
+                asm.mul_add(offset as i64, factor),
+            IrOp::AddAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.add_cell(value.into())),
+            IrOp::SetAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.set_cell(value)),
+            IrOp::Output => {
+                asm.load_cell();
+                asm.emit(&[addi(A0, VALUE, 0)]);    /* mv a0, t0 */
+                asm.jump(RA, runtime.put_value);
+                },
+            IrOp::Input => {
+                asm.jump(RA, runtime.get_byte);
+                /* Only the lowest bit is kept for 1-bit cells */
+                match asm.options.cell_size {
+                    CellSize::U1 => asm.emit(&[i_type(OP_IMM, 7, VALUE, A0, 1)]),  /* andi t0, a0, 1 */
+                    _ => asm.emit(&[addi(VALUE, A0, 0)])                            /* mv t0, a0 */
+                    };
+                asm.cell_address();
+                asm.store_cell();
+                },
+            /* Jump past the loop's end, when the cell is zero - branches only reach 4 KiB */
+            IrOp::LoopStart(_) => {
+                asm.load_cell();
+                asm.emit(&[b_type(BNE, VALUE, ZERO, 12)]);  /* bnez t0, +12 */
+                loop_stack.push(asm.forward(&[
+                    u_type(AUIPC, ADDRESS, 0),
+                    i_type(JALR, 0, ZERO, ADDRESS, 0)
+                    ]));
+                },
+            /* Jump back to the loop's body, when the cell is non-zero */
+            IrOp::LoopEnd(_) => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some(open) = loop_stack.pop() else {
+                    continue;
+                    };
+
+                asm.load_cell();
+                asm.emit(&[b_type(BEQ, VALUE, ZERO, 12)]);  /* beqz t0, +12 */
+                asm.jump(ZERO, open + 8);
+
+                let end = asm.pos();
+                asm.patch(open, end);
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run, then `exit(0)` - raw output stays byte-exact */
+    if options.display_mode != DisplayMode::Raw {
+        asm.emit(&[addi(A0, ZERO, b'\n' as i32)]); /* li a0, '\n' */
+        asm.jump(RA, runtime.put_byte);
+        }
+    asm.jump(RA, runtime.flush);
+    asm.emit(&[
+        addi(A0, ZERO, 0),              /* li a0, 0 */
+        addi(A7, ZERO, 93),             /* li a7, 93 */
+        ECALL
+        ]);
+
+    Ok(executable(MACHINE_RISCV, &asm.code, entry as u64, options.bss_size()))
+    }
+
+#[cfg(test)]
+mod test {
+    use crate::riscv::*;
+
+    #[test]
+    fn compile_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let binary = compile_riscv64(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&binary[.. 4], b"\x7FELF");
+        assert_eq!(u16::from_le_bytes([binary[18], binary[19]]), MACHINE_RISCV);
+        }
+
+    #[test]
+    fn split_offsets() {
+        for offset in [0, 1, 0x7FF, 0x800, -0x800, -0x801, 0x12345678, -0x12345678] {
+            let (upper, lower) = split(offset);
+
+            assert!((-0x800 .. 0x800).contains(&lower));
+            assert_eq!((upper << 12) + lower, offset);
+            }
+        }
+
+    #[test]
+    fn compile_err_ascii_bit_cells() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            cell_size: CellSize::U1,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_riscv64(&instr, options), Err(CompileError::AsciiBitCells));
+        }
+    }
\ No newline at end of file
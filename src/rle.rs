@@ -1,11 +1,32 @@
 use {
-    core::num::NonZeroU16,
-    crate::eval::Instruction
+    alloc::{
+        boxed::Box,
+        vec::{
+            IntoIter,
+            Vec
+            }
+        },
+    core::{
+        num::NonZeroU16,
+        slice::Iter
+        },
+    crate::eval::{
+        check_loops,
+        EvalError,
+        Instruction
+        }
+    };
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize
     };
 
 
 /* Run-Length Encoding helper type */
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RLE<T> ( NonZeroU16, T );
 
 impl RLE<()> {
@@ -33,12 +54,81 @@ where T: Copy {
     }
 
 
-/* Container for an optimised instruction set */
+/* Container for an optimised instruction set - deserialised ones are checked the same way, as the parsed ones */
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "Vec<RLE<Instruction>>"))]
 pub struct RLEInstructionSet (
     pub(crate) Box<[RLE<Instruction>]>
     );
 
+impl TryFrom<Vec<RLE<Instruction>>> for RLEInstructionSet {
+    type Error = EvalError;
+
+    /* Conversion from the runs, which have to keep their loops balanced */
+    fn try_from(value: Vec<RLE<Instruction>>) -> Result<Self, Self::Error> {
+        check_loops(value.iter().map(RLE::get))?;
+
+        Ok(RLEInstructionSet(value.into_boxed_slice()))
+        }
+    }
+
+impl IntoIterator for RLEInstructionSet {
+    type Item = RLE<Instruction>;
+    type IntoIter = IntoIter<RLE<Instruction>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_vec()
+            .into_iter()
+        }
+    }
+
+impl<'a> IntoIterator for &'a RLEInstructionSet {
+    type Item = &'a RLE<Instruction>;
+    type IntoIter = Iter<'a, RLE<Instruction>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+        }
+    }
+
+impl RLEInstructionSet {
+    /* Get number of runs */
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+        }
+    /* Get whether is empty */
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+        }
+
+    /* Get the runs, in order */
+    #[inline]
+    pub const fn as_slice(&self) -> &[RLE<Instruction>] {
+        &self.0
+        }
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, RLE<Instruction>> {
+        self.0.iter()
+        }
+
+    /* Get the runs, as their lengths, and instructions */
+    pub fn runs(&self) -> impl Iterator<Item = (u16, Instruction)> + '_ {
+        self.0.iter()
+            .map(RLE::get)
+        }
+
+    /* Get a stable hash of the runs - FNV-1a over the instruction kinds, and the lengths */
+    pub fn fingerprint(&self) -> u64 {
+        self.runs()
+            .fold(0xCBF2_9CE4_8422_2325, |hash, (count, inst)| {
+                let hash = (hash ^ inst as u64).wrapping_mul(0x0100_0000_01B3);
+                (hash ^ u64::from(count)).wrapping_mul(0x0100_0000_01B3)
+                })
+        }
+    }
+
 #[cfg(test)]
 mod test {
     use {
@@ -97,6 +187,17 @@ mod test {
         assert_eq!(instructions, rle);
         }
 
+    #[test]
+    fn instr_rle_iter() {
+        let rle = eval_instr("+++>.")
+            .expect("Unreachable")
+            .encode_run_length();
+
+        assert_eq!(rle.as_slice(), [RLE::new(3, Increment), RLE::new(1, Right), RLE::new(1, Output)]);
+        assert_eq!(rle.iter().map(RLE::get).map(|(count, _)| count).sum::<u16>(), 5);
+        assert_eq!(rle.into_iter().next(), Some(RLE::new(3, Increment)));
+        }
+
     #[test]
     fn instr_rle_many() {
         let instr_str: String = repeat_n('+', RLE::MAX as usize + 1)
@@ -113,4 +214,20 @@ mod test {
 
         assert_eq!(instructions, rle);
         }
+
+    #[test]
+    fn instr_rle_runs() {
+        let instructions = eval_instr("+++>>[-]")
+            .expect("Unreachable")
+            .encode_run_length();
+
+        assert_eq!(instructions.len(), 5);
+        assert_eq!(instructions.runs().collect::<Vec<_>>(), vec![
+            (3, Increment),
+            (2, Right),
+            (1, LoopOpen),
+            (1, Decrement),
+            (1, LoopClose)
+            ]);
+        }
     }
\ No newline at end of file
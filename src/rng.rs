@@ -0,0 +1,96 @@
+use std::time::{
+    SystemTime,
+    UNIX_EPOCH
+    };
+
+
+/* Function for getting a seed based on the current time */
+pub fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+    }
+
+
+/* Seeded pseudo-random number generator - SplitMix64 */
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64
+    }
+
+impl Default for Rng {
+    /* Seed from the system clock, when no explicit seed was given */
+    fn default() -> Self {
+        Self::new(clock_seed())
+        }
+    }
+
+impl Rng {
+    /* Constructor function */
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+        }
+
+    /* Get the internal state - a generator constructed with it continues the same sequence */
+    #[inline]
+    pub const fn state(&self) -> u64 {
+        self.state
+        }
+
+    /* Generate next pseudo-random value */
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::rng::*;
+
+    #[test]
+    fn rng_reproducible() {
+        let mut first = Rng::new(1983);
+        let mut second = Rng::new(1983);
+
+        let same = (0 .. 64)
+            .all(|_| first.next_u64() == second.next_u64());
+
+        assert!(same);
+        }
+
+    #[test]
+    fn rng_state_resume() {
+        let mut first = Rng::new(1988);
+        first.next_u64();
+
+        let mut second = Rng::new(first.state());
+
+        assert_eq!(first.next_u64(), second.next_u64());
+        }
+
+    #[test]
+    fn rng_seed_dependent() {
+        let mut first = Rng::new(0);
+        let mut second = Rng::new(1);
+
+        assert_ne!(first.next_u64(), second.next_u64());
+        }
+
+    #[test]
+    fn rng_not_constant() {
+        let mut rng = Rng::new(0);
+        let value = rng.next_u64();
+
+        let varied = (0 .. 8)
+            .any(|_| rng.next_u64() != value);
+
+        assert!(varied);
+        }
+    }
\ No newline at end of file
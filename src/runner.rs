@@ -0,0 +1,171 @@
+use {
+    anyhow::{
+        bail,
+        Result as DynResult
+        },
+    log::info,
+    std::{
+        fs::{
+            read,
+            read_dir,
+            read_to_string,
+            write
+            },
+        io::{
+            Cursor,
+            ErrorKind
+            },
+        path::Path,
+        time::Duration
+        },
+    crate::args::Settings,
+    braincooker::*
+    };
+
+
+/* Output limit of a single test - keeps a runaway program from filling the memory */
+const TEST_MAX_OUTPUT: u64 = 1 << 20;
+
+
+/* Function for running every `*.bf` program in the directory, which has a sibling `*.out` file */
+pub fn run_tests(dir: &Path, settings: &Settings, max_steps: u64, timeout: Duration) -> DynResult<()> {
+    /* Discover the programs, in a stable order */
+    let mut programs = read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    programs.retain(|path| path.extension().is_some_and(|ext| ext == "bf"));
+    programs.sort();
+
+    let mut failed = 0;
+    let mut total = 0;
+
+    for program in programs {
+        /* Programs without an expected output aren't tests */
+        let Some(expected) = read_optional(&program.with_extension("out"))? else {
+            info!("Skipping, no expected output: {}", program.display());
+            continue;
+            };
+        let script = read_optional(&program.with_extension("in"))?
+            .unwrap_or_default();
+
+        let name = program.file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+
+        total += 1;
+
+        match run_test(&program, script, settings, max_steps, timeout) {
+            Ok(actual) if actual == expected =>
+                println!("PASS {name}"),
+            Ok(actual) => {
+                failed += 1;
+
+                println!("FAIL {name}");
+                print_diff(&expected, &actual);
+                },
+            Err(err) => {
+                failed += 1;
+
+                println!("FAIL {name}");
+                println!("    error: {err}");
+                }
+            }
+        }
+
+    println!("{} passed, {failed} failed", total - failed);
+
+    if failed != 0 {
+        bail!("{failed} of {total} test(s) failed");
+        }
+
+    Ok(())
+    }
+
+/* Helper function, for reading a file, which may not exist */
+fn read_optional(path: &Path) -> DynResult<Option<Vec<u8>>> {
+    match read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into())
+        }
+    }
+
+/* Helper function, for running a single program with the scripted input, and getting its output - the limits turn a runaway program into a failure */
+fn run_test(program: &Path, script: Vec<u8>, settings: &Settings, max_steps: u64, timeout: Duration) -> DynResult<Vec<u8>> {
+    let (instr, _) = settings.eval(&read_to_string(program)?)?;
+
+    let outcome = Interpreter::builder()
+        .display_mode(DisplayMode::ASCII)
+        .max_output(TEST_MAX_OUTPUT)
+        .max_steps(max_steps)
+        .time_limit(timeout)
+        .input(Box::new(Cursor::new(script)))
+        .byte_input(true)
+        .capture_output()
+        .try_build::<u16, u8>()?
+        .run_consuming(&instr)?;
+
+    Ok(strip_run_newline(outcome.output
+        .unwrap_or_default()
+        ))
+    }
+
+/* Helper function, for dropping the newline written at the end of every run */
+fn strip_run_newline(mut output: Vec<u8>) -> Vec<u8> {
+    if output.last() == Some(&b'\n') {
+        output.pop();
+        }
+
+    output
+    }
+
+/* Function for comparing the output of a run with a golden file, or overwriting the file with it - only the raw output has no newline written at the end of the run, so it's kept whole */
+pub fn check_golden(path: &Path, output: Vec<u8>, display_mode: Option<DisplayMode>, update: bool) -> DynResult<()> {
+    let actual = match display_mode {
+        Some(DisplayMode::Raw) => output,
+        _ => strip_run_newline(output)
+        };
+
+    if update {
+        write(path, &actual)?;
+        info!("Updated the expected output: {}", path.display());
+
+        return Ok(());
+        }
+
+    let expected = read(path)?;
+
+    if expected != actual {
+        print_diff(&expected, &actual);
+        bail!("Output doesn't match the expected one: {}", path.display());
+        }
+
+    info!("Output matches the expected one: {}", path.display());
+
+    Ok(())
+    }
+
+/* Helper function, for printing the lines which differ */
+fn print_diff(expected: &[u8], actual: &[u8]) {
+    let expected = expected.split(|&byte| byte == b'\n')
+        .collect::<Vec<_>>();
+    let actual = actual.split(|&byte| byte == b'\n')
+        .collect::<Vec<_>>();
+
+    for i in 0 .. expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(i), actual.get(i));
+
+        if old == new {
+            continue;
+            }
+
+        println!("    line {}:", i + 1);
+        if let Some(line) = old {
+            println!("    - {}", line.escape_ascii());
+            }
+        if let Some(line) = new {
+            println!("    + {}", line.escape_ascii());
+            }
+        }
+    }
\ No newline at end of file
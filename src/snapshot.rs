@@ -0,0 +1,276 @@
+use {
+    thiserror::Error,
+    num_bigint::BigUint,
+    crate::codec::*
+    };
+
+
+/* Magic bytes, and version of the encoded format */
+const MAGIC: &[u8; 4] = b"BCSS";
+const VERSION: u8 = 3;
+/* Version before the cells were stored without a loss - they're read as 64-bit values */
+const VERSION_NARROW_CELLS: u8 = 2;
+/* Version before the procedures were captured - it's read as having none */
+const VERSION_NO_PROCEDURES: u8 = 1;
+
+
+/* Snapshot decoding's result output type */
+#[derive(PartialEq, Debug, Error)]
+pub enum SnapshotError {
+    #[error("Snapshot data is malformed")]
+    Malformed,
+    #[error("Snapshot was made with an unsupported format version: {0}")]
+    UnsupportedVersion(u8)
+    }
+
+impl From<Malformed> for SnapshotError {
+    fn from(_: Malformed) -> Self {
+        SnapshotError::Malformed
+        }
+    }
+
+/* Captured Interpreter state, from which execution can be resumed */
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    /* Fingerprint of the program the snapshot was made for */
+    pub(crate) program: u64,
+    /* Instruction pointer, and number of instructions executed so far */
+    pub(crate) instr_ptr: usize,
+    pub(crate) count: u64,
+    /* State of the random number generator */
+    pub(crate) rng: u64,
+    /* Output written before the snapshot */
+    pub(crate) output: Vec<u8>,
+    /* Tape pointer location, and cells differing from the fill value - arbitrary-precision ones keep every digit */
+    pub(crate) position: isize,
+    pub(crate) cells: Vec<(isize, BigUint)>,
+    /* Defined procedures' values, and starts, and the calls waiting to return - by their instructions' indices */
+    pub(crate) procedures: Vec<(u64, usize)>,
+    pub(crate) calls: Vec<usize>
+    }
+
+impl Snapshot {
+    /* Get the index of the instruction execution resumes at */
+    #[inline]
+    pub const fn instr_ptr(&self) -> usize {
+        self.instr_ptr
+        }
+
+    /* Get the number of instructions executed before the snapshot */
+    #[inline]
+    pub const fn executed(&self) -> u64 {
+        self.count
+        }
+
+    /* Get the location of the pointer */
+    #[inline]
+    pub const fn position(&self) -> isize {
+        self.position
+        }
+
+    /* Get the cells differing from the fill value, with their locations */
+    #[inline]
+    pub fn cells(&self) -> &[(isize, BigUint)] {
+        &self.cells
+        }
+
+    /* Serialise into bytes */
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::with_capacity(64 + self.output.len() + 16 * self.cells.len());
+
+        output.extend_from_slice(MAGIC);
+        output.push(VERSION);
+
+        output.extend_from_slice(&self.program.to_le_bytes());
+        output.extend_from_slice(&(self.instr_ptr as u64).to_le_bytes());
+        output.extend_from_slice(&self.count.to_le_bytes());
+        output.extend_from_slice(&self.rng.to_le_bytes());
+
+        put_bytes(&mut output, &self.output);
+
+        output.extend_from_slice(&(self.position as i64).to_le_bytes());
+        output.extend_from_slice(&(self.cells.len() as u64).to_le_bytes());
+        for (index, value) in self.cells.iter() {
+            output.extend_from_slice(&(*index as i64).to_le_bytes());
+            put_bytes(&mut output, &value.to_bytes_le());
+            }
+
+        output.extend_from_slice(&(self.procedures.len() as u64).to_le_bytes());
+        for &(value, start) in self.procedures.iter() {
+            output.extend_from_slice(&value.to_le_bytes());
+            output.extend_from_slice(&(start as u64).to_le_bytes());
+            }
+        output.extend_from_slice(&(self.calls.len() as u64).to_le_bytes());
+        for &call in self.calls.iter() {
+            output.extend_from_slice(&(call as u64).to_le_bytes());
+            }
+
+        output
+        }
+
+    /* Deserialise from bytes */
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader(bytes);
+
+        /* Check the header */
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(SnapshotError::Malformed);
+            }
+
+        let version = match reader.take(1)?[0] {
+            version @ (VERSION | VERSION_NARROW_CELLS | VERSION_NO_PROCEDURES) => version,
+            version => return Err(SnapshotError::UnsupportedVersion(version))
+            };
+
+        let program = reader.u64()?;
+        let instr_ptr = reader.size()?;
+        let count = reader.u64()?;
+        let rng = reader.u64()?;
+
+        let output = reader.bytes()?
+            .to_vec();
+
+        let position = reader.i64()? as isize;
+        let cells_len = reader.size()?;
+        let cells = (0 .. cells_len)
+            .map(|_| Ok((reader.i64()? as isize, match version {
+                VERSION => BigUint::from_bytes_le(reader.bytes()?),
+                _ => BigUint::from(reader.u64()?)
+                })))
+            .collect::<Result<_, Malformed>>()?;
+
+        let (procedures, calls) = match version {
+            VERSION_NO_PROCEDURES => (Vec::new(), Vec::new()),
+            _ => {
+                let procedures_len = reader.size()?;
+                let procedures = (0 .. procedures_len)
+                    .map(|_| Ok((reader.u64()?, reader.size()?)))
+                    .collect::<Result<_, Malformed>>()?;
+                let calls_len = reader.size()?;
+                let calls = (0 .. calls_len)
+                    .map(|_| reader.size())
+                    .collect::<Result<_, Malformed>>()?;
+
+                (procedures, calls)
+                }
+            };
+
+        /* Trailing data means the snapshot is corrupted */
+        if ! reader.is_empty() {
+            return Err(SnapshotError::Malformed);
+            }
+
+        Ok(Self { program, instr_ptr, count, rng, output, position, cells, procedures, calls })
+        }
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::snapshot::*;
+
+    fn sample() -> Snapshot {
+        Snapshot {
+            program: 0xDEAD_BEEF,
+            instr_ptr: 42,
+            count: 1000,
+            rng: 7,
+            output: b"Hello".to_vec(),
+            position: -2,
+            cells: vec![(-2, BigUint::from(1_u8)), (0, BigUint::from(255_u8)), (30000, BigUint::from(u64::MAX) << 8)],
+            procedures: vec![(0, 3), (5, 11)],
+            calls: vec![20]
+            }
+        }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let snapshot = sample();
+
+        assert_eq!(Snapshot::decode(&snapshot.encode()), Ok(snapshot));
+        }
+
+    #[test]
+    fn snapshot_truncated() {
+        let bytes = sample()
+            .encode();
+
+        let truncated = (0 .. bytes.len())
+            .all(|len| Snapshot::decode(&bytes[.. len]) == Err(SnapshotError::Malformed));
+
+        assert!(truncated);
+        }
+
+    #[test]
+    fn snapshot_trailing() {
+        let mut bytes = sample()
+            .encode();
+        bytes.push(0);
+
+        assert_eq!(Snapshot::decode(&bytes), Err(SnapshotError::Malformed));
+        }
+
+    /* Older formats store the cells as 64-bit values - the first one ends right after them */
+    fn narrow(snapshot: &Snapshot, version: u8) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(version);
+        bytes.extend_from_slice(&snapshot.encode()[MAGIC.len() + 1 ..][.. 32]);
+        put_bytes(&mut bytes, &snapshot.output);
+        bytes.extend_from_slice(&(snapshot.position as i64).to_le_bytes());
+        bytes.extend_from_slice(&(snapshot.cells.len() as u64).to_le_bytes());
+        for (index, value) in snapshot.cells.iter() {
+            bytes.extend_from_slice(&(*index as i64).to_le_bytes());
+            bytes.extend_from_slice(&value.iter_u64_digits().next().unwrap_or_default().to_le_bytes());
+            }
+
+        if version == VERSION_NARROW_CELLS {
+            bytes.extend_from_slice(&(snapshot.procedures.len() as u64).to_le_bytes());
+            for &(value, start) in snapshot.procedures.iter() {
+                bytes.extend_from_slice(&value.to_le_bytes());
+                bytes.extend_from_slice(&(start as u64).to_le_bytes());
+                }
+            bytes.extend_from_slice(&(snapshot.calls.len() as u64).to_le_bytes());
+            for &call in snapshot.calls.iter() {
+                bytes.extend_from_slice(&(call as u64).to_le_bytes());
+                }
+            }
+
+        bytes
+        }
+
+    #[test]
+    fn snapshot_narrow_cells() {
+        let snapshot = Snapshot {
+            cells: vec![(-2, BigUint::from(1_u8)), (0, BigUint::from(u64::MAX))],
+            ..sample()
+            };
+
+        assert_eq!(Snapshot::decode(&narrow(&snapshot, VERSION_NARROW_CELLS)), Ok(snapshot));
+        }
+
+    #[test]
+    fn snapshot_no_procedures() {
+        let snapshot = Snapshot {
+            procedures: Vec::new(),
+            calls: Vec::new(),
+            ..sample()
+            };
+
+        /* Only the lowest 64 bits of the cells were stored */
+        assert_eq!(Snapshot::decode(&narrow(&snapshot, VERSION_NO_PROCEDURES)), Ok(Snapshot {
+            cells: vec![(-2, BigUint::from(1_u8)), (0, BigUint::from(255_u8)), (30000, BigUint::from(0xFFFF_FFFF_FFFF_FF00_u64))],
+            ..snapshot
+            }));
+        }
+
+    #[test]
+    fn snapshot_version() {
+        let mut bytes = sample()
+            .encode();
+        bytes[MAGIC.len()] = VERSION + 1;
+
+        assert_eq!(Snapshot::decode(&bytes), Err(SnapshotError::UnsupportedVersion(VERSION + 1)));
+        }
+    }
\ No newline at end of file
@@ -1,115 +1,1187 @@
 use {
     log::error,
     min_max_traits::Max,
+    num_bigint::BigUint,
     num_traits::{
+        Num,
         Unsigned,
         ConstZero,
         ConstOne,
+        CheckedSub,
         WrappingAdd,
         WrappingSub,
         ToPrimitive,
-        ToBytes
+        FromPrimitive
         },
-    std::{
+    alloc::{
+        boxed::Box,
+        collections::BTreeMap,
         string::ToString,
-        process::exit
+        vec::Vec
         },
     core::{
-        fmt::UpperHex,
+        fmt::{
+            Binary,
+            Octal,
+            UpperHex
+            },
         iter::repeat_n,
+        marker::PhantomData,
+        mem::replace,
         str::FromStr
         }
     };
 
+#[cfg(feature = "std")]
+use std::{
+    process::exit,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError
+        }
+    };
+
 /* Trait for Tape's Pointer which will serve both as pointer of a cell, and bound for number of cells */
 pub trait TapePointer:
-    Sized + Max +
-    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive {}
+    Sized + Send + Max +
+    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive + FromPrimitive {}
 
 impl<T> TapePointer for T where T:
-    Sized + Max +
-    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive {}
+    Sized + Send + Max +
+    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive + FromPrimitive {}
 
 /* Trait for Tape's Cell which will hold a value, and allow conversions for reading, and writing */
 pub trait TapeCell:
-    Sized + Copy + UpperHex + From<u8> + ToString + FromStr +
-    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive + ToBytes {}
+    Sized + Send + Clone + UpperHex + Binary + Octal + ToString + FromStr +
+    Num + ConstZero + ToPrimitive + FromPrimitive {
+    /* Largest value of the cell - none for the unbounded cells */
+    const BOUND: Option<Self>;
+
+    /* Get the cell from a raw value, keeping only the bits of its width */
+    fn truncate(value: u64) -> Self;
+    /* Get the cell's bits, widened to u64 - unbounded cells keep only their lowest 64 bits, so it only keys the procedures */
+    fn widen(&self) -> u64;
+    /* Get the cell from a value widened to u64 - none, when it doesn't fit the cell's width */
+    fn narrow(value: u64) -> Option<Self> {
+        Some(Self::truncate(value))
+            .filter(|cell| cell.widen() == value)
+        }
+    /* Get the cell's bits, widened without losing any of them - for the dumps, and the snapshots */
+    fn exact(&self) -> BigUint {
+        BigUint::from(self.widen())
+        }
+    /* Get the cell from a value widened without a loss - none, when it doesn't fit the cell's width */
+    fn from_exact(value: &BigUint) -> Option<Self> {
+        value.to_u64()
+            .and_then(Self::narrow)
+        }
+
+    /* Add a signed value to the cell */
+    fn offset(&self, value: i64) -> Self;
+    /* Add a signed value to the cell - none, when it would wrap around */
+    fn checked_offset(&self, value: i64) -> Option<Self>;
+    /* Add a signed value to the cell - stopping at the bounds, instead of wrapping around */
+    fn saturating_offset(&self, value: i64) -> Self;
+    /* Add the source cell's value multiplied by the factor to the cell */
+    fn add_product(&self, source: &Self, factor: i64) -> Self;
+
+    /* Get the cell as little-endian bytes of its width - unbounded cells take as many, as their value needs */
+    fn le_bytes(&self) -> Vec<u8>;
+    }
+
+/* Fixed-width cells wrap around their range - signed ones are widened as their unsigned bits */
+macro_rules! fixed_cell {
+    ($($cell:ty => $bits:ty),*) => {$(
+        impl TapeCell for $cell {
+            const BOUND: Option<Self> = Some(<$cell>::MAX);
+
+            fn truncate(value: u64) -> Self {
+                value as $cell
+                }
+            fn widen(&self) -> u64 {
+                *self as $bits as u64
+                }
+
+            fn offset(&self, value: i64) -> Self {
+                <$cell>::wrapping_add(*self, value as $cell)
+                }
+            fn checked_offset(&self, value: i64) -> Option<Self> {
+                <$cell>::try_from(i128::from(*self) + i128::from(value))
+                    .ok()
+                }
+            fn saturating_offset(&self, value: i64) -> Self {
+                (i128::from(*self) + i128::from(value))
+                    .clamp(i128::from(<$cell>::MIN), i128::from(<$cell>::MAX)) as $cell
+                }
+            fn add_product(&self, source: &Self, factor: i64) -> Self {
+                <$cell>::wrapping_add(*self, source.wrapping_mul(factor as $cell))
+                }
+
+            fn le_bytes(&self) -> Vec<u8> {
+                self.to_le_bytes().to_vec()
+                }
+            }
+        )*};
+    }
+
+fixed_cell!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, i8 => u8, i16 => u16, i32 => u32);
+
+/* Arbitrary-precision cells grow without a bound, and stop at zero instead of wrapping below it - the wrapping mode still reports it as an underflow */
+impl TapeCell for BigUint {
+    const BOUND: Option<Self> = None;
+
+    fn truncate(value: u64) -> Self {
+        BigUint::from(value)
+        }
+    fn widen(&self) -> u64 {
+        self.iter_u64_digits()
+            .next()
+            .unwrap_or_default()
+        }
+    fn exact(&self) -> BigUint {
+        self.clone()
+        }
+    fn from_exact(value: &BigUint) -> Option<Self> {
+        Some(value.clone())
+        }
+
+    fn offset(&self, value: i64) -> Self {
+        self.checked_offset(value)
+            .unwrap_or_default()
+        }
+    fn checked_offset(&self, value: i64) -> Option<Self> {
+        let magnitude = BigUint::from(value.unsigned_abs());
+
+        match value < 0 {
+            true => self.checked_sub(&magnitude),
+            false => Some(self + magnitude)
+            }
+        }
+    fn saturating_offset(&self, value: i64) -> Self {
+        self.offset(value)
+        }
+    fn add_product(&self, source: &Self, factor: i64) -> Self {
+        let product = source * factor.unsigned_abs();
+
+        match factor < 0 {
+            true => self.checked_sub(&product).unwrap_or_default(),
+            false => self + product
+            }
+        }
+
+    fn le_bytes(&self) -> Vec<u8> {
+        self.to_bytes_le()
+        }
+    }
+
+/* Trait for a storage of cells, which can be driven by the Interpreter - can be moved across threads along with it */
+pub trait TapeBackend<U>: Send {
+    /* Moves pointer to the right, logical equivalent to '>' */
+    fn right(&mut self);
+    /* Moves pointer to the left, logical equivalent to '<' */
+    fn left(&mut self);
+
+    /* Increments cell at the current pointer location, logical equivalent to '+' */
+    fn increment(&mut self);
+    /* Decrements cell at the current pointer location, logical equivalent to '-' */
+    fn decrement(&mut self);
+
+    /* Adds to cell at the current pointer location, logical equivalent to a run of '+', or '-' */
+    fn add(&mut self, value: i16) {
+        for _ in 0 .. value.unsigned_abs() {
+            match value > 0 {
+                true => self.increment(),
+                false => self.decrement()
+                }
+            }
+        }
+
+    /* Moves pointer by the given distance, logical equivalent to a run of '>', or '<' */
+    fn shift(&mut self, value: isize) {
+        for _ in 0 .. value.unsigned_abs() {
+            match value > 0 {
+                true => self.right(),
+                false => self.left()
+                }
+            }
+        }
+
+    /* Adds to cell at an offset from the current pointer location, without moving the pointer */
+    fn add_at(&mut self, offset: isize, value: i16) {
+        self.shift(offset);
+        self.add(value);
+        self.shift(-offset);
+        }
+    /* Set cell value at an offset from the current pointer location, without moving the pointer */
+    fn set_at(&mut self, offset: isize, value: U) {
+        self.shift(offset);
+        self.set(value);
+        self.shift(-offset);
+        }
+
+    /* Get cell value at the current pointer location */
+    fn get(&self) -> U;
+    /* Set cell value at the current pointer location */
+    fn set(&mut self, value: U);
+
+    /* Check whether cell value at the current pointer location is equal to zero */
+    fn is_zero(&self) -> bool;
+
+    /* Get the current pointer location */
+    fn position(&self) -> isize;
+    /* Move the pointer to the given location, returns whether it exists on the tape */
+    fn seek(&mut self, position: isize) -> bool;
+
+    /* Get all cells holding something else than the fill value, with their locations */
+    fn cells(&self) -> Vec<(isize, U)>;
+    /* Get all cells holding something else than the fill value on every tape, with the tape's index, and their locations */
+    fn all_cells(&self) -> Vec<(usize, isize, U)> {
+        self.cells()
+            .into_iter()
+            .map(|(index, value)| (0, index, value))
+            .collect()
+        }
+
+    /* Get every cell in order, with the location of the first one */
+    fn values(&self) -> (isize, Vec<U>);
+
+    /* Set every cell to the fill value, and move the pointer back to the start - keeps the allocated storage */
+    fn clear(&mut self);
+
+    /* Set every cell to the value, and use it as the fill value - the cells are zero until then */
+    fn fill(&mut self, value: U);
+
+    /* Switch to another tape, by the distance from the current one - a single tape stays where it is */
+    fn switch(&mut self, _offset: isize) {}
+    /* Get the number of tapes */
+    fn tape_count(&self) -> usize {
+        1
+        }
+
+    /* Get the cells as a plain array, indexed by the pointer's location - only for the fixed tapes storing whole cells */
+    #[cfg(feature = "jit")]
+    fn raw_cells(&mut self) -> Option<&mut [U]> {
+        None
+        }
+    }
+
+
+/* Helper function, for getting the number of cells addressable by a pointer type */
+fn tape_len<T>() -> usize
+where T: TapePointer {
+    /* Declaration of size, with additional assertion to halt the execution in case of invalid pointer size */
+    let Some(size) = T::MAX
+        .to_usize()
+        .and_then(|e| e.checked_add(1))
+    else {
+        error!("Couldn't safely convert to the intended pointer size");
+        halt();
+        };
+
+    size
+    }
+
+/* Helper function, for halting the execution after the logged error - without the standard library, it panics instead */
+fn halt() -> ! {
+    #[cfg(feature = "std")]
+    exit(1);
+
+    #[cfg(not(feature = "std"))]
+    panic!("Tape couldn't be set up");
+    }
+
+/* Helper function, for quick conversion of a pointer into an index */
+fn ptr_index<T>(pointer: &T) -> usize
+where T: TapePointer {
+    /* Unsafe note - unwrap is safe, because it was asserted during construction */
+    let ptr = pointer.to_usize();
+    unsafe {
+        ptr.unwrap_unchecked()
+        }
+    }
+
+/* Helper function, for quick conversion of an index into a pointer */
+fn index_ptr<T>(index: usize) -> T
+where T: TapePointer {
+    /* Unsafe note - unwrap is safe, because indices stay below the tape's length, which was asserted to fit the pointer */
+    let pointer = T::from_usize(index);
+    unsafe {
+        pointer.unwrap_unchecked()
+        }
+    }
+
+
+/* Container for pointer, and it's array */
+pub struct Tape<T, U> {
+    pointer: T,
+    array: Box<[U]>,
+    blank: U
+    }
+
+impl<T, U> Default for Tape<T, U>
+where T: TapePointer, U: TapeCell  {
+    /* Default constructor method - the tape spans the whole range of the pointer */
+    fn default() -> Self {
+        Self::with_size(tape_len::<T>())
+        }
+    }
+
+impl<T, U> Tape<T, U>
+where T: TapePointer, U: TapeCell  {
+    /* Constructor method, for a tape of the given number of cells - it can't be longer than the pointer's range */
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            pointer: T::ZERO,
+            array: repeat_n(U::ZERO, size)
+                .collect(),
+            blank: U::ZERO
+            }
+        }
+
+    /* Helper function, for quick conversion into a pointer */
+    fn ptr(&self) -> usize {
+        ptr_index(&self.pointer)
+        }
+
+    /* Helper function, for getting the index at an offset from the pointer - it wraps around the array's length */
+    fn ptr_at(&self, offset: isize) -> usize {
+        let len = self.array.len() as isize;
+        (self.ptr() as isize + offset % len).rem_euclid(len) as usize
+        }
+    }
+
+impl<T, U> TapeBackend<U> for Tape<T, U>
+where T: TapePointer, U: TapeCell  {
+    fn right(&mut self) {
+        self.pointer = index_ptr(self.ptr_at(1));
+        }
+    fn left(&mut self) {
+        self.pointer = index_ptr(self.ptr_at(-1));
+        }
+
+    fn increment(&mut self) {
+        let ptr = self.ptr();
+        self.array[ptr] = self.array[ptr].offset(1);
+        }
+    fn decrement(&mut self) {
+        let ptr = self.ptr();
+        self.array[ptr] = self.array[ptr].offset(-1);
+        }
+    fn add(&mut self, value: i16) {
+        let ptr = self.ptr();
+        self.array[ptr] = self.array[ptr].offset(i64::from(value));
+        }
+
+    fn shift(&mut self, value: isize) {
+        self.pointer = index_ptr(self.ptr_at(value));
+        }
+
+    fn add_at(&mut self, offset: isize, value: i16) {
+        let ptr = self.ptr_at(offset);
+        self.array[ptr] = self.array[ptr].offset(i64::from(value));
+        }
+    fn set_at(&mut self, offset: isize, value: U) {
+        let ptr = self.ptr_at(offset);
+        self.array[ptr] = value;
+        }
+
+    fn get(&self) -> U {
+        self.array[self.ptr()].clone()
+        }
+    fn set(&mut self, value: U) {
+        self.array[self.ptr()] = value;
+        }
+
+    fn is_zero(&self) -> bool {
+        self.array[self.ptr()] == U::ZERO
+        }
+
+    fn position(&self) -> isize {
+        self.ptr() as isize
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        T::from_isize(position)
+            .filter(|_| (position as usize) < self.array.len())
+            .map(|pointer| self.pointer = pointer)
+            .is_some()
+        }
+
+    fn cells(&self) -> Vec<(isize, U)> {
+        self.array.iter()
+            .enumerate()
+            .filter(|&(_, value)| *value != self.blank)
+            .map(|(i, value)| (i as isize, value.clone()))
+            .collect()
+        }
+
+    fn values(&self) -> (isize, Vec<U>) {
+        (0, self.array.to_vec())
+        }
+
+    fn clear(&mut self) {
+        self.pointer = T::ZERO;
+        self.array.fill(self.blank.clone());
+        }
+
+    fn fill(&mut self, value: U) {
+        self.array.fill(value.clone());
+        self.blank = value;
+        }
+
+    #[cfg(feature = "jit")]
+    fn raw_cells(&mut self) -> Option<&mut [U]> {
+        Some(&mut self.array)
+        }
+    }
+
+
+/* Container for pointer, and bit-packed array of 1-bit cells, exposed as values of the cell type */
+pub struct BitTape<T, U> {
+    pointer: T,
+    len: usize,
+    array: Box<[u64]>,
+    /* Word every cell of the array gets cleared to - either all zeros, or all ones */
+    blank: u64,
+    cell: PhantomData<U>
+    }
+
+impl<T, U> Default for BitTape<T, U>
+where T: TapePointer, U: TapeCell {
+    /* Default constructor method - the tape spans the whole range of the pointer */
+    fn default() -> Self {
+        Self::with_size(tape_len::<T>())
+        }
+    }
+
+impl<T, U> BitTape<T, U>
+where T: TapePointer, U: TapeCell {
+    /* Constructor method, for a tape of the given number of cells - it can't be longer than the pointer's range */
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            pointer: T::ZERO,
+            len: size,
+            array: repeat_n(0, size.div_ceil(u64::BITS as usize))
+                .collect(),
+            blank: 0,
+            cell: PhantomData
+            }
+        }
+
+    /* Helper function, for getting the word index, and bit mask of the current cell */
+    fn locate(&self) -> (usize, u64) {
+        let ptr = ptr_index(&self.pointer);
+        let bits = u64::BITS as usize;
+
+        (ptr / bits, 1 << (ptr % bits))
+        }
+
+    /* Flips the current cell - both incrementing, and decrementing a single bit wraps around */
+    fn flip(&mut self) {
+        let (word, mask) = self.locate();
+        self.array[word] ^= mask;
+        }
+    }
+
+impl<T, U> TapeBackend<U> for BitTape<T, U>
+where T: TapePointer, U: TapeCell {
+    fn right(&mut self) {
+        self.pointer = index_ptr((ptr_index(&self.pointer) + 1) % self.len);
+        }
+    fn left(&mut self) {
+        self.pointer = index_ptr((ptr_index(&self.pointer) + self.len - 1) % self.len);
+        }
+
+    fn increment(&mut self) {
+        self.flip();
+        }
+    fn decrement(&mut self) {
+        self.flip();
+        }
+    /* Adding an odd value to a single bit flips it */
+    fn add(&mut self, value: i16) {
+        if value % 2 != 0 {
+            self.flip();
+            }
+        }
+
+    fn get(&self) -> U {
+        match self.is_zero() {
+            true => U::ZERO,
+            false => U::one()
+            }
+        }
+    /* Only the lowest bit of the value is kept */
+    fn set(&mut self, value: U) {
+        let (word, mask) = self.locate();
+        let two = U::one() + U::one();
+
+        match value % two == U::ZERO {
+            true => self.array[word] &= ! mask,
+            false => self.array[word] |= mask
+            }
+        }
+
+    fn is_zero(&self) -> bool {
+        let (word, mask) = self.locate();
+        self.array[word] & mask == 0
+        }
+
+    fn position(&self) -> isize {
+        ptr_index(&self.pointer) as isize
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        T::from_isize(position)
+            .filter(|_| (position as usize) < self.len)
+            .map(|pointer| self.pointer = pointer)
+            .is_some()
+        }
+
+    fn cells(&self) -> Vec<(isize, U)> {
+        let bits = u64::BITS as usize;
+
+        self.array.iter()
+            .enumerate()
+            .flat_map(|(i, &word)| (0 .. bits)
+                .filter(move |bit| (word ^ self.blank) & (1 << bit) != 0)
+                .map(move |bit| (i * bits + bit, word & (1 << bit) != 0))
+                )
+            .take_while(|&(index, _)| index < self.len)
+            .map(|(index, set)| (index as isize, match set {
+                true => U::one(),
+                false => U::ZERO
+                }))
+            .collect()
+        }
+
+    /* Every bit gets its own value */
+    fn values(&self) -> (isize, Vec<U>) {
+        let bits = u64::BITS as usize;
+
+        let values = (0 .. self.len)
+            .map(|i| match self.array[i / bits] & (1 << (i % bits)) {
+                0 => U::ZERO,
+                _ => U::one()
+                })
+            .collect();
+
+        (0, values)
+        }
+
+    fn clear(&mut self) {
+        self.pointer = T::ZERO;
+        self.array.fill(self.blank);
+        }
+
+    /* Only the lowest bit of the value is kept */
+    fn fill(&mut self, value: U) {
+        let two = U::one() + U::one();
+
+        self.blank = match value % two == U::ZERO {
+            true => 0,
+            false => u64::MAX
+            };
+        self.array.fill(self.blank);
+        }
+    }
+
+
+/* Number of cells the growable tape's array can always grow up to, even when it's far from its end */
+const GROWTH_MIN: usize = 1 << 16;
+
+/* Container for pointer, and it's array allocated on demand - the pointer wraps around like on the fixed tape, but only the cells up to the furthest written one are stored */
+pub struct GrowableTape<T, U> {
+    pointer: T,
+    /* Number of cells the pointer wraps at - capped at the signed range, so every cell has a location */
+    len: usize,
+    array: Vec<U>,
+    /* Cells written too far past the array's end to double it up to them - they're moved into it, once it reaches them */
+    far: BTreeMap<usize, U>,
+    blank: U
+    }
+
+impl<T, U> Default for GrowableTape<T, U>
+where T: TapePointer, U: TapeCell {
+    /* Default constructor method - the tape spans the whole range of the pointer, but nothing gets allocated until the first write */
+    fn default() -> Self {
+        /* Assertion halting the execution in case of invalid pointer size, as on the fixed tape */
+        let Some(max) = T::MAX.to_usize() else {
+            error!("Couldn't safely convert to the intended pointer size");
+            halt();
+            };
+
+        Self {
+            pointer: T::ZERO,
+            len: max.min(isize::MAX as usize) + 1,
+            array: Vec::new(),
+            far: BTreeMap::new(),
+            blank: U::ZERO
+            }
+        }
+    }
+
+impl<T, U> GrowableTape<T, U>
+where T: TapePointer, U: TapeCell {
+    /* Constructor method, for a tape of the given number of cells - it can't be longer than the pointer's range */
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            pointer: T::ZERO,
+            len: size.min(isize::MAX as usize + 1),
+            array: Vec::new(),
+            far: BTreeMap::new(),
+            blank: U::ZERO
+            }
+        }
+
+    /* Helper function, for quick conversion into a pointer */
+    fn ptr(&self) -> usize {
+        ptr_index(&self.pointer)
+        }
+
+    /* Helper function, for getting the pointer at an offset from the current one - it wraps around the tape's length */
+    fn ptr_at(&self, offset: isize) -> T {
+        let ptr = self.ptr();
+        let distance = offset.unsigned_abs() % self.len;
+
+        /* Neither sum overflows, as both parts are below the length, which is at most half of the range */
+        let index = match offset > 0 {
+            true => ptr + distance,
+            false => ptr + self.len - distance
+            };
+
+        /* Unsafe note - unwrap is safe, because the index is below the tape's length, which fits the pointer */
+        let pointer = T::from_usize(index % self.len);
+        unsafe {
+            pointer.unwrap_unchecked()
+            }
+        }
+
+    /* Get the current cell for writing, doubling the array until it reaches the cell - the cells further away are kept apart, and it halts the execution, when the array can't be allocated */
+    fn cell_mut(&mut self) -> &mut U {
+        let ptr = self.ptr();
+
+        if ptr >= self.array.len() {
+            let len = ptr.checked_add(1)
+                .and_then(usize::checked_next_power_of_two)
+                .unwrap_or(usize::MAX)
+                .min(self.len);
+
+            if len > GROWTH_MIN.max(2 * self.array.len()) {
+                return self.far.entry(ptr)
+                    .or_insert_with(|| self.blank.clone());
+                }
+
+            if self.array.try_reserve_exact(len - self.array.len()).is_err() {
+                error!("Couldn't grow the tape up to the cell at {ptr}");
+                halt();
+                }
+            self.array.resize(len, self.blank.clone());
+
+            /* Cells kept apart, which the array reaches now, are moved into it */
+            let further = self.far.split_off(&len);
+            for (index, value) in replace(&mut self.far, further) {
+                self.array[index] = value;
+                }
+            }
+
+        &mut self.array[ptr]
+        }
+    }
+
+impl<T, U> TapeBackend<U> for GrowableTape<T, U>
+where T: TapePointer, U: TapeCell {
+    fn right(&mut self) {
+        self.pointer = self.ptr_at(1);
+        }
+    fn left(&mut self) {
+        self.pointer = self.ptr_at(-1);
+        }
+
+    fn increment(&mut self) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(1);
+        }
+    fn decrement(&mut self) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(-1);
+        }
+    fn add(&mut self, value: i16) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(i64::from(value));
+        }
+
+    fn shift(&mut self, value: isize) {
+        self.pointer = self.ptr_at(value);
+        }
+
+    /* Cells which were never written hold the fill value */
+    fn get(&self) -> U {
+        let ptr = self.ptr();
+
+        self.array.get(ptr)
+            .or_else(|| self.far.get(&ptr))
+            .cloned()
+            .unwrap_or_else(|| self.blank.clone())
+        }
+    fn set(&mut self, value: U) {
+        *self.cell_mut() = value;
+        }
+
+    fn is_zero(&self) -> bool {
+        self.get() == U::ZERO
+        }
+
+    /* Every location is within the signed range, so the negative ones are off the tape */
+    fn position(&self) -> isize {
+        self.ptr() as isize
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        T::from_isize(position)
+            .filter(|_| (position as usize) < self.len)
+            .map(|pointer| self.pointer = pointer)
+            .is_some()
+        }
+
+    /* Cells kept apart are all past the array's end, so the order stays ascending */
+    fn cells(&self) -> Vec<(isize, U)> {
+        self.array.iter()
+            .enumerate()
+            .chain(self.far.iter()
+                .map(|(&i, value)| (i, value))
+                )
+            .filter(|&(_, value)| *value != self.blank)
+            .map(|(i, value)| (i as isize, value.clone()))
+            .collect()
+        }
+
+    /* Only the cells up to the furthest written one are stored, so the values end before the pointer's bound - halts the execution, when they can't be allocated */
+    fn values(&self) -> (isize, Vec<U>) {
+        let mut values = self.array.clone();
+
+        if let Some((&last, _)) = self.far.last_key_value() {
+            if values.try_reserve_exact(last + 1 - values.len()).is_err() {
+                error!("Couldn't hold every cell up to the one at {last}");
+                halt();
+                }
+            values.resize(last + 1, self.blank.clone());
+
+            for (&index, value) in self.far.iter() {
+                values[index] = value.clone();
+                }
+            }
+
+        (0, values)
+        }
+
+    /* The array shrinks back to nothing, but its capacity stays for the next run */
+    fn clear(&mut self) {
+        self.pointer = T::ZERO;
+        self.array.clear();
+        self.far.clear();
+        }
+
+    /* Cells kept apart get the value as well, so they're the fill value now */
+    fn fill(&mut self, value: U) {
+        self.array.fill(value.clone());
+        self.far.clear();
+        self.blank = value;
+        }
+    }
+
+
+/* Container for a tape growing in both directions, with cells at negative indices */
+pub struct InfiniteTape<U> {
+    pointer: isize,
+    /* Cells at indices 0, 1, 2, ... */
+    right: Vec<U>,
+    /* Cells at indices -1, -2, -3, ... */
+    left: Vec<U>,
+    blank: U
+    }
+
+impl<U> Default for InfiniteTape<U>
+where U: TapeCell {
+    /* Default constructor method */
+    fn default() -> Self {
+        Self {
+            pointer: 0,
+            right: Vec::new(),
+            left: Vec::new(),
+            blank: U::ZERO
+            }
+        }
+    }
+
+impl<U> InfiniteTape<U>
+where U: TapeCell {
+    /* Helper function, for getting the side of the tape, and index within it - `!ptr` maps -1, -2, ... onto 0, 1, ... */
+    const fn locate(&self) -> (bool, usize) {
+        match self.pointer {
+            ptr @ 0 .. => (true, ptr as usize),
+            ptr => (false, ! ptr as usize)
+            }
+        }
+
+    /* Get the current cell for writing, growing the tape if it was not reached yet */
+    fn cell_mut(&mut self) -> &mut U {
+        let (is_right, index) = self.locate();
+        let side = match is_right {
+            true => &mut self.right,
+            false => &mut self.left
+            };
+
+        if index >= side.len() {
+            side.resize(index + 1, self.blank.clone());
+            }
+
+        &mut side[index]
+        }
+    }
+
+impl<U> TapeBackend<U> for InfiniteTape<U>
+where U: TapeCell {
+    fn right(&mut self) {
+        self.pointer += 1;
+        }
+    fn left(&mut self) {
+        self.pointer -= 1;
+        }
+
+    fn increment(&mut self) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(1);
+        }
+    fn decrement(&mut self) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(-1);
+        }
+    fn add(&mut self, value: i16) {
+        let cell = self.cell_mut();
+        *cell = cell.offset(i64::from(value));
+        }
+
+    /* Cells which were never written hold the fill value */
+    fn get(&self) -> U {
+        let (is_right, index) = self.locate();
+        let side = match is_right {
+            true => &self.right,
+            false => &self.left
+            };
+
+        side.get(index)
+            .cloned()
+            .unwrap_or_else(|| self.blank.clone())
+        }
+    fn set(&mut self, value: U) {
+        *self.cell_mut() = value;
+        }
+
+    fn is_zero(&self) -> bool {
+        self.get() == U::ZERO
+        }
+
+    fn position(&self) -> isize {
+        self.pointer
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        self.pointer = position;
+        true
+        }
+
+    /* Cells at negative indices are listed from the furthest one, to keep the order ascending */
+    fn cells(&self) -> Vec<(isize, U)> {
+        let left = self.left.iter()
+            .enumerate()
+            .rev()
+            .map(|(i, value)| (! (i as isize), value.clone()));
+        let right = self.right.iter()
+            .enumerate()
+            .map(|(i, value)| (i as isize, value.clone()));
+
+        left.chain(right)
+            .filter(|(_, value)| *value != self.blank)
+            .collect()
+        }
+
+    /* Only the reached cells are stored, so the values start at the furthest one on the left */
+    fn values(&self) -> (isize, Vec<U>) {
+        let values = self.left.iter()
+            .rev()
+            .chain(&self.right)
+            .cloned()
+            .collect();
+
+        (- (self.left.len() as isize), values)
+        }
+
+    /* Both sides shrink back to nothing, but their capacity stays for the next run */
+    fn clear(&mut self) {
+        self.pointer = 0;
+        self.right.clear();
+        self.left.clear();
+        }
+
+    fn fill(&mut self, value: U) {
+        self.right.fill(value.clone());
+        self.left.fill(value.clone());
+        self.blank = value;
+        }
+    }
+
+
+/* Container for a pointer, and cells shared with the other clones - every access locks the cells, so the Interpreters over them can run on separate threads */
+#[cfg(feature = "std")]
+pub struct SharedTape<U> {
+    pointer: usize,
+    cells: Arc<Mutex<SharedCells<U>>>
+    }
+
+/* Cells of the shared tape, with the value they get cleared to */
+#[cfg(feature = "std")]
+struct SharedCells<U> {
+    array: Box<[U]>,
+    blank: U
+    }
+
+#[cfg(feature = "std")]
+impl<U> Clone for SharedTape<U> {
+    /* Clones share the cells, but move their own pointer - starting at the original's location */
+    fn clone(&self) -> Self {
+        Self {
+            pointer: self.pointer,
+            cells: Arc::clone(&self.cells)
+            }
+        }
+    }
+
+#[cfg(feature = "std")]
+impl<U> SharedTape<U>
+where U: TapeCell {
+    /* Constructor method, for a tape of the given number of cells - the pointers wrap around at its end */
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            pointer: 0,
+            cells: Arc::new(Mutex::new(SharedCells {
+                array: repeat_n(U::ZERO, size.max(1))
+                    .collect(),
+                blank: U::ZERO
+                }))
+            }
+        }
+
+    /* Helper function, for locking the cells - a panicking holder can't leave them half-written, so the poisoning is ignored */
+    fn lock(&self) -> MutexGuard<'_, SharedCells<U>> {
+        self.cells.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+        }
+
+    /* Helper function, for getting the index at an offset from the pointer - it wraps around the array's length */
+    fn ptr_at(&self, offset: isize) -> usize {
+        let len = self.lock().array.len() as isize;
+        (self.pointer as isize + offset % len).rem_euclid(len) as usize
+        }
+    }
+
+#[cfg(feature = "std")]
+impl<U> TapeBackend<U> for SharedTape<U>
+where U: TapeCell {
+    fn right(&mut self) {
+        self.pointer = self.ptr_at(1);
+        }
+    fn left(&mut self) {
+        self.pointer = self.ptr_at(-1);
+        }
+
+    fn increment(&mut self) {
+        self.add(1);
+        }
+    fn decrement(&mut self) {
+        self.add(-1);
+        }
+    fn add(&mut self, value: i16) {
+        let ptr = self.pointer;
+        let cells = &mut self.lock().array;
+        cells[ptr] = cells[ptr].offset(i64::from(value));
+        }
+
+    fn shift(&mut self, value: isize) {
+        self.pointer = self.ptr_at(value);
+        }
+
+    fn add_at(&mut self, offset: isize, value: i16) {
+        let ptr = self.ptr_at(offset);
+        let cells = &mut self.lock().array;
+        cells[ptr] = cells[ptr].offset(i64::from(value));
+        }
+    fn set_at(&mut self, offset: isize, value: U) {
+        let ptr = self.ptr_at(offset);
+        self.lock().array[ptr] = value;
+        }
+
+    fn get(&self) -> U {
+        self.lock().array[self.pointer].clone()
+        }
+    fn set(&mut self, value: U) {
+        let ptr = self.pointer;
+        self.lock().array[ptr] = value;
+        }
+
+    fn is_zero(&self) -> bool {
+        self.lock().array[self.pointer] == U::ZERO
+        }
+
+    fn position(&self) -> isize {
+        self.pointer as isize
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        usize::try_from(position)
+            .ok()
+            .filter(|&position| position < self.lock().array.len())
+            .map(|position| self.pointer = position)
+            .is_some()
+        }
+
+    fn cells(&self) -> Vec<(isize, U)> {
+        let cells = self.lock();
+
+        cells.array.iter()
+            .enumerate()
+            .filter(|&(_, value)| *value != cells.blank)
+            .map(|(i, value)| (i as isize, value.clone()))
+            .collect()
+        }
+
+    fn values(&self) -> (isize, Vec<U>) {
+        (0, self.lock().array.to_vec())
+        }
+
+    /* The cells are cleared for every clone */
+    fn clear(&mut self) {
+        self.pointer = 0;
+
+        let cells = &mut *self.lock();
+        cells.array.fill(cells.blank.clone());
+        }
+
+    fn fill(&mut self, value: U) {
+        let cells = &mut *self.lock();
+        cells.array.fill(value.clone());
+        cells.blank = value;
+        }
+    }
 
-impl<T> TapeCell for T where T:
-    Sized + Copy + UpperHex + From<u8> + ToString + FromStr +
-    Unsigned + ConstZero + ConstOne + WrappingAdd + WrappingSub + ToPrimitive + ToBytes {}
 
-/* Container for pointer, and it's array */
-pub struct Tape<T, U> {
-    pointer: T,
-    array: Box<[U]>
+/* Container for several tapes, each with its own pointer - only the current one gets accessed, and the switches wrap around the last one - the cells, and values of the other tapes are only reached through the all cells */
+pub struct MultiTape<U> {
+    tapes: Vec<Box<dyn TapeBackend<U>>>,
+    current: usize
     }
 
-impl<T, U> Default for Tape<T, U>
-where T: TapePointer, U: TapeCell  {
-    /* Default constructor method */
-    fn default() -> Self {
-        /* Declaration of size, with additional assertion to halt the execution in case of invalid pointer size */
-        let Some(size) = T::MAX
-            .to_usize()
-            .and_then(|e| e.checked_add(1))
-        else {
-            error!("Couldn't safely convert to the intended pointer size");
-            exit(1);
-            };
-
-        /* Struct declaration */
-        Self {
-            pointer: T::ZERO,
-            array: repeat_n(U::ZERO, size)
-                .collect()
+impl<U> MultiTape<U> {
+    /* Constructor function, starting on the first tape - none, when there's no tape */
+    pub fn new(tapes: Vec<Box<dyn TapeBackend<U>>>) -> Option<Self> {
+        match tapes.is_empty() {
+            true => None,
+            false => Some(Self {
+                tapes,
+                current: 0
+                })
             }
         }
+
+    /* Get the index of the current tape */
+    #[inline]
+    pub const fn current(&self) -> usize {
+        self.current
+        }
     }
 
-impl<T, U> Tape<T, U>
-where T: TapePointer, U: TapeCell  {
-    /* Helper function, for quick conversion into a pointer */
-    fn ptr(&self) -> usize {
-        /* Unsafe note - unwrap is safe, because it was asserted earlier */
-        let ptr = self.pointer.to_usize();
-        unsafe {
-            ptr.unwrap_unchecked()
-            }
+impl<U> TapeBackend<U> for MultiTape<U>
+where U: TapeCell {
+    fn right(&mut self) {
+        self.tapes[self.current].right();
+        }
+    fn left(&mut self) {
+        self.tapes[self.current].left();
         }
 
-    /* Moves pointer to the right, logical equivalent to '>' */
-    pub fn right(&mut self) {
-        self.pointer = self.pointer.wrapping_add(&T::ONE);
+    fn increment(&mut self) {
+        self.tapes[self.current].increment();
         }
-    /* Moves pointer to the left, logical equivalent to '<' */
-    pub fn left(&mut self) {
-        self.pointer = self.pointer.wrapping_sub(&T::ONE);
+    fn decrement(&mut self) {
+        self.tapes[self.current].decrement();
+        }
+    fn add(&mut self, value: i16) {
+        self.tapes[self.current].add(value);
         }
 
-    /* Increments cell at the current pointer location, logical equivalent to '+' */
-    pub fn increment(&mut self) {
-        let ptr = self.ptr();
-        self.array[ptr] = self.array[ptr].wrapping_add(&U::ONE);
+    fn shift(&mut self, value: isize) {
+        self.tapes[self.current].shift(value);
         }
-    /* Decrements cell at the current pointer location, logical equivalent to '-' */
-    pub fn decrement(&mut self) {
-        let ptr = self.ptr();
-        self.array[ptr] = self.array[ptr].wrapping_sub(&U::ONE);
+
+    fn add_at(&mut self, offset: isize, value: i16) {
+        self.tapes[self.current].add_at(offset, value);
+        }
+    fn set_at(&mut self, offset: isize, value: U) {
+        self.tapes[self.current].set_at(offset, value);
         }
 
-    /* Get cell value at the current pointer location */
-    pub fn get(&self) -> U {
-        self.array[self.ptr()]
+    fn get(&self) -> U {
+        self.tapes[self.current].get()
         }
-    /* Set cell value at the current pointer location */
-    pub fn set(&mut self, value: U) {
-        self.array[self.ptr()] = value;
+    fn set(&mut self, value: U) {
+        self.tapes[self.current].set(value);
         }
 
-    /* Check whether cell value at the current pointer location is equal to zero */
-    pub fn is_zero(&self) -> bool {
-        self.array[self.ptr()] == U::ZERO
+    fn is_zero(&self) -> bool {
+        self.tapes[self.current].is_zero()
+        }
+
+    fn position(&self) -> isize {
+        self.tapes[self.current].position()
+        }
+    fn seek(&mut self, position: isize) -> bool {
+        self.tapes[self.current].seek(position)
+        }
+
+    fn cells(&self) -> Vec<(isize, U)> {
+        self.tapes[self.current].cells()
+        }
+    fn all_cells(&self) -> Vec<(usize, isize, U)> {
+        self.tapes.iter()
+            .enumerate()
+            .flat_map(|(tape, cells)| cells.cells()
+                .into_iter()
+                .map(move |(index, value)| (tape, index, value))
+                )
+            .collect()
+        }
+
+    fn values(&self) -> (isize, Vec<U>) {
+        self.tapes[self.current].values()
+        }
+
+    /* Every tape gets cleared, and the first one becomes the current one */
+    fn clear(&mut self) {
+        for tape in self.tapes.iter_mut() {
+            tape.clear();
+            }
+        self.current = 0;
+        }
+
+    fn fill(&mut self, value: U) {
+        for tape in self.tapes.iter_mut() {
+            tape.fill(value.clone());
+            }
+        }
+
+    fn switch(&mut self, offset: isize) {
+        let len = self.tapes.len() as isize;
+        self.current = (self.current as isize + offset % len).rem_euclid(len) as usize;
+        }
+    fn tape_count(&self) -> usize {
+        self.tapes.len()
         }
     }
 
@@ -125,6 +1197,202 @@ mod test {
         assert_eq!(tape.get(), 0);
         }
 
+    #[test]
+    fn tape_add_shift() {
+        let mut tape = Tape::<u8, u8>::default();
+
+        tape.add(-3);
+        tape.shift(-2);
+        tape.add(300);
+
+        assert_eq!(tape.position(), 254);
+        assert_eq!(tape.get(), 44);
+
+        tape.shift(514);
+
+        assert_eq!(tape.position(), 0);
+        assert_eq!(tape.get(), 253);
+        }
+
+    #[test]
+    fn tape_add_set_at() {
+        let mut tape = Tape::<u8, u8>::default();
+
+        tape.add_at(-1, 5);
+        tape.set_at(258, 7);
+        tape.shift(-1);
+
+        assert_eq!(tape.get(), 5);
+
+        tape.shift(3);
+
+        assert_eq!(tape.get(), 7);
+        }
+
+    #[test]
+    fn growable_tape_add_shift() {
+        let mut tape = GrowableTape::<u8, u8>::default();
+
+        assert_eq!(tape.array.len(), 0);
+
+        tape.add(-3);
+        tape.shift(-2);
+        tape.add(300);
+
+        assert_eq!(tape.position(), 254);
+        assert_eq!(tape.get(), 44);
+        assert_eq!(tape.array.len(), 256);
+
+        tape.shift(514);
+
+        assert_eq!(tape.position(), 0);
+        assert_eq!(tape.get(), 253);
+
+        tape.clear();
+        tape.shift(5);
+        tape.increment();
+
+        assert_eq!(tape.array.len(), 8);
+        assert_eq!(tape.cells(), vec![(5, 1)]);
+        }
+
+    #[test]
+    fn growable_tape_far_cells() {
+        let mut tape = GrowableTape::<u64, u8>::default();
+
+        tape.increment();
+        tape.left();
+        tape.add(2);
+
+        assert_eq!(tape.position(), isize::MAX);
+        assert_eq!(tape.get(), 2);
+        assert_eq!(tape.array.len(), 1);
+        assert_eq!(tape.cells(), vec![(0, 1), (isize::MAX, 2)]);
+        assert!(! tape.seek(-1));
+
+        let mut tape = GrowableTape::<u32, u8>::default();
+
+        tape.left();
+        tape.increment();
+        tape.seek(70000);
+        tape.increment();
+        tape.seek(40000);
+        tape.increment();
+
+        assert_eq!(tape.array.len(), 1 << 16);
+        assert_eq!(tape.far.len(), 2);
+        assert_eq!(tape.cells(), vec![(40000, 1), (70000, 1), (u32::MAX as isize, 1)]);
+
+        /* Array doubles up to the cell at 100000, and takes the one at 70000 along */
+        tape.seek(100000);
+        tape.increment();
+
+        assert_eq!(tape.array.len(), 1 << 17);
+        assert_eq!(tape.far.len(), 1);
+        assert_eq!(tape.cells(), vec![(40000, 1), (70000, 1), (100000, 1), (u32::MAX as isize, 1)]);
+
+        tape.fill(3);
+
+        assert!(tape.cells().is_empty());
+        assert_eq!(tape.values(), (0, vec![3; 1 << 17]));
+        }
+
+    #[test]
+    fn tapes_with_size_wrap() {
+        let mut tape = Tape::<u16, u8>::with_size(5);
+        let mut bit_tape = BitTape::<u16, u8>::with_size(5);
+        let mut growable_tape = GrowableTape::<u16, u8>::with_size(5);
+
+        tape.left();
+        bit_tape.left();
+        growable_tape.left();
+
+        assert_eq!(tape.position(), 4);
+        assert_eq!(bit_tape.position(), 4);
+        assert_eq!(growable_tape.position(), 4);
+
+        tape.shift(-12);
+        bit_tape.right();
+        growable_tape.shift(7);
+        growable_tape.increment();
+
+        assert_eq!(tape.position(), 2);
+        assert_eq!(bit_tape.position(), 0);
+        assert_eq!(growable_tape.position(), 1);
+        assert_eq!(growable_tape.array.len(), 2);
+        assert!(! tape.seek(5));
+        assert_eq!(bit_tape.values().1.len(), 5);
+        }
+
+    #[test]
+    fn signed_cells_wrap() {
+        let mut tape = Tape::<u8, i8>::default();
+
+        tape.decrement();
+
+        assert_eq!(tape.get(), -1);
+        assert_eq!(tape.get().widen(), 0xFF);
+        assert_eq!(i8::narrow(0xFF), Some(-1));
+        assert_eq!(i8::narrow(0x100), None);
+
+        tape.add(-127);
+
+        assert_eq!(tape.get(), i8::MIN);
+        assert_eq!(tape.get().checked_offset(-1), None);
+
+        tape.decrement();
+
+        assert_eq!(tape.get(), i8::MAX);
+        assert_eq!(tape.cells(), vec![(0, i8::MAX)]);
+        }
+
+    #[test]
+    fn big_cells_unbounded() {
+        let mut tape = InfiniteTape::<BigUint>::default();
+
+        tape.decrement();
+
+        assert_eq!(tape.get(), BigUint::ZERO);
+
+        tape.set(BigUint::from(u64::MAX));
+        tape.increment();
+
+        assert_eq!(tape.get(), BigUint::from(u64::MAX) + 1_u8);
+
+        tape.right();
+        tape.add(-300);
+        tape.set(tape.get().add_product(&BigUint::from(u64::MAX), 4));
+
+        assert_eq!(tape.get(), BigUint::from(u64::MAX) * 4_u8);
+        assert_eq!(tape.get().le_bytes(), [0xFC, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x03]);
+        assert_eq!(BigUint::from_exact(&tape.get().exact()), Some(tape.get()));
+        assert_eq!(tape.get().widen(), 0xFFFF_FFFF_FFFF_FFFC);
+        }
+
+    #[test]
+    fn exact_cells() {
+        let wide = BigUint::from(u64::MAX) + 1_u8;
+
+        assert_eq!((-1_i8).exact(), BigUint::from(0xFF_u8));
+        assert_eq!(i8::from_exact(&BigUint::from(0x80_u8)), Some(i8::MIN));
+        assert_eq!(u16::from_exact(&BigUint::from(0x1_0000_u32)), None);
+        assert_eq!(u64::from_exact(&wide), None);
+        }
+
+    #[test]
+    fn bit_tape_add_shift() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.add(3);
+        tape.shift(-1);
+
+        assert_eq!(tape.position(), 255);
+
+        tape.shift(1);
+
+        assert_eq!(tape.get(), 1);
+        }
+
     #[test]
     fn tape_cell_size_u8() {
         let mut tape = Tape::<u8, u8>::default();
@@ -218,4 +1486,265 @@ mod test {
 
         assert_eq!(array.len(), length);
         }
+
+    #[test]
+    fn bit_tape_basic() {
+        let tape = BitTape::<u8, u8>::default();
+
+        assert_eq!(tape.get(), 0);
+        }
+
+    #[test]
+    fn bit_tape_cell_wrap() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.increment();
+        assert_eq!(tape.get(), 1);
+
+        tape.increment();
+        assert_eq!(tape.get(), 0);
+
+        tape.decrement();
+        assert_eq!(tape.get(), 1);
+        }
+
+    #[test]
+    fn bit_tape_set_low_bit() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.set(3);
+        assert_eq!(tape.get(), 1);
+
+        tape.set(2);
+        assert_eq!(tape.get(), 0);
+        }
+
+    #[test]
+    fn bit_tape_packed_cells() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        /* Set every other cell across word boundaries */
+        (0 ..= u8::MAX).for_each(|i| {
+            tape.set(i % 2);
+            tape.right();
+            });
+
+        let BitTape { ref array, .. } = tape;
+
+        assert_eq!(array.len(), 4);
+        assert!(array.iter().all(|&word| word == 0xAAAA_AAAA_AAAA_AAAA));
+        }
+
+    #[test]
+    fn bit_tape_ptr_wrap_u8() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.set(1);
+
+        (0 ..= u8::MAX).for_each(|_| tape.right());
+
+        assert_eq!(tape.get(), 1);
+        }
+
+    #[test]
+    fn infinite_tape_basic() {
+        let tape = InfiniteTape::<u8>::default();
+
+        assert_eq!(tape.get(), 0);
+        assert_eq!(tape.pointer, 0);
+        }
+
+    #[test]
+    fn infinite_tape_negative() {
+        let mut tape = InfiniteTape::<u8>::default();
+
+        tape.left();
+        tape.increment();
+
+        assert_eq!(tape.pointer, -1);
+        assert_eq!(tape.get(), 1);
+
+        tape.right();
+
+        assert_eq!(tape.get(), 0);
+        }
+
+    #[test]
+    fn infinite_tape_no_wrap() {
+        let mut tape = InfiniteTape::<u8>::default();
+
+        let value = 69;
+
+        tape.set(value);
+
+        (0 ..= u16::MAX).for_each(|_| tape.right());
+
+        assert_eq!(tape.get(), 0);
+
+        (0 ..= u16::MAX).for_each(|_| tape.left());
+
+        assert_eq!(tape.get(), value);
+        }
+
+    #[test]
+    fn infinite_tape_grows_lazily() {
+        let mut tape = InfiniteTape::<u8>::default();
+
+        (0 .. 100).for_each(|_| tape.left());
+
+        assert!(tape.is_zero());
+        assert!(tape.left.is_empty());
+
+        tape.decrement();
+
+        assert_eq!(tape.get(), u8::MAX);
+        assert_eq!(tape.left.len(), 100);
+        assert!(tape.right.is_empty());
+        }
+
+    #[test]
+    fn tape_cells_seek() {
+        let mut tape = Tape::<u8, u16>::default();
+
+        assert!(tape.seek(200));
+        tape.set(500);
+        assert!(tape.seek(3));
+        tape.set(7);
+
+        assert_eq!(tape.position(), 3);
+        assert_eq!(tape.cells(), vec![(3, 7), (200, 500)]);
+        assert!(! tape.seek(256));
+        assert!(! tape.seek(-1));
+        }
+
+    #[test]
+    fn bit_tape_cells_seek() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        assert!(tape.seek(130));
+        tape.increment();
+        assert!(tape.seek(1));
+        tape.increment();
+
+        assert_eq!(tape.position(), 1);
+        assert_eq!(tape.cells(), vec![(1, 1), (130, 1)]);
+        }
+
+    #[test]
+    fn infinite_tape_cells_seek() {
+        let mut tape = InfiniteTape::<u8>::default();
+
+        assert!(tape.seek(-3));
+        tape.set(1);
+        assert!(tape.seek(-1));
+        tape.set(2);
+        assert!(tape.seek(4));
+        tape.set(3);
+
+        assert_eq!(tape.position(), 4);
+        assert_eq!(tape.cells(), vec![(-3, 1), (-1, 2), (4, 3)]);
+        }
+
+    #[test]
+    fn tape_values() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.right();
+        tape.increment();
+
+        assert_eq!(tape.values().1.len(), 256);
+        assert_eq!(tape.values().1[.. 3], [0, 1, 0]);
+
+        let mut tape = InfiniteTape::<u8>::default();
+
+        tape.seek(-2);
+        tape.set(1);
+        tape.seek(1);
+        tape.set(2);
+
+        assert_eq!(tape.values(), (-2, vec![1, 0, 0, 2]));
+        }
+
+    #[test]
+    fn tape_clear() {
+        let mut tape = Tape::<u8, u8>::default();
+
+        tape.increment();
+        tape.right();
+        tape.increment();
+        tape.clear();
+
+        assert_eq!(tape.position(), 0);
+        assert!(tape.cells().is_empty());
+        }
+
+    #[test]
+    fn bit_tape_clear() {
+        let mut tape = BitTape::<u8, u8>::default();
+
+        tape.right();
+        tape.increment();
+        tape.clear();
+
+        assert_eq!(tape.position(), 0);
+        assert!(tape.cells().is_empty());
+        }
+
+    #[test]
+    fn bit_tape_fill() {
+        let mut tape = BitTape::<u8, u8>::with_size(70);
+
+        tape.fill(3);
+        tape.seek(66);
+        tape.decrement();
+
+        assert_eq!(tape.cells(), vec![(66, 0)]);
+        assert!(tape.values().1.iter().filter(|&&value| value == 1).count() == 69);
+
+        tape.clear();
+
+        assert!(tape.cells().is_empty());
+        assert_eq!(tape.get(), 1);
+        }
+
+    #[test]
+    fn multi_tape_switch() {
+        let mut tape = MultiTape::<u8>::new(vec![
+            Box::new(Tape::<u8, u8>::default()),
+            Box::new(InfiniteTape::<u8>::default())
+            ])
+            .expect("Unreachable");
+
+        tape.increment();
+        tape.switch(-1);
+        tape.left();
+        tape.add(2);
+
+        assert_eq!((tape.current(), tape.position(), tape.get()), (1, -1, 2));
+
+        tape.switch(3);
+
+        assert_eq!((tape.current(), tape.position(), tape.get()), (0, 0, 1));
+        assert_eq!(tape.tape_count(), 2);
+        assert_eq!(tape.cells(), vec![(0, 1)]);
+        assert_eq!(tape.all_cells(), vec![(0, 0, 1), (1, -1, 2)]);
+
+        tape.switch(1);
+        tape.clear();
+
+        assert_eq!((tape.current(), tape.position()), (0, 0));
+        assert!(MultiTape::<u8>::new(Vec::new()).is_none());
+        }
+
+    #[test]
+    fn infinite_tape_clear() {
+        let mut tape = InfiniteTape::<u8>::default();
+
+        tape.left();
+        tape.increment();
+        tape.clear();
+
+        assert_eq!(tape.position(), 0);
+        assert!(tape.cells().is_empty());
+        }
     }
\ No newline at end of file
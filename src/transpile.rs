@@ -0,0 +1,576 @@
+use crate::{
+    codegen::*,
+    eval::*,
+    ir::*,
+    utils::*
+    };
+
+
+/* Helper container for writing indented source code */
+struct Source {
+    code: String,
+    depth: usize
+    }
+
+impl Source {
+    /* Write a single line, at the current depth - empty lines aren't indented */
+    fn line(&mut self, line: &str) {
+        if ! line.is_empty() {
+            for _ in 0 .. self.depth {
+                self.code.push_str("    ");
+                }
+            }
+        self.code.push_str(line);
+        self.code.push('\n');
+        }
+    }
+
+/* Function for truncating a run's net change to the given width - sign-extended, so it's the shorter of an addition, or a subtraction */
+const fn truncate_run(value: i64, bits: u32) -> i64 {
+    let shift = i64::BITS - bits;
+    value << shift >> shift
+    }
+
+/* Function for getting a run's statement in C - `+=`, or `-=` */
+fn run_statement(target: &str, value: i64, bits: u32) -> Option<String> {
+    match truncate_run(value, bits) {
+        0 => None,
+        value @ 1 .. => Some(format!("{target} += {value};")),
+        value => Some(format!("{target} -= {};", value.unsigned_abs()))
+        }
+    }
+
+/* Function for getting a run's statement in Rust - checked runs only fail, when their net change overflows */
+fn run_method(target: &str, value: i64, bits: u32, checked: bool, error: &str) -> Option<String> {
+    let value = match checked {
+        true => value,
+        false => truncate_run(value, bits)
+        };
+    let (method, magnitude) = match value {
+        0 => return None,
+        1 .. => ("add", value.unsigned_abs()),
+        _ => ("sub", value.unsigned_abs())
+        };
+
+    Some(match checked {
+        /* Changes wider than the type would overflow on any value */
+        true if magnitude >> bits != 0 => format!("panic!(\"{error}\");"),
+        true => format!("{target} = {target}.checked_{method}({magnitude}).expect(\"{error}\");"),
+        false => format!("{target} = {target}.wrapping_{method}({magnitude});")
+        })
+    }
+
+/* Function for getting the index of a cell at an offset in C - the cast wraps it around the tape */
+fn offset_index(offset: isize) -> String {
+    match offset {
+        0 .. => format!("(index_t) (p + {offset})"),
+        _ => format!("(index_t) (p - {})", offset.unsigned_abs())
+        }
+    }
+
+/* Function for getting the cell at an offset from the pointer in Rust - none, if the checked pointer could never reach it */
+fn cell_rust(offset: isize, options: &CodegenOptions, checked: bool) -> Option<String> {
+    let pointer_bits = options.pointer_bits();
+
+    match checked {
+        true => {
+            let (method, distance) = match offset {
+                0 .. => ("add", offset.unsigned_abs() as u64),
+                _ => ("sub", offset.unsigned_abs() as u64)
+                };
+
+            (distance >> pointer_bits == 0)
+                .then(|| format!("tape[p.checked_{method}({distance}).expect(\"Pointer overflow\") as usize]"))
+            },
+        false => Some(match truncate_run(offset as i64, pointer_bits) {
+            distance @ 0 .. => format!("tape[p.wrapping_add({distance}) as usize]"),
+            distance => format!("tape[p.wrapping_sub({}) as usize]", distance.unsigned_abs())
+            })
+        }
+    }
+
+/* Function for getting a multiplication's statement in C - the factor's sign is kept, so the product fits an int for the narrow cells */
+fn mul_add_statement(offset: isize, factor: i16, options: &CodegenOptions) -> Option<String> {
+    let target = format!("tape[{}]", offset_index(offset));
+
+    match options.cell_size {
+        /* Bits are flipped by odd factors only, and the cell is the product itself */
+        CellSize::U1 => (factor % 2 != 0)
+            .then(|| format!("{target} ^= tape[p];")),
+        _ => (factor != 0)
+            .then(|| format!("{target} += tape[p] * {factor};"))
+        }
+    }
+
+/* Function for getting a multiplication's statements in Rust - checked ones only run for a non-zero cell, like the loop, and fail when the target overflows */
+fn mul_add_method(offset: isize, factor: i16, options: &CodegenOptions, checked: bool) -> Vec<String> {
+    let pointer_bits = options.pointer_bits();
+    let bits = cell_bits(options);
+
+    /* Bits are flipped by odd factors only, and the cell is the product itself */
+    if (options.cell_size == CellSize::U1 && factor % 2 == 0) || factor == 0 {
+        return Vec::new();
+        }
+
+    match checked {
+        true => {
+            let (method, distance) = match offset {
+                0 .. => ("add", offset.unsigned_abs() as u64),
+                _ => ("sub", offset.unsigned_abs() as u64)
+                };
+            let (operation, magnitude) = match factor {
+                0 .. => ("add", factor.unsigned_abs()),
+                _ => ("sub", factor.unsigned_abs())
+                };
+
+            let mut lines = vec!["if tape[p as usize] != 0 {".to_owned()];
+            match distance >> pointer_bits {
+                0 => lines.push(format!("    let q = p.checked_{method}({distance}).expect(\"Pointer overflow\") as usize;")),
+                _ => lines.push("    panic!(\"Pointer overflow\");".to_owned())
+                }
+            match options.cell_size {
+                CellSize::U1 => lines.push("    tape[q] ^= tape[p as usize];".to_owned()),
+                _ => match u64::from(magnitude) >> bits {
+                    0 => lines.push(format!("    tape[q] = tape[p as usize].checked_mul({magnitude}).and_then(|v| tape[q].checked_{operation}(v)).expect(\"Cell overflow\");")),
+                    _ => lines.push("    panic!(\"Cell overflow\");".to_owned())
+                    }
+                }
+            lines.push("    }".to_owned());
+
+            lines
+            },
+        false => {
+            let (method, distance) = match truncate_run(offset as i64, pointer_bits) {
+                distance @ 0 .. => ("add", distance.unsigned_abs()),
+                distance => ("sub", distance.unsigned_abs())
+                };
+            let index = format!("let q = p.wrapping_{method}({distance}) as usize;");
+
+            let statement = match options.cell_size {
+                CellSize::U1 => "tape[q] ^= tape[p as usize];".to_owned(),
+                _ => {
+                    let (operation, magnitude) = match truncate_run(factor.into(), bits) {
+                        factor @ 0 .. => ("add", factor.unsigned_abs()),
+                        factor => ("sub", factor.unsigned_abs())
+                        };
+                    format!("tape[q] = tape[q].wrapping_{operation}(tape[p as usize].wrapping_mul({magnitude}));")
+                    }
+                };
+
+            vec![format!("{{ {index} {statement} }}")]
+            }
+        }
+    }
+
+/* Function for getting an addition's statement in C */
+fn add_statement(target: &str, value: i16, options: &CodegenOptions) -> Option<String> {
+    match options.cell_size {
+        /* Adding an odd value to a single bit flips it */
+        CellSize::U1 => (value % 2 != 0)
+            .then(|| format!("{target} ^= 1;")),
+        _ => run_statement(target, value.into(), cell_bits(options))
+        }
+    }
+
+/* Function for getting an addition's statement in Rust */
+fn add_method(target: &str, value: i16, options: &CodegenOptions, checked: bool) -> Option<String> {
+    match options.cell_size {
+        /* Adding an odd value to a single bit flips it - whether the arithmetic is checked, or not */
+        CellSize::U1 => (value % 2 != 0)
+            .then(|| format!("{target} ^= 1;")),
+        _ => run_method(target, value.into(), cell_bits(options), checked, "Cell overflow")
+        }
+    }
+
+/* Function for getting the width of the cells, for the runs - 1-bit cells are handled separately */
+const fn cell_bits(options: &CodegenOptions) -> u32 {
+    match options.cell_size {
+        CellSize::U1 => 1,
+        CellSize::U8 | CellSize::I8 => u8::BITS,
+        CellSize::U16 | CellSize::I16 => u16::BITS,
+        CellSize::U32 | CellSize::I32 => u32::BITS,
+        CellSize::U64 => u64::BITS,
+        /* Arbitrary-precision cells were rejected while validating */
+        CellSize::Big => u64::BITS
+        }
+    }
+
+/* Function for translating the instructions into a standalone C program */
+pub fn transpile_c(instr: &InstructionSet, options: CodegenOptions) -> Result<String, CompileError> {
+    options.validate()?;
+
+    let ir = lower_checked(instr, options.opt_level)?;
+    let cell_type = format!("uint{}_t", 8 * options.cell_bytes());
+    let index_type = format!("uint{}_t", options.pointer_bits());
+
+    let mut source = Source {
+        code: String::with_capacity(512 + 16 * ir.len()),
+        depth: 0
+        };
+
+    /* Prelude - the tape spans the whole range of the pointer, so it wraps around on its own */
+    source.line("#include <stdint.h>");
+    source.line("#include <stdio.h>");
+    source.line("#include <stdlib.h>");
+    source.line("");
+    source.line(&format!("typedef {cell_type} cell_t;"));
+    source.line(&format!("typedef {index_type} index_t;"));
+    source.line("");
+    source.line(&format!("static cell_t tape[{}];", options.tape_len()));
+    source.line("");
+
+    /* Output routine, with the same formatting as the Interpreter - routines are only written when used, to keep the compilers quiet */
+    if ir.ops().contains(&IrOp::Output) {
+        source.line("static void output(cell_t value) {");
+        source.depth += 1;
+        match options.display_mode {
+            DisplayMode::ASCII => {
+                source.line("if (value == '\\t' || value == '\\n' || (value >= ' ' && value <= '~')) {");
+                source.line("    putchar(value);");
+                source.line("    }");
+                source.line("else {");
+                source.line(&format!("    printf(\"0x%0{}llX\", (unsigned long long) value);", 2 * options.cell_bytes()));
+                source.line("    }");
+                },
+            DisplayMode::Numeric =>
+                source.line("printf(\"%llu\", (unsigned long long) value);"),
+            DisplayMode::Hex =>
+                source.line("printf(\"%llX\", (unsigned long long) value);"),
+            DisplayMode::Bin => {
+                source.line(&format!("int shift = {};", 8 * options.cell_bytes() - 1));
+                source.line("while (shift > 0 && !(value >> shift & 1)) {");
+                source.line("    shift--;");
+                source.line("    }");
+                source.line("for (; shift >= 0; shift--) {");
+                source.line("    putchar('0' + (value >> shift & 1));");
+                source.line("    }");
+                },
+            DisplayMode::Oct =>
+                source.line("printf(\"%llo\", (unsigned long long) value);"),
+            DisplayMode::Raw =>
+                source.line("putchar((unsigned char) value);"),
+            DisplayMode::Unicode => {
+                source.line("unsigned long long code = value;");
+                source.line("if (code == '\\t' || code == '\\n' || (code >= ' ' && code <= '~')) {");
+                source.line("    putchar(code);");
+                source.line("    }");
+                source.line("else if (code >= 0xA0 && code <= 0x10FFFF && (code < 0xD800 || code > 0xDFFF)) {");
+                source.line("    if (code < 0x800) {");
+                source.line("        putchar(0xC0 | code >> 6);");
+                source.line("        }");
+                source.line("    else if (code < 0x10000) {");
+                source.line("        putchar(0xE0 | code >> 12);");
+                source.line("        putchar(0x80 | (code >> 6 & 0x3F));");
+                source.line("        }");
+                source.line("    else {");
+                source.line("        putchar(0xF0 | code >> 18);");
+                source.line("        putchar(0x80 | (code >> 12 & 0x3F));");
+                source.line("        putchar(0x80 | (code >> 6 & 0x3F));");
+                source.line("        }");
+                source.line("    putchar(0x80 | (code & 0x3F));");
+                source.line("    }");
+                source.line("else {");
+                source.line(&format!("    printf(\"0x%0{}llX\", code);", 2 * options.cell_bytes()));
+                source.line("    }");
+                }
+            }
+        source.depth -= 1;
+        source.line("    }");
+        source.line("");
+        }
+
+    /* Input routine - the end of input is a failure, like in the compiled executables */
+    if ir.ops().contains(&IrOp::Input) {
+        source.line("static cell_t input(void) {");
+        source.line("    int value = getchar();");
+        source.line("    if (value == EOF) {");
+        source.line("        exit(EXIT_FAILURE);");
+        source.line("        }");
+        source.line("    return (cell_t) value;");
+        source.line("    }");
+        source.line("");
+        }
+
+    source.line("int main(void) {");
+    source.depth += 1;
+    source.line("index_t p = 0;");
+    source.line("");
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) => if let Some(statement) = add_statement("tape[p]", value, &options) {
+                source.line(&statement);
+                },
+            IrOp::AddAt(offset, value) => if let Some(statement) = add_statement(&format!("tape[{}]", offset_index(offset)), value, &options) {
+                source.line(&statement);
+                },
+            IrOp::Move(value) => if let Some(statement) = run_statement("p", value as i64, options.pointer_bits()) {
+                source.line(&statement);
+                },
+            IrOp::Set(value) =>
+                source.line(&format!("tape[p] = {};", value & options.cell_mask())),
+            IrOp::SetAt(offset, value) =>
+                source.line(&format!("tape[{}] = {};", offset_index(offset), value & options.cell_mask())),
+            IrOp::MulAdd(offset, factor) => if let Some(statement) = mul_add_statement(offset, factor, &options) {
+                source.line(&statement);
+                },
+            IrOp::Output =>
+                source.line("output(tape[p]);"),
+            /* Only the lowest bit is kept for 1-bit cells */
+            IrOp::Input => match options.cell_size {
+                CellSize::U1 => source.line("tape[p] = input() & 1;"),
+                _ => source.line("tape[p] = input();")
+                },
+            IrOp::LoopStart(_) => {
+                source.line("while (tape[p]) {");
+                source.depth += 1;
+                },
+            IrOp::LoopEnd(_) => {
+                source.line("}");
+                source.depth -= 1;
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run */
+    source.line("");
+    if options.display_mode != DisplayMode::Raw {
+        source.line("putchar('\\n');");
+        }
+    source.line("return EXIT_SUCCESS;");
+    source.line("}");
+    source.depth -= 1;
+
+    Ok(source.code)
+    }
+
+/* Function for translating the instructions into a standalone Rust program - with wrapping, or checked arithmetic */
+pub fn transpile_rust(instr: &InstructionSet, options: CodegenOptions, checked: bool) -> Result<String, CompileError> {
+    options.validate()?;
+
+    let ir = lower_checked(instr, options.opt_level)?;
+
+    let mut source = Source {
+        code: String::with_capacity(1024 + 32 * ir.len()),
+        depth: 0
+        };
+
+    /* Prelude - the tape spans the whole range of the pointer, so indexing never goes out of bounds */
+    match ir.ops().contains(&IrOp::Input) {
+        true => {
+            source.line("use std::{");
+            source.line("    io::{stdin, stdout, BufWriter, Read, Write},");
+            source.line("    process::exit");
+            source.line("    };");
+            },
+        false =>
+            source.line("use std::io::{stdout, BufWriter, Write};")
+        }
+    source.line("");
+    source.line(&format!("type Cell = u{};", 8 * options.cell_bytes()));
+    source.line(&format!("type Index = u{};", options.pointer_bits()));
+    source.line(&format!("const TAPE_LEN: usize = {};", options.tape_len()));
+    source.line("");
+
+    /* Output routine, with the same formatting as the Interpreter - routines are only written when used, to keep the compiler quiet */
+    if ir.ops().contains(&IrOp::Output) {
+        source.line("fn output(out: &mut impl Write, value: Cell) {");
+        match options.display_mode {
+            DisplayMode::ASCII => {
+                source.line("    let result = match value {");
+                source.line("        0x09 ..= 0x0A | 0x20 ..= 0x7E => out.write_all(&[value as u8]),");
+                source.line(&format!("        _ => write!(out, \"{{value:#0{}X}}\")", 2 + 2 * options.cell_bytes()));
+                source.line("        };");
+                },
+            DisplayMode::Numeric =>
+                source.line("    let result = write!(out, \"{value}\");"),
+            DisplayMode::Hex =>
+                source.line("    let result = write!(out, \"{value:X}\");"),
+            DisplayMode::Bin =>
+                source.line("    let result = write!(out, \"{value:b}\");"),
+            DisplayMode::Oct =>
+                source.line("    let result = write!(out, \"{value:o}\");"),
+            DisplayMode::Raw =>
+                source.line("    let result = out.write_all(&[value as u8]);"),
+            DisplayMode::Unicode => {
+                source.line("    let printable = (value as u64 <= 0x10FFFF).then(|| value as u32)");
+                source.line("        .and_then(char::from_u32)");
+                source.line("        .filter(|&character| matches!(character, '\\t' | '\\n') || ! character.is_control());");
+                source.line("    let result = match printable {");
+                source.line("        Some(character) => write!(out, \"{character}\"),");
+                source.line(&format!("        None => write!(out, \"{{value:#0{}X}}\")", 2 + 2 * options.cell_bytes()));
+                source.line("        };");
+                }
+            }
+        source.line("    result.expect(\"Failed to write the output\");");
+        source.line("    }");
+        source.line("");
+        }
+
+    /* Input routine - the end of input is a failure, like in the compiled executables */
+    if ir.ops().contains(&IrOp::Input) {
+        source.line("fn input(out: &mut impl Write) -> Cell {");
+        source.line("    let mut byte = [0];");
+        source.line("    out.flush().expect(\"Failed to write the output\");");
+        source.line("    match stdin().read_exact(&mut byte) {");
+        source.line("        Ok(()) => Cell::from(byte[0]),");
+        source.line("        Err(_) => exit(1)");
+        source.line("        }");
+        source.line("    }");
+        source.line("");
+        }
+
+    source.line("fn main() {");
+    source.depth += 1;
+    source.line("let mut tape = vec![0 as Cell; TAPE_LEN].into_boxed_slice();");
+    source.line("let mut p: Index = 0;");
+    source.line("let mut out = BufWriter::new(stdout().lock());");
+    source.line("");
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) => if let Some(statement) = add_method("tape[p as usize]", value, &options, checked) {
+                source.line(&statement);
+                },
+            IrOp::AddAt(offset, value) => match cell_rust(offset, &options, checked) {
+                Some(target) => if let Some(statement) = add_method(&target, value, &options, checked) {
+                    source.line(&statement);
+                    },
+                None => source.line("panic!(\"Pointer overflow\");")
+                },
+            IrOp::Move(value) => if let Some(statement) = run_method("p", value as i64, options.pointer_bits(), checked, "Pointer overflow") {
+                source.line(&statement);
+                },
+            IrOp::Set(value) =>
+                source.line(&format!("tape[p as usize] = {};", value & options.cell_mask())),
+            IrOp::SetAt(offset, value) => match cell_rust(offset, &options, checked) {
+                Some(target) => source.line(&format!("{target} = {};", value & options.cell_mask())),
+                None => source.line("panic!(\"Pointer overflow\");")
+                },
+            IrOp::MulAdd(offset, factor) =>
+                for statement in mul_add_method(offset, factor, &options, checked) {
+                    source.line(&statement);
+                    },
+            IrOp::Output =>
+                source.line("output(&mut out, tape[p as usize]);"),
+            /* Only the lowest bit is kept for 1-bit cells */
+            IrOp::Input => match options.cell_size {
+                CellSize::U1 => source.line("tape[p as usize] = input(&mut out) & 1;"),
+                _ => source.line("tape[p as usize] = input(&mut out);")
+                },
+            IrOp::LoopStart(_) => {
+                source.line("while tape[p as usize] != 0 {");
+                source.depth += 1;
+                },
+            IrOp::LoopEnd(_) => {
+                source.line("}");
+                source.depth -= 1;
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run */
+    source.line("");
+    match options.display_mode {
+        DisplayMode::Raw =>
+            source.line("out.flush().expect(\"Failed to write the output\");"),
+        _ =>
+            source.line("out.write_all(b\"\\n\").and_then(|_| out.flush()).expect(\"Failed to write the output\");")
+        }
+    source.line("}");
+    source.depth -= 1;
+
+    Ok(source.code)
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::transpile::*;
+
+    #[test]
+    fn run_statements() {
+        assert_eq!(run_statement("p", 3, 8), Some("p += 3;".to_owned()));
+        assert_eq!(run_statement("p", -3, 8), Some("p -= 3;".to_owned()));
+        assert_eq!(run_statement("p", 255, 8), Some("p -= 1;".to_owned()));
+        assert_eq!(run_statement("p", 256, 8), None);
+        assert_eq!(run_statement("p", i64::from(i32::MIN), 32), Some("p -= 2147483648;".to_owned()));
+        }
+
+    #[test]
+    fn transpile_c_basic() {
+        let instr = eval_instr("+++[->>+<]>.")
+            .expect("Unreachable");
+
+        let source = transpile_c(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert!(source.contains("typedef uint8_t cell_t;"));
+        assert!(source.contains("typedef uint16_t index_t;"));
+        assert!(source.contains("    tape[p] += 3;\n    while (tape[p]) {\n        tape[p] -= 1;\n        tape[(index_t) (p + 2)] += 1;\n        p += 1;\n"));
+        assert!(source.contains("    output(tape[p]);\n"));
+        }
+
+    #[test]
+    fn transpile_c_mul_add() {
+        let instr = eval_instr("+++[->>+++<<<-->]")
+            .expect("Unreachable");
+
+        let source = transpile_c(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert!(source.contains("    tape[(index_t) (p - 1)] += tape[p] * -2;\n    tape[(index_t) (p + 2)] += tape[p] * 3;\n    tape[p] = 0;\n"));
+        }
+
+    #[test]
+    fn run_methods() {
+        assert_eq!(run_method("p", 255, 8, false, "Overflow"), Some("p = p.wrapping_sub(1);".to_owned()));
+        assert_eq!(run_method("p", 255, 8, true, "Overflow"), Some("p = p.checked_add(255).expect(\"Overflow\");".to_owned()));
+        assert_eq!(run_method("p", -256, 8, true, "Overflow"), Some("panic!(\"Overflow\");".to_owned()));
+        assert_eq!(run_method("p", 0, 8, true, "Overflow"), None);
+        }
+
+    #[test]
+    fn transpile_rust_basic() {
+        let instr = eval_instr("+++[->>+<]>.")
+            .expect("Unreachable");
+
+        let source = transpile_rust(&instr, CodegenOptions::default(), false)
+            .expect("Unreachable");
+
+        assert!(source.contains("type Cell = u8;"));
+        assert!(source.contains("type Index = u16;"));
+        assert!(source.contains("    while tape[p as usize] != 0 {\n        tape[p as usize] = tape[p as usize].wrapping_sub(1);\n        tape[p.wrapping_add(2) as usize] = tape[p.wrapping_add(2) as usize].wrapping_add(1);\n        p = p.wrapping_add(1);\n"));
+        }
+
+    #[test]
+    fn transpile_rust_mul_add() {
+        let instr = eval_instr("+++[-<-->]")
+            .expect("Unreachable");
+
+        let wrapping = transpile_rust(&instr, CodegenOptions::default(), false)
+            .expect("Unreachable");
+        let checked = transpile_rust(&instr, CodegenOptions::default(), true)
+            .expect("Unreachable");
+
+        assert!(wrapping.contains("    { let q = p.wrapping_sub(1) as usize; tape[q] = tape[q].wrapping_sub(tape[p as usize].wrapping_mul(2)); }\n"));
+        assert!(checked.contains("    if tape[p as usize] != 0 {\n        let q = p.checked_sub(1).expect(\"Pointer overflow\") as usize;\n"));
+        assert!(checked.contains("        tape[q] = tape[p as usize].checked_mul(2).and_then(|v| tape[q].checked_sub(v)).expect(\"Cell overflow\");\n"));
+        }
+
+    #[test]
+    fn transpile_c_err_ascii_bit_cells() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            cell_size: CellSize::U1,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(transpile_c(&instr, options), Err(CompileError::AsciiBitCells));
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,253 @@
+use {
+    anyhow::Result as DynResult,
+    core::time::Duration,
+    ratatui::{
+        crossterm::event::{
+            poll,
+            read,
+            Event,
+            KeyCode,
+            KeyEventKind
+            },
+        layout::{
+            Constraint,
+            Layout,
+            Rect
+            },
+        style::{
+            Color,
+            Modifier,
+            Style
+            },
+        text::{
+            Line,
+            Span
+            },
+        widgets::{
+            Block,
+            Paragraph
+            },
+        DefaultTerminal,
+        Frame
+        },
+    braincooker::*
+    };
+
+
+/* Time between the frames - keys are checked for this long */
+const TICK: Duration = Duration::from_millis(50);
+
+/* Width of a single cell in the tape's view */
+const CELL_WIDTH: usize = 6;
+
+/* Limits of the instructions executed per frame, while running */
+const MIN_SPEED: u64 = 1;
+const MAX_SPEED: u64 = 1 << 20;
+
+
+/* State of the run */
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Paused,
+    Running,
+    Finished
+    }
+
+/* Visual debugger's state */
+struct App<'a> {
+    debugger: Debugger,
+    instr: &'a InstructionSet,
+    /* Sink of the program's output, and everything taken out of it so far */
+    buffer: SharedBuffer,
+    output: Vec<u8>,
+    mode: Mode,
+    /* Number of instructions executed per frame, while running */
+    speed: u64,
+    /* Error, which stopped the run */
+    error: Option<String>
+    }
+
+
+/* Function for running the visual debugger in the terminal, until it gets closed - the program's output has to be written into the buffer */
+pub fn run_tui(debugger: Debugger, instr: &InstructionSet, buffer: SharedBuffer) -> DynResult<()> {
+    let mut app = App {
+        debugger,
+        instr,
+        buffer,
+        output: Vec::new(),
+        mode: Mode::Paused,
+        speed: MIN_SPEED,
+        error: None
+        };
+
+    /* Terminal gets restored, even when drawing failed */
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+
+    result
+    }
+
+impl App<'_> {
+    /* Main loop - draws the frame, handles the keys, and keeps stepping while running */
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> DynResult<()> {
+        loop {
+            self.output.extend(self.buffer.take());
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if poll(TICK)? && let Event::Key(key) = read()? && key.kind == KeyEventKind::Press {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc =>
+                        return Ok(()),
+                    KeyCode::Char('s') | KeyCode::Right if self.mode == Mode::Paused =>
+                        self.advance(1),
+                    KeyCode::Char('r') if self.mode == Mode::Paused =>
+                        self.mode = Mode::Running,
+                    KeyCode::Char('p') | KeyCode::Char(' ') if self.mode == Mode::Running =>
+                        self.mode = Mode::Paused,
+                    KeyCode::Char('b') => {
+                        let location = self.debugger.location();
+
+                        if ! self.debugger.remove_breakpoint(location) {
+                            self.debugger.add_breakpoint(location);
+                            }
+                        },
+                    KeyCode::Char('+') =>
+                        self.speed = (self.speed * 2).min(MAX_SPEED),
+                    KeyCode::Char('-') =>
+                        self.speed = (self.speed / 2).max(MIN_SPEED),
+                    _ => ()
+                    }
+                }
+
+            if self.mode == Mode::Running {
+                self.advance(self.speed);
+                }
+            }
+        }
+
+    /* Execute up to the number of instructions - stops early at the breakpoints, after the `!` instructions, and at the end */
+    fn advance(&mut self, steps: u64) {
+        for _ in 0 .. steps {
+            match self.debugger.step() {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.mode = Mode::Finished;
+                    return;
+                    }
+                }
+
+            if self.debugger.is_breakpoint(self.debugger.location()) || self.debugger.passed_break() {
+                self.mode = Mode::Paused;
+                break;
+                }
+            }
+
+        if self.debugger.is_finished() {
+            self.mode = Mode::Finished;
+            }
+        }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [code_area, tape_area, output_area, status_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(4),
+            Constraint::Min(3),
+            Constraint::Length(1)
+            ]).areas(frame.area());
+
+        self.draw_code(frame, code_area);
+        self.draw_tape(frame, tape_area);
+        self.draw_output(frame, output_area);
+        self.draw_status(frame, status_area);
+        }
+
+    /* Instructions around the current one - it's highlighted, and the breakpoints are marked red */
+    fn draw_code(&self, frame: &mut Frame, area: Rect) {
+        let width = usize::from(area.width.saturating_sub(2));
+        let location = self.debugger.location();
+        let start = location.saturating_sub(width / 2);
+        let end = (start + width).min(self.instr.len());
+
+        let symbols: Vec<Span> = (start .. end)
+            .map(|i| {
+                let style = match (i == location, self.debugger.is_breakpoint(i)) {
+                    (true, _) => Style::new().fg(Color::Black).bg(Color::Yellow),
+                    (false, true) => Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    (false, false) => Style::new()
+                    };
+
+                Span::styled(self.instr[i].symbol().to_string(), style)
+                })
+            .collect();
+
+        let lines = vec![
+            Line::from(symbols),
+            Line::from(format!("Instruction {location} of {}", self.instr.len()))
+            ];
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Code ")), area);
+        }
+
+    /* Cells around the pointer, with their locations above them - the current one is highlighted */
+    fn draw_tape(&mut self, frame: &mut Frame, area: Rect) {
+        let count = (usize::from(area.width.saturating_sub(2)) / CELL_WIDTH).max(1) as isize;
+        let position = self.debugger.position();
+        let from = position - count / 2;
+
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+
+        for (index, value) in self.debugger.cells(from, from + count - 1) {
+            let style = match index == position {
+                true => Style::new().fg(Color::Black).bg(Color::Yellow),
+                false => Style::new()
+                };
+            /* Cells off the tape are left blank */
+            let value = value.map(|value| value.to_string())
+                .unwrap_or_default();
+
+            indices.push(Span::styled(format!("{index:>width$}", width = CELL_WIDTH), Style::new().fg(Color::DarkGray)));
+            values.push(Span::styled(format!("{value:>width$}", width = CELL_WIDTH), style));
+            }
+
+        let lines = vec![
+            Line::from(indices),
+            Line::from(values)
+            ];
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(format!(" Tape - pointer at {position} "))), area);
+        }
+
+    /* Latest lines of the output, which fit */
+    fn draw_output(&self, frame: &mut Frame, area: Rect) {
+        let text = String::from_utf8_lossy(&self.output);
+        let height = usize::from(area.height.saturating_sub(2));
+
+        let lines: Vec<&str> = text.lines()
+            .collect();
+        let lines: Vec<Line> = lines[lines.len().saturating_sub(height) ..].iter()
+            .map(|&line| Line::from(line))
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Output ")), area);
+        }
+
+    /* Run's state, and the keys */
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let mode = match (self.mode, &self.error) {
+            (_, Some(err)) => format!("Failed: {err}"),
+            (Mode::Paused, None) => "Paused".to_owned(),
+            (Mode::Running, None) => "Running".to_owned(),
+            (Mode::Finished, None) => "Finished".to_owned()
+            };
+
+        let status = format!("{mode} | executed: {} | speed: {}/frame | [s]tep [r]un [p]ause [b]reakpoint [+/-] speed [q]uit",
+            self.debugger.executed(), self.speed);
+
+        frame.render_widget(Paragraph::new(status).style(Style::new().add_modifier(Modifier::REVERSED)), area);
+        }
+    }
\ No newline at end of file
@@ -1,16 +1,297 @@
 use {
-    clap::ValueEnum,
+    alloc::vec::Vec,
     core::str::FromStr,
     crate::tape::*
     };
 
+#[cfg(feature = "std")]
+use {
+    clap::ValueEnum,
+    std::{
+        io::{
+            Result as IOResult,
+            Write
+            },
+        sync::{
+            Arc,
+            Mutex,
+            PoisonError
+            }
+        },
+    core::mem::take
+    };
+
+#[cfg(any(feature = "async", feature = "web"))]
+use std::{
+    collections::VecDeque,
+    io::Read
+    };
+
 
 /* Value visualisation mode */
-#[derive(Clone, Copy, Default, ValueEnum)]
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
 pub enum DisplayMode {
     ASCII,
     #[default]
-    Numeric
+    Numeric,
+    Raw,
+    Unicode,
+    Hex,
+    Bin,
+    Oct
+    }
+
+impl DisplayMode {
+    /* Check whether the values are written as digits */
+    pub(crate) const fn is_numeric(self) -> bool {
+        matches!(self, Self::Numeric | Self::Hex | Self::Bin | Self::Oct)
+        }
+
+    /* Get the base of the numeric display modes - the others fall back to decimal */
+    pub(crate) const fn radix(self) -> u32 {
+        match self {
+            Self::Hex => 16,
+            Self::Bin => 2,
+            Self::Oct => 8,
+            _ => 10
+            }
+        }
+    }
+
+/* Formatting of the values written in the numeric display modes - nothing surrounds them by default */
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct NumericFormat {
+    /* Written between the successive values */
+    pub separator: Vec<u8>,
+    /* Written before, and after every value */
+    pub prefix: Vec<u8>,
+    pub suffix: Vec<u8>
+    }
+
+impl NumericFormat {
+    /* Surround the digits with the prefix, and the suffix - the separator goes first, unless it's the first value */
+    pub(crate) fn apply(&self, digits: &[u8], first: bool) -> Vec<u8> {
+        let separator = match first {
+            true => &[],
+            false => &self.separator[..]
+            };
+
+        [separator, &self.prefix, digits, &self.suffix].concat()
+        }
+    }
+
+/* Pointer, and cell size */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum DataSize {
+    U8,
+    U16,
+    U32,
+    U64
+    }
+
+/* Cell size, including sizes narrower than a byte */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum CellSize {
+    U1,
+    U8,
+    U16,
+    U32,
+    U64,
+    /* Arbitrary-precision cells, which never wrap */
+    Big,
+    /* Signed cells, in two's complement */
+    I8,
+    I16,
+    I32
+    }
+
+/* Tape layout */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum TapeKind {
+    /* Fixed number of cells, based on the pointer size, with the pointer wrapping around */
+    #[default]
+    Fixed,
+    /* Cells growing on demand in both directions, with negative indices */
+    Infinite,
+    /* Same cells as the fixed tape, but allocated on demand by doubling - the pointer wraps around */
+    Growable
+    }
+
+/* Behaviour of the pointer moved past either end of the tape */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum PointerOverflow {
+    /* Pointer wraps around to the other end */
+    #[default]
+    Wrap,
+    /* Run fails */
+    Error
+    }
+
+/* Behaviour of the cell taken past its bounds */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum CellOverflow {
+    /* Cell wraps around to the other bound */
+    #[default]
+    Wrap,
+    /* Cell stops at the bound */
+    Saturate,
+    /* Run fails */
+    Error
+    }
+
+/* Behaviour of the input instruction at the end of the input */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum EofMode {
+    /* Cell is set to zero */
+    Zero,
+    /* Cell is set to its maximum value, like -1 in C */
+    Max,
+    /* Cell keeps its value */
+    Unchanged,
+    /* Run fails */
+    #[default]
+    Error
+    }
+
+/* Optimisation level - every level runs the passes of the previous ones */
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum OptLevel {
+    /* Every instruction executed on its own */
+    #[cfg_attr(feature = "std", value(name = "0"))]
+    O0,
+    /* Runs of increments, decrements, and pointer moves merged */
+    #[cfg_attr(feature = "std", value(name = "1"))]
+    O1,
+    /* Clearing, and copying loops rewritten */
+    #[cfg_attr(feature = "std", value(name = "2"))]
+    O2,
+    /* Pointer moves deferred in the straight-line code */
+    #[default]
+    #[cfg_attr(feature = "std", value(name = "3"))]
+    O3
+    }
+
+#[cfg(any(feature = "python", feature = "web"))]
+impl OptLevel {
+    /* Get the level by its number - anything above the highest one is the highest one */
+    pub(crate) const fn from_number(level: u8) -> Self {
+        match level {
+            0 => Self::O0,
+            1 => Self::O1,
+            2 => Self::O2,
+            _ => Self::O3
+            }
+        }
+    }
+
+/* Treatment of the characters outside the instructions, during evaluation */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum CommentMode {
+    /* Every one is a comment */
+    #[default]
+    Any,
+    /* Only the whitespace is allowed */
+    Whitespace,
+    /* None is allowed */
+    Forbidden
+    }
+
+/* Languages of the source code */
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Lang {
+    #[default]
+    Brainfuck,
+    /* Pairs of the "Ook.", "Ook?", and "Ook!" words, one for every instruction */
+    Ook
+    }
+
+/* Opt-in language extensions */
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(ValueEnum))]
+pub enum Dialect {
+    /* '?' - writes a pseudo-random value into the current cell */
+    ExtRand,
+    /* '#' - dumps the instruction pointer, and the cells around the pointer to stderr */
+    ExtDebug,
+    /* '(', and ')' - define a procedure for the current cell's value, ':' - calls the one for the current cell's value */
+    Pbrain,
+    /* '!' - stops the run, '@' - prints the pointer, '$' - prints the cells around the pointer to stderr */
+    ExtInspect,
+    /* '^', and 'v' - switch to the previous, and the next tape, each with its own pointer */
+    MultiTape
+    }
+
+
+/* In-memory output sink, which can still be read after being handed over to the Interpreter */
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct SharedBuffer (
+    Arc<Mutex<Vec<u8>>>
+    );
+
+#[cfg(feature = "std")]
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        self.0.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend_from_slice(buf);
+
+        Ok(buf.len())
+        }
+    fn flush(&mut self) -> IOResult<()> {
+        Ok(())
+        }
+    }
+
+#[cfg(feature = "std")]
+impl SharedBuffer {
+    /* Take the contents written so far, leaving the buffer empty */
+    pub fn take(&self) -> Vec<u8> {
+        let mut contents = self.0.lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        take(&mut *contents)
+        }
+    }
+
+/* In-memory input source, which can still be filled after being handed over to the Interpreter - running out of bytes is the end of input */
+#[cfg(any(feature = "async", feature = "web"))]
+#[derive(Clone, Default)]
+pub(crate) struct SharedFeed (
+    Arc<Mutex<VecDeque<u8>>>
+    );
+
+#[cfg(any(feature = "async", feature = "web"))]
+impl Read for SharedFeed {
+    fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+        self.0.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read(buf)
+        }
+    }
+
+#[cfg(any(feature = "async", feature = "web"))]
+impl SharedFeed {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+        }
+
+    pub(crate) fn extend(&self, bytes: &[u8]) {
+        self.0.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(bytes);
+        }
     }
 
 
@@ -26,12 +307,26 @@ where T: TapeCell {
         }
     }
 
+/* Function for getting the printable character of a Unicode scalar value - tab, line feed, and non-control characters */
+pub fn unicode_printable<T>(value: T) -> Option<char>
+where T: TapeCell {
+    value.to_u32()
+        .and_then(char::from_u32)
+        .filter(|&character| matches!(character, '\t' | '\n') || ! character.is_control())
+    }
+
+/* Function for truncating a raw value into a cell's range */
+pub fn truncate_cell_value<T>(value: u64) -> T
+where T: TapeCell {
+    T::truncate(value)
+    }
+
 /* Function for parsing written input buffer */
 pub fn parse_cell_value<T>(buf: &str) -> Result<T, <T as FromStr>::Err>
 where T: TapeCell {
     /* Try parsing the buffer as a char literal */
     if let &[b'\'', byte, b'\''] = buf.as_bytes() {
-        return Ok(T::from(byte));
+        return Ok(truncate_cell_value(u64::from(byte)));
         }
 
     /* Pare as a normal integer */
@@ -41,7 +336,10 @@ where T: TapeCell {
 
 #[cfg(test)]
 mod test {
-    use crate::utils::*;
+    use {
+        std::io::Write,
+        crate::utils::*
+        };
 
     #[test]
     fn ascii_printable_numbers() {
@@ -65,6 +363,17 @@ mod test {
         assert!(lowercase);
         }
 
+    #[test]
+    fn unicode_printable_chars() {
+        assert_eq!(unicode_printable(0x41_u8), Some('A'));
+        assert_eq!(unicode_printable(0xE9_u16), Some('é'));
+        assert_eq!(unicode_printable(0x1F600_u32), Some('😀'));
+        assert_eq!(unicode_printable(0x0A_u32), Some('\n'));
+        assert_eq!(unicode_printable(0x07_u32), None);
+        assert_eq!(unicode_printable(0xD800_u16), None);
+        assert_eq!(unicode_printable(0x110000_u32), None);
+        }
+
     #[test]
     fn ascii_printable_signs() {
         let signs = ('!' ..= '/')
@@ -95,6 +404,26 @@ mod test {
         assert!(unprintable);
         }
 
+    #[test]
+    fn shared_buffer_take() {
+        let buffer = SharedBuffer::default();
+        let mut sink = buffer.clone();
+
+        sink.write_all(b"Hello")
+            .expect("Unreachable");
+
+        assert_eq!(buffer.take(), b"Hello");
+        assert!(buffer.take().is_empty());
+        }
+
+    #[test]
+    fn truncate_values() {
+        assert_eq!(truncate_cell_value::<u8>(0x1234_5678), 0x78);
+        assert_eq!(truncate_cell_value::<u16>(0x1234_5678), 0x5678);
+        assert_eq!(truncate_cell_value::<u32>(0x1234_5678_9ABC_DEF0), 0x9ABC_DEF0);
+        assert_eq!(truncate_cell_value::<u8>(u64::MAX), u8::MAX);
+        }
+
     #[test]
     fn parse_letters() {
         assert_eq!(parse_cell_value("'A'"), Ok(b'A'));
@@ -180,6 +509,18 @@ mod test {
         assert!(parse_cell_value::<u32>("5000000000").is_err());
     }
 
+    #[test]
+    fn parse_raw_signed() {
+        assert_eq!(parse_cell_value("-1"), Ok(-1i8));
+        assert_eq!(parse_cell_value("-128"), Ok(-128i8));
+        assert_eq!(parse_cell_value("127"), Ok(127i8));
+        assert_eq!(parse_cell_value("-32768"), Ok(-32768i16));
+        assert_eq!(parse_cell_value("-2147483648"), Ok(-2147483648i32));
+        assert_eq!(parse_cell_value("'A'"), Ok(65i8));
+        assert!(parse_cell_value::<i8>("128").is_err());
+        assert!(parse_cell_value::<i8>("-129").is_err());
+        }
+
     #[test]
     fn parse_padded() {
         assert_eq!(parse_cell_value("01"), Ok(1u8));
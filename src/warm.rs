@@ -0,0 +1,106 @@
+use {
+    anyhow::Result as DynResult,
+    log::{
+        info,
+        warn
+        },
+    std::{
+        env::temp_dir,
+        fs::{
+            create_dir_all,
+            read,
+            rename,
+            write
+            },
+        hash::{
+            DefaultHasher,
+            Hash,
+            Hasher
+            },
+        path::{
+            Path,
+            PathBuf
+            }
+        },
+    braincooker::*
+    };
+
+
+/* Function for hashing settings into a key of the cached snapshot */
+pub fn cache_key<H>(value: H) -> u64
+where H: Hash {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+    }
+
+/* Function for getting the path of a cached snapshot */
+fn cache_path(key: u64) -> PathBuf {
+    temp_dir()
+        .join("braincooker")
+        .join(format!("{key:016x}.warm"))
+    }
+
+/* Function for running from a cached warm start snapshot - it gets made, and stored on the first run */
+pub fn run_warm(interp: &mut dyn InterpRun, instr: &InstructionSet, key: u64) -> DynResult<()> {
+    let path = cache_path(key);
+
+    /* Try resuming from the cached snapshot - the Interpreter is left untouched if it doesn't fit */
+    let cached = read(&path).ok()
+        .and_then(|bytes| Snapshot::decode(&bytes).ok());
+
+    if let Some(snapshot) = cached {
+        info!("Using cached warm start: {}", path.display());
+
+        match interp.resume(instr, &snapshot) {
+            Err(RunError::SnapshotMismatch) =>
+                warn!("Cached warm start doesn't match the program, making a new one"),
+            outcome =>
+                return Ok(outcome?)
+            }
+        }
+
+    /* Run the deterministic prefix, and cache it - caching is best-effort */
+    let snapshot = interp.warm_start(instr)?;
+
+    let stored = path.parent()
+        .map_or(Ok(()), create_dir_all)
+        .and_then(|_| write(&path, snapshot.encode()));
+
+    match stored {
+        Ok(()) =>
+            info!("Cached warm start: {}", path.display()),
+        Err(err) =>
+            warn!("Couldn't cache the warm start: {err}")
+        }
+
+    interp.resume(instr, &snapshot)?;
+
+    Ok(())
+    }
+
+/* Function for getting a hook, which saves every checkpoint into the file - written next to it first, so a restart never finds half of one */
+pub fn checkpoint_hook(path: &Path) -> CheckpointHook {
+    let path = path.to_owned();
+    let partial = path.with_extension("partial");
+
+    Box::new(move |snapshot| {
+        write(&partial, snapshot.encode())?;
+        rename(&partial, &path)?;
+
+        info!("Checkpoint at instruction {} was saved into: {}", snapshot.instr_ptr(), path.display());
+
+        Ok(())
+        })
+    }
+
+/* Function for continuing a run from a checkpoint file - the snapshot holds the program's fingerprint, so a different program is rejected */
+pub fn run_restored(interp: &mut dyn InterpRun, instr: &InstructionSet, path: &Path) -> DynResult<()> {
+    let snapshot = Snapshot::decode(&read(path)?)?;
+
+    info!("Restoring from instruction: {}", snapshot.instr_ptr());
+
+    interp.resume(instr, &snapshot)?;
+
+    Ok(())
+    }
@@ -0,0 +1,488 @@
+use crate::{
+    codegen::*,
+    eval::*,
+    ir::*,
+    utils::*
+    };
+
+
+/* Size of a page of the linear memory, and the maximal number of pages */
+const PAGE_SIZE: u64 = 0x1_0000;
+const MAX_PAGES: u64 = 0x1_0000;
+
+/* Indices of the functions - imports come first */
+const READ: u8 = 0;
+const WRITE: u8 = 1;
+const PUT_VALUE: u8 = 2;
+const RUN: u8 = 3;
+
+/* Value, and block types */
+const I32: u8 = 0x7F;
+const VOID: u8 = 0x40;
+
+/* Instructions */
+const UNREACHABLE: u8 = 0x00;
+const BLOCK: u8 = 0x02;
+const LOOP: u8 = 0x03;
+const IF: u8 = 0x04;
+const ELSE: u8 = 0x05;
+const END: u8 = 0x0B;
+const BR: u8 = 0x0C;
+const BR_IF: u8 = 0x0D;
+const RETURN: u8 = 0x0F;
+const CALL: u8 = 0x10;
+const LOCAL_GET: u8 = 0x20;
+const LOCAL_SET: u8 = 0x21;
+const LOCAL_TEE: u8 = 0x22;
+const I32_CONST: u8 = 0x41;
+const I32_EQZ: u8 = 0x45;
+const I32_LT_S: u8 = 0x48;
+const I32_LT_U: u8 = 0x49;
+const I32_LE_U: u8 = 0x4D;
+const I32_GE_S: u8 = 0x4E;
+const I32_GE_U: u8 = 0x4F;
+const I32_ADD: u8 = 0x6A;
+const I32_SUB: u8 = 0x6B;
+const I32_MUL: u8 = 0x6C;
+const I32_DIV_U: u8 = 0x6E;
+const I32_REM_U: u8 = 0x70;
+const I32_AND: u8 = 0x71;
+const I32_OR: u8 = 0x72;
+const I32_XOR: u8 = 0x73;
+const I32_SHL: u8 = 0x74;
+const I32_SHR_U: u8 = 0x76;
+
+
+/* Function for writing an unsigned LEB128 number */
+fn put_unsigned(output: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = value as u8 & 0x7F;
+        value >>= 7;
+
+        match value {
+            0 => return output.push(byte),
+            _ => output.push(byte | 0x80)
+            }
+        }
+    }
+
+/* Function for writing a signed LEB128 number */
+fn put_signed(output: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = value as u8 & 0x7F;
+        value >>= 7;
+
+        /* Done, once the rest is only the sign-extension of the last byte */
+        match (value, byte & 0x40 != 0) {
+            (0, false) | (-1, true) => return output.push(byte),
+            _ => output.push(byte | 0x80)
+            }
+        }
+    }
+
+/* Function for writing a length-prefixed vector of bytes */
+fn put_vector(output: &mut Vec<u8>, bytes: &[u8]) {
+    put_unsigned(output, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+    }
+
+/* Function for writing a section, with its identifier */
+fn put_section(output: &mut Vec<u8>, id: u8, content: &[u8]) {
+    output.push(id);
+    put_vector(output, content);
+    }
+
+
+/* Helper container for assembling a function's body */
+struct Body {
+    code: Vec<u8>,
+    options: CodegenOptions,
+    /* Local holding the addressed cell's index - the pointer is in the first one, and the cells at an offset use the third one */
+    index: u8
+    }
+
+impl Body {
+    fn emit(&mut self, bytes: &[u8]) {
+        self.code.extend_from_slice(bytes);
+        }
+
+    fn constant(&mut self, value: i32) {
+        self.code.push(I32_CONST);
+        put_signed(&mut self.code, value.into());
+        }
+
+    /* Emit the operation on the cell at an offset from the pointer */
+    fn at(&mut self, offset: i64, operation: impl FnOnce(&mut Self)) {
+        if offset != 0 {
+            self.advance(2, offset);
+            self.index = 2;
+            }
+
+        operation(self);
+        self.index = 0;
+        }
+
+    /* Push the addressed cell's address */
+    fn address(&mut self) {
+        self.emit(&[LOCAL_GET, self.index]);
+        match self.options.cell_bytes().trailing_zeros() {
+            0 => (),
+            shift => {
+                self.constant(shift as i32);
+                self.emit(&[I32_SHL]);
+                }
+            }
+        }
+
+    /* Push the current cell's value, zero-extended */
+    fn load_cell(&mut self) {
+        self.address();
+        match self.options.cell_bytes() {
+            1 => self.emit(&[0x2D, 0, 0]),      /* i32.load8_u */
+            2 => self.emit(&[0x2F, 1, 0]),      /* i32.load16_u */
+            _ => self.emit(&[0x28, 2, 0])       /* i32.load */
+            }
+        }
+
+    /* Store the value on top of the stack into the current cell, with its address right below - it gets truncated to the cell's width */
+    fn store_cell(&mut self) {
+        match self.options.cell_bytes() {
+            1 => self.emit(&[0x3A, 0, 0]),      /* i32.store8 */
+            2 => self.emit(&[0x3B, 1, 0]),      /* i32.store16 */
+            _ => self.emit(&[0x36, 2, 0])       /* i32.store */
+            }
+        }
+
+    /* Set the current cell to a value, truncated to the cell's width */
+    fn set_cell(&mut self, value: u64) {
+        self.address();
+        self.constant((value & self.options.cell_mask()) as u32 as i32);
+        self.store_cell();
+        }
+
+    /* Add the current cell times a factor to the cell at an offset - the product is kept in the second local, while the pointer is moved there, and back */
+    fn mul_add(&mut self, offset: i64, factor: i16) {
+        /* Bits are flipped by odd factors only, and the cell is the product itself */
+        let (skip, combine, factor) = match self.options.cell_size {
+            CellSize::U1 => (factor % 2 == 0, I32_XOR, 1),
+            _ => (factor == 0, I32_ADD, factor)
+            };
+        if skip {
+            return;
+            }
+
+        self.load_cell();
+        if factor != 1 {
+            self.constant(factor.into());
+            self.emit(&[I32_MUL]);
+            }
+        self.emit(&[LOCAL_SET, 1]);
+
+        self.move_pointer(offset);
+        self.address();
+        self.load_cell();
+        self.emit(&[LOCAL_GET, 1, combine]);
+        self.store_cell();
+        self.move_pointer(-offset);
+        }
+
+    /* Add a value to the current cell - wrapping, as the store truncates it */
+    fn add_cell(&mut self, value: i64) {
+        let operation = match self.options.cell_size {
+            /* Adding an odd value to a single bit flips it */
+            CellSize::U1 if value % 2 != 0 => (I32_XOR, 1),
+            CellSize::U1 => return,
+            CellSize::U8 => (I32_ADD, value as u8 as i32),
+            CellSize::U16 => (I32_ADD, value as u16 as i32),
+            CellSize::U32 => (I32_ADD, value as i32),
+            /* 64-bit, arbitrary-precision, and signed cells were rejected while validating */
+            CellSize::U64 | CellSize::Big | CellSize::I8 | CellSize::I16 | CellSize::I32 => return
+            };
+
+        if operation.1 != 0 {
+            self.address();
+            self.load_cell();
+            self.constant(operation.1);
+            self.emit(&[operation.0]);
+            self.store_cell();
+            }
+        }
+
+    /* Move the pointer - wrapping, by masking it to the pointer's width */
+    fn move_pointer(&mut self, value: i64) {
+        if value as i32 != 0 {
+            self.advance(0, value);
+            }
+        }
+
+    /* Set a local to the pointer's location at a distance - wrapping, by masking it to the pointer's width */
+    fn advance(&mut self, local: u8, value: i64) {
+        let mask = match self.options.pointer_size {
+            DataSize::U8 => Some(u8::MAX as i32),
+            DataSize::U16 => Some(u16::MAX as i32),
+            /* 64-bit pointers were rejected while validating */
+            DataSize::U32 | DataSize::U64 => None
+            };
+
+        self.emit(&[LOCAL_GET, 0]);
+        self.constant(value as i32);
+        self.emit(&[I32_ADD]);
+        if let Some(mask) = mask {
+            self.constant(mask);
+            self.emit(&[I32_AND]);
+            }
+        self.emit(&[LOCAL_SET, local]);
+        }
+    }
+
+
+/* Function for emitting the routine writing the value of its parameter, according to the display mode */
+fn put_value(options: CodegenOptions) -> Vec<u8> {
+    let mut body = Body {
+        code: Vec::with_capacity(128),
+        options,
+        index: 0
+        };
+
+    match options.display_mode {
+        DisplayMode::ASCII => {
+            /* Printable characters are written as they are - tab, line feed, and graphic characters */
+            body.emit(&[LOCAL_GET, 0]);
+            body.constant(b'\t'.into());
+            body.emit(&[I32_GE_U, LOCAL_GET, 0]);
+            body.constant(b'\n'.into());
+            body.emit(&[I32_LE_U, I32_AND, LOCAL_GET, 0]);
+            body.constant(b' '.into());
+            body.emit(&[I32_GE_U, LOCAL_GET, 0]);
+            body.constant(b'~'.into());
+            body.emit(&[I32_LE_U, I32_AND, I32_OR]);
+            body.emit(&[IF, VOID, LOCAL_GET, 0, CALL, WRITE, RETURN, END]);
+
+            /* Fallback - `0x` prefixed, zero padded, uppercase hexadecimal - the second local holds the shift, and the third one the digit */
+            body.constant(b'0'.into());
+            body.emit(&[CALL, WRITE]);
+            body.constant(b'x'.into());
+            body.emit(&[CALL, WRITE]);
+            body.constant(((options.cell_bytes() * 2 - 1) * 4) as i32);
+            body.emit(&[LOCAL_SET, 1]);
+
+            body.emit(&[LOOP, VOID, LOCAL_GET, 0, LOCAL_GET, 1, I32_SHR_U]);
+            body.constant(0xF);
+            body.emit(&[I32_AND, LOCAL_TEE, 2]);
+            body.constant(10);
+            body.emit(&[I32_LT_U, IF, I32]);
+            body.constant(b'0'.into());
+            body.emit(&[ELSE]);
+            body.constant((b'A' - 10).into());
+            body.emit(&[END, LOCAL_GET, 2, I32_ADD, CALL, WRITE]);
+            body.emit(&[LOCAL_GET, 1]);
+            body.constant(4);
+            body.emit(&[I32_SUB, LOCAL_TEE, 1]);
+            body.constant(0);
+            body.emit(&[I32_GE_S, BR_IF, 0, END]);
+            },
+        DisplayMode::Numeric | DisplayMode::Hex | DisplayMode::Bin | DisplayMode::Oct => {
+            /* Find the highest power of the display mode's base, not above the value - the second local holds it */
+            let base = options.display_mode.radix() as i32;
+            body.constant(1);
+            body.emit(&[LOCAL_SET, 1]);
+            body.emit(&[BLOCK, VOID, LOOP, VOID, LOCAL_GET, 0, LOCAL_GET, 1, I32_DIV_U]);
+            body.constant(base);
+            body.emit(&[I32_LT_U, BR_IF, 1, LOCAL_GET, 1]);
+            body.constant(base);
+            body.emit(&[I32_MUL, LOCAL_SET, 1, BR, 0, END, END]);
+
+            /* Write the digits from the most significant one - the third local holds the digit */
+            body.emit(&[LOOP, VOID, LOCAL_GET, 0, LOCAL_GET, 1, I32_DIV_U]);
+            body.constant(base);
+            body.emit(&[I32_REM_U]);
+            match base > 10 {
+                true => {
+                    body.emit(&[LOCAL_TEE, 2]);
+                    body.constant(10);
+                    body.emit(&[I32_LT_U, IF, I32]);
+                    body.constant(b'0'.into());
+                    body.emit(&[ELSE]);
+                    body.constant((b'A' - 10).into());
+                    body.emit(&[END, LOCAL_GET, 2]);
+                    },
+                false =>
+                    body.constant(b'0'.into())
+                }
+            body.emit(&[I32_ADD, CALL, WRITE, LOCAL_GET, 1]);
+            body.constant(base);
+            body.emit(&[I32_DIV_U, LOCAL_TEE, 1, BR_IF, 0, END]);
+            },
+        DisplayMode::Raw => {
+            /* The low byte is written as it is */
+            body.emit(&[LOCAL_GET, 0]);
+            body.constant(0xFF);
+            body.emit(&[I32_AND, CALL, WRITE]);
+            },
+        /* Unicode display was rejected while validating */
+        DisplayMode::Unicode => ()
+        }
+
+    body.emit(&[END]);
+    body.code
+    }
+
+/* Function for compiling the instructions into a WebAssembly module - exporting the `run` function, and the `memory` with the tape, and importing the `env.read`, and `env.write` callbacks */
+pub fn compile_wasm(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    options.validate_native()?;
+
+    /* The tape is placed at the start of the memory */
+    let pages = (options.tape_len() * options.cell_bytes()).div_ceil(PAGE_SIZE);
+    if pages > MAX_PAGES {
+        return Err(CompileError::TapeTooLarge);
+        }
+
+    let ir = lower_checked(instr, options.opt_level)?;
+    let mut body = Body {
+        code: Vec::with_capacity(16 * ir.len()),
+        options,
+        index: 0
+        };
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) =>
+                body.add_cell(value.into()),
+            IrOp::Move(value) =>
+                body.move_pointer(value as i64),
+            IrOp::Set(value) =>
+                body.set_cell(value),
+            IrOp::MulAdd(offset, factor) =>
+                body.mul_add(offset as i64, factor),
+            IrOp::AddAt(offset, value) =>
+                body.at(offset as i64, |body| body.add_cell(value.into())),
+            IrOp::SetAt(offset, value) =>
+                body.at(offset as i64, |body| body.set_cell(value)),
+            IrOp::Output => {
+                body.load_cell();
+                body.emit(&[CALL, PUT_VALUE]);
+                },
+            /* Negative result of the callback marks the end of input, which is a failure, like in the compiled executables */
+            IrOp::Input => {
+                body.emit(&[CALL, READ, LOCAL_TEE, 1]);
+                body.constant(0);
+                body.emit(&[I32_LT_S, IF, VOID, UNREACHABLE, END]);
+
+                body.address();
+                body.emit(&[LOCAL_GET, 1]);
+                /* Only the lowest bit is kept for 1-bit cells */
+                if options.cell_size == CellSize::U1 {
+                    body.constant(1);
+                    body.emit(&[I32_AND]);
+                    }
+                body.store_cell();
+                },
+            /* Leave the outer block, when the cell is zero */
+            IrOp::LoopStart(_) => {
+                body.emit(&[BLOCK, VOID, LOOP, VOID]);
+                body.load_cell();
+                body.emit(&[I32_EQZ, BR_IF, 1]);
+                },
+            /* Go back to the start of the inner loop */
+            IrOp::LoopEnd(_) =>
+                body.emit(&[BR, 0, END, END]),
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run - raw output stays byte-exact */
+    if options.display_mode != DisplayMode::Raw {
+        body.constant(b'\n'.into());
+        body.emit(&[CALL, WRITE]);
+        }
+    body.emit(&[END]);
+
+    let mut output = Vec::with_capacity(256 + body.code.len());
+    output.extend_from_slice(b"\0asm");
+    output.extend_from_slice(&1_u32.to_le_bytes());
+
+    /* Types - `() -> i32`, `(i32) -> ()`, and `() -> ()` */
+    put_section(&mut output, 1, &[
+        3,
+        0x60, 0, 1, I32,
+        0x60, 1, I32, 0,
+        0x60, 0, 0
+        ]);
+
+    /* Imported callbacks */
+    let mut imports = vec![2];
+    for (name, kind) in [(&b"read"[..], 0), (&b"write"[..], 1)] {
+        put_vector(&mut imports, b"env");
+        put_vector(&mut imports, name);
+        imports.extend_from_slice(&[0x00, kind]);
+        }
+    put_section(&mut output, 2, &imports);
+
+    /* Types of the routine, and of the program */
+    put_section(&mut output, 3, &[2, 1, 2]);
+
+    /* Memory, with a minimal size only */
+    let mut memory = vec![1, 0x00];
+    put_unsigned(&mut memory, pages);
+    put_section(&mut output, 5, &memory);
+
+    /* Exports */
+    let mut exports = vec![2];
+    put_vector(&mut exports, b"run");
+    exports.extend_from_slice(&[0x00, RUN]);
+    put_vector(&mut exports, b"memory");
+    exports.extend_from_slice(&[0x02, 0]);
+    put_section(&mut output, 7, &exports);
+
+    /* Code - both functions have three extra i32 locals */
+    let mut code = vec![2];
+    for body in [put_value(options), body.code] {
+        let mut function = vec![1, 3, I32];
+        function.extend_from_slice(&body);
+        put_vector(&mut code, &function);
+        }
+    put_section(&mut output, 10, &code);
+
+    Ok(output)
+    }
+
+
+#[cfg(test)]
+mod test {
+    use crate::wasm::*;
+
+    #[test]
+    fn leb128() {
+        let mut output = Vec::new();
+        put_unsigned(&mut output, 624485);
+        put_signed(&mut output, -123456);
+        put_signed(&mut output, 64);
+
+        assert_eq!(output, [0xE5, 0x8E, 0x26, 0xC0, 0xBB, 0x78, 0xC0, 0x00]);
+        }
+
+    #[test]
+    fn compile_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let module = compile_wasm(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&module[.. 8], b"\0asm\x01\0\0\0");
+        }
+
+    #[test]
+    fn compile_err_tape_too_large() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            pointer_size: DataSize::U32,
+            cell_size: CellSize::U16,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_wasm(&instr, options), Err(CompileError::TapeTooLarge));
+        }
+    }
\ No newline at end of file
@@ -0,0 +1,133 @@
+use {
+    js_sys::{
+        Function,
+        Uint8Array
+        },
+    wasm_bindgen::prelude::*,
+    num_traits::ToPrimitive,
+    crate::{
+        eval::eval_instr,
+        interp::*,
+        ir::Ir,
+        utils::{
+            DisplayMode,
+            EofMode,
+            OptLevel,
+            SharedBuffer,
+            SharedFeed
+            }
+        }
+    };
+
+
+/* Function for turning the error thrown by a callback into a Rust one */
+fn thrown(value: JsValue) -> JsError {
+    JsError::new(&value.as_string().unwrap_or_else(|| format!("{value:?}")))
+    }
+
+
+/* Function for parsing the source code, and getting its instructions back, without the comments */
+#[wasm_bindgen]
+pub fn eval(code: &str) -> Result<String, JsError> {
+    Ok(eval_instr(code)?
+        .to_source())
+    }
+
+/* Function for getting the optimised operations of the source code, one per line */
+#[wasm_bindgen]
+pub fn optimize(code: &str, level: u8) -> Result<String, JsError> {
+    let ir = Ir::lower_at(&eval_instr(code)?, OptLevel::from_number(level));
+
+    Ok(ir.ops()
+        .iter()
+        .map(|op| format!("{op:?}\n"))
+        .collect())
+    }
+
+/* Interpreter driven by the JavaScript callbacks - the output one gets the written bytes before every input, and at the end, while the input one gives a whole line, or nothing at the end of input */
+#[wasm_bindgen]
+pub struct Playground {
+    interp: Box<dyn InterpRun>,
+    feed: SharedFeed,
+    sink: SharedBuffer,
+    on_input: Function,
+    on_output: Function
+    }
+
+#[wasm_bindgen]
+impl Playground {
+    /* Constructor function - the cells are bytes, the streams stay raw, and the end of input reads as zero */
+    #[wasm_bindgen(constructor)]
+    pub fn new(on_input: Function, on_output: Function, level: u8) -> Result<Playground, JsError> {
+        let feed = SharedFeed::default();
+        let sink = SharedBuffer::default();
+
+        let interp = Interpreter::builder()
+            .display_mode(DisplayMode::Raw)
+            .byte_input(true)
+            .eof(EofMode::Zero)
+            .opt_level(OptLevel::from_number(level))
+            .input(Box::new(feed.clone()))
+            .output(Box::new(sink.clone()))
+            .try_build::<u16, u8>()?;
+
+        Ok(Self {
+            interp: Box::new(interp),
+            feed,
+            sink,
+            on_input,
+            on_output
+            })
+        }
+
+    /* Run the source code - the tape stays, until the reset */
+    pub fn run(&mut self, code: &str) -> Result<(), JsError> {
+        let instr = eval_instr(code)?;
+        let ir = self.interp.prepare(&instr);
+
+        let mut op_ptr = 0;
+        let mut count = 0;
+
+        loop {
+            op_ptr = self.interp.run_until_input(&ir, op_ptr, &mut count)?;
+
+            /* Hand out everything written so far */
+            let output = self.sink.take();
+            if ! output.is_empty() {
+                self.on_output.call1(&JsValue::NULL, &Uint8Array::from(&output[..]))
+                    .map_err(thrown)?;
+                }
+
+            if op_ptr >= ir.len() {
+                return Ok(());
+                }
+
+            /* Ask for the next line, only when the last one was used up */
+            if self.feed.is_empty() {
+                let line = self.on_input.call0(&JsValue::NULL)
+                    .map_err(thrown)?;
+
+                if let Some(line) = line.as_string() {
+                    self.feed.extend(line.as_bytes());
+                    }
+                }
+
+            op_ptr = self.interp.step(&ir, op_ptr, &mut count)?;
+            }
+        }
+
+    /* Bring the tape back to its starting state */
+    pub fn reset(&mut self) {
+        self.interp.reset();
+        }
+
+    /* Getters */
+    pub fn position(&self) -> isize {
+        self.interp.position()
+        }
+    /* Get the cell's value - none, for the ones off the tape, as the byte cells always fit */
+    pub fn peek(&mut self, index: isize) -> Option<u32> {
+        self.interp.peek(index)
+            .and_then(|value| value.to_u32())
+        }
+    }
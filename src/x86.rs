@@ -0,0 +1,571 @@
+use crate::{
+    codegen::*,
+    elf,
+    eval::*,
+    ir::*,
+    macho,
+    utils::*
+    };
+
+
+/* Index registers' encodings */
+const RBX: u8 = 0b011;
+const RCX: u8 = 0b001;
+
+
+/* Helper container for assembling machine code */
+struct Asm {
+    code: Vec<u8>,
+    options: CodegenOptions,
+    layout: Layout,
+    /* Register holding the addressed cell's index - rbx, or rcx for the cells at an offset */
+    index: u8
+    }
+
+impl Asm {
+    /* Get the current offset */
+    const fn pos(&self) -> usize {
+        self.code.len()
+        }
+
+    fn emit(&mut self, bytes: &[u8]) {
+        self.code.extend_from_slice(bytes);
+        }
+
+    /* Emit a 32-bit displacement to the target, relative to the end of the displacement */
+    fn rel32(&mut self, target: usize) {
+        let rel = target as i64 - (self.pos() + 4) as i64;
+        self.emit(&(rel as i32).to_le_bytes());
+        }
+
+    /* Patch a 32-bit displacement, emitted earlier, to point at the target */
+    fn patch_rel32(&mut self, at: usize, target: usize) {
+        let rel = target as i64 - (at + 4) as i64;
+        self.code[at .. at + 4].copy_from_slice(&(rel as i32).to_le_bytes());
+        }
+
+    /* Emit a short conditional jump with a placeholder, returns the location of its displacement */
+    fn jump8(&mut self, opcode: u8) -> usize {
+        self.emit(&[opcode, 0]);
+        self.pos() - 1
+        }
+
+    /* Emit a short conditional jump back to the target */
+    fn jump8_back(&mut self, opcode: u8, target: usize) {
+        let rel = target as i64 - (self.pos() + 2) as i64;
+        self.emit(&[opcode, rel as i8 as u8]);
+        }
+
+    /* Patch a short jump's displacement, emitted earlier, to point at the target */
+    fn patch8(&mut self, at: usize, target: usize) {
+        self.code[at] = (target - (at + 1)) as u8;
+        }
+
+    fn call(&mut self, target: usize) {
+        self.emit(&[0xE8]);
+        self.rel32(target);
+        }
+
+    /* Load an address in the zero-initialised memory - `lea reg, [rip + disp32]` */
+    fn lea_bss(&mut self, opcode: &[u8], offset: u64) {
+        self.emit(opcode);
+        let next = self.layout.code_address + self.pos() as u64 + 4;
+        let rel = (self.layout.bss_address + offset).wrapping_sub(next);
+        self.emit(&(rel as i32).to_le_bytes());
+        }
+
+    /* Emit a system call, with its arguments already in place - `mov eax, number`, and `syscall` */
+    fn syscall(&mut self, call: Syscall) {
+        let number: u32 = match self.layout.platform {
+            Platform::Linux => match call {
+                Syscall::Read => 0,
+                Syscall::Write => 1,
+                Syscall::Exit => 60
+                },
+            /* BSD system calls are in their own class */
+            Platform::MacOs => 0x200_0000 | call.bsd_number()
+            };
+
+        self.emit(&[0xB8]);
+        self.emit(&number.to_le_bytes());
+        self.emit(&[0x0F, 0x05]);
+        }
+
+    /* Emit the operation on the cell at an offset from the pointer - its index is computed into rcx, wrapping in the pointer's width */
+    fn at(&mut self, offset: i64, operation: impl FnOnce(&mut Self)) {
+        if offset != 0 {
+            self.emit(&[0x8D, 0x8B]);                   /* lea ecx, [rbx + offset] */
+            self.emit(&(offset as i32).to_le_bytes());
+            match self.options.pointer_size {
+                DataSize::U8 => self.emit(&[0x0F, 0xB6, 0xC9]),     /* movzx ecx, cl */
+                DataSize::U16 => self.emit(&[0x0F, 0xB7, 0xC9]),    /* movzx ecx, cx */
+                /* 64-bit pointers were rejected while validating */
+                DataSize::U32 | DataSize::U64 => ()
+                }
+            self.index = RCX;
+            }
+
+        operation(self);
+        self.index = RBX;
+        }
+
+    /* Emit an instruction with the addressed cell as the memory operand - `[r12 + rbx * size]` */
+    fn cell_op(&mut self, opcode: &[u8], reg: u8) {
+        let scale = self.options.cell_bytes().trailing_zeros() as u8;
+
+        /* Operand size prefix for 16-bit cells, then REX.B for r12 */
+        if self.options.cell_bytes() == 2 {
+            self.emit(&[0x66]);
+            }
+        self.emit(&[0x41]);
+        self.emit(opcode);
+        self.emit(&[reg << 3 | 0b100, scale << 6 | self.index << 3 | 0b100]);
+        }
+
+    /* Add a value to the current cell - wrapping, as it's done in the cell's width */
+    fn add_cell(&mut self, value: i64) {
+        match self.options.cell_size {
+            /* Adding an odd value to a single bit flips it */
+            CellSize::U1 => if value % 2 != 0 {
+                self.cell_op(&[0x80], 6);
+                self.emit(&[1]);
+                },
+            CellSize::U8 => if value as u8 != 0 {
+                self.cell_op(&[0x80], 0);
+                self.emit(&[value as u8]);
+                },
+            CellSize::U16 => if value as u16 != 0 {
+                self.cell_op(&[0x81], 0);
+                self.emit(&(value as u16).to_le_bytes());
+                },
+            CellSize::U32 => if value as u32 != 0 {
+                self.cell_op(&[0x81], 0);
+                self.emit(&(value as u32).to_le_bytes());
+                },
+            /* 64-bit, arbitrary-precision, and signed cells were rejected while validating */
+            CellSize::U64 | CellSize::Big | CellSize::I8 | CellSize::I16 | CellSize::I32 => ()
+            }
+        }
+
+    /* Move the pointer - wrapping, as it's done in the pointer's width */
+    fn move_pointer(&mut self, value: i64) {
+        match self.options.pointer_size {
+            DataSize::U8 => if value as u8 != 0 {
+                self.emit(&[0x80, 0xC3, value as u8]);
+                },
+            DataSize::U16 => if value as u16 != 0 {
+                self.emit(&[0x66, 0x81, 0xC3]);
+                self.emit(&(value as u16).to_le_bytes());
+                },
+            DataSize::U32 => if value as u32 != 0 {
+                self.emit(&[0x81, 0xC3]);
+                self.emit(&(value as u32).to_le_bytes());
+                },
+            /* 64-bit pointers were rejected while validating */
+            DataSize::U64 => ()
+            }
+        }
+
+    /* Compare the current cell with zero */
+    fn test_cell(&mut self) {
+        match self.options.cell_bytes() {
+            1 => self.cell_op(&[0x80], 7),
+            _ => self.cell_op(&[0x83], 7)
+            };
+        self.emit(&[0]);
+        }
+
+    /* Load the current cell into eax, zero-extended */
+    fn load_cell(&mut self) {
+        match self.options.cell_bytes() {
+            1 => self.cell_op(&[0x0F, 0xB6], 0),
+            2 => self.cell_op(&[0x0F, 0xB7], 0),
+            _ => self.cell_op(&[0x8B], 0)
+            }
+        }
+
+    /* Set the current cell to a value, truncated to the cell's width */
+    fn set_cell(&mut self, value: u64) {
+        self.emit(&[0xB8]);             /* mov eax, value */
+        self.emit(&((value & self.options.cell_mask()) as u32).to_le_bytes());
+        self.store_cell();
+        }
+
+    /* Add the current cell times a factor to the cell at an offset - the pointer is moved there, and back */
+    fn mul_add(&mut self, offset: i64, factor: i16) {
+        match self.options.cell_size {
+            /* Bits are flipped by odd factors only, and the cell is the product itself */
+            CellSize::U1 => if factor % 2 != 0 {
+                self.load_cell();
+                self.move_pointer(offset);
+                self.cell_op(&[0x30], 0);           /* xor [cell], al */
+                self.move_pointer(-offset);
+                },
+            _ => if factor != 0 {
+                self.load_cell();
+                if factor != 1 {
+                    self.emit(&[0x69, 0xC0]);       /* imul eax, eax, factor */
+                    self.emit(&i32::from(factor).to_le_bytes());
+                    }
+                self.move_pointer(offset);
+                match self.options.cell_bytes() {
+                    1 => self.cell_op(&[0x00], 0),  /* add [cell], al */
+                    _ => self.cell_op(&[0x01], 0)   /* add [cell], eax */
+                    }
+                self.move_pointer(-offset);
+                }
+            }
+        }
+
+    /* Store eax into the current cell */
+    fn store_cell(&mut self) {
+        /* Only the lowest bit is kept for 1-bit cells */
+        if self.options.cell_size == CellSize::U1 {
+            self.emit(&[0x24, 0x01]);
+            }
+
+        match self.options.cell_bytes() {
+            1 => self.cell_op(&[0x88], 0),
+            _ => self.cell_op(&[0x89], 0)
+            }
+        }
+    }
+
+
+/* Addresses of the runtime routines */
+struct Runtime {
+    flush: usize,
+    put_byte: usize,
+    get_byte: usize,
+    put_value: usize
+    }
+
+/* Function for emitting the runtime routines - r13 holds the buffered output's length, and r14 the buffer's address */
+fn emit_runtime(asm: &mut Asm) -> Runtime {
+    /* Write out the buffered output - `write(1, r14, r13)` */
+    let flush = asm.pos();
+    asm.emit(&[
+        0x4D, 0x85, 0xED,               /* test r13, r13 */
+        0x74, 0x15,                     /* je .ret */
+        0xBF, 0x01, 0, 0, 0,            /* mov edi, 1 */
+        0x4C, 0x89, 0xF6,               /* mov rsi, r14 */
+        0x4C, 0x89, 0xEA                /* mov rdx, r13 */
+        ]);
+    asm.syscall(Syscall::Write);
+    asm.emit(&[
+        0x45, 0x31, 0xED,               /* xor r13d, r13d */
+        0xC3                            /* .ret: ret */
+        ]);
+
+    /* Buffer the byte in al, and flush once the buffer gets full */
+    let put_byte = asm.pos();
+    asm.emit(&[
+        0x43, 0x88, 0x04, 0x2E,         /* mov [r14 + r13], al */
+        0x49, 0xFF, 0xC5,               /* inc r13 */
+        0x49, 0x81, 0xFD                /* cmp r13, BUFFER_SIZE */
+        ]);
+    asm.emit(&(BUFFER_SIZE as u32).to_le_bytes());
+    asm.emit(&[
+        0x75, 0x05,                     /* jne .ret */
+        0xE9                            /* jmp flush */
+        ]);
+    asm.rel32(flush);
+    asm.emit(&[0xC3]);                  /* .ret: ret */
+
+    /* Exit with a failure, keeping the output written so far - `exit(1)` */
+    let exit_failure = asm.pos();
+    asm.call(flush);
+    asm.emit(&[0xBF, 0x01, 0, 0, 0]);   /* mov edi, 1 */
+    asm.syscall(Syscall::Exit);
+
+    /* Read a single byte into eax - `read(0, rsp, 1)`, the end of input is a failure */
+    let get_byte = asm.pos();
+    asm.call(flush);
+    asm.emit(&[
+        0x6A, 0x00,                     /* push 0 */
+        0x31, 0xFF,                     /* xor edi, edi */
+        0x48, 0x89, 0xE6,               /* mov rsi, rsp */
+        0xBA, 0x01, 0, 0, 0             /* mov edx, 1 */
+        ]);
+    asm.syscall(Syscall::Read);
+    asm.emit(&[
+        0x59,                           /* pop rcx */
+        0x48, 0x83, 0xF8, 0x01,         /* cmp rax, 1 */
+        0x0F, 0x85                      /* jne exit_failure */
+        ]);
+    asm.rel32(exit_failure);
+    asm.emit(&[
+        0x0F, 0xB6, 0xC1,               /* movzx eax, cl */
+        0xC3                            /* ret */
+        ]);
+
+    /* Write the value in eax, according to the display mode */
+    let put_value = asm.pos();
+    match asm.options.display_mode {
+        DisplayMode::ASCII => {
+            /* Printable characters are written as they are - tab, line feed, and graphic characters */
+            asm.emit(&[0x83, 0xF8, b'~']);      /* cmp eax, '~' */
+            let above_tilde = asm.jump8(0x77);  /* ja .hex */
+            asm.emit(&[0x83, 0xF8, b' ']);      /* cmp eax, ' ' */
+            let graphic = asm.jump8(0x73);      /* jae .byte */
+            asm.emit(&[0x83, 0xF8, b'\t']);     /* cmp eax, '\t' */
+            let below_tab = asm.jump8(0x72);    /* jb .hex */
+            asm.emit(&[0x83, 0xF8, b'\n']);     /* cmp eax, '\n' */
+            let above_feed = asm.jump8(0x77);   /* ja .hex */
+
+            let byte = asm.pos();
+            asm.patch8(graphic, byte);
+            asm.emit(&[0xE9]);                  /* .byte: jmp put_byte */
+            asm.rel32(put_byte);
+
+            /* Fallback - `0x` prefixed, zero padded, uppercase hexadecimal */
+            let hex = asm.pos();
+            for at in [above_tilde, below_tab, above_feed] {
+                asm.patch8(at, hex);
+                }
+
+            asm.emit(&[
+                0x41, 0x89, 0xC0,               /* .hex: mov r8d, eax */
+                0xB0, b'0'                      /* mov al, '0' */
+                ]);
+            asm.call(put_byte);
+            asm.emit(&[0xB0, b'x']);            /* mov al, 'x' */
+            asm.call(put_byte);
+
+            /* Shift of the most significant digit */
+            let shift = (asm.options.cell_bytes() * 2 - 1) * 4;
+            asm.emit(&[0x41, 0xB9]);            /* mov r9d, shift */
+            asm.emit(&(shift as u32).to_le_bytes());
+
+            let digit = asm.pos();
+            asm.emit(&[
+                0x44, 0x89, 0xC0,               /* .digit: mov eax, r8d */
+                0x44, 0x89, 0xC9,               /* mov ecx, r9d */
+                0xD3, 0xE8,                     /* shr eax, cl */
+                0x83, 0xE0, 0x0F,               /* and eax, 15 */
+                0x3C, 0x0A,                     /* cmp al, 10 */
+                0x72, 0x02,                     /* jb .decimal */
+                0x04, 0x07,                     /* add al, 'A' - '0' - 10 */
+                0x04, b'0'                      /* .decimal: add al, '0' */
+                ]);
+            asm.call(put_byte);
+            asm.emit(&[0x41, 0x83, 0xE9, 0x04]); /* sub r9d, 4 */
+            asm.jump8_back(0x79, digit);        /* jns .digit */
+            asm.emit(&[0xC3]);                  /* ret */
+            },
+        DisplayMode::Numeric | DisplayMode::Hex | DisplayMode::Bin | DisplayMode::Oct => {
+            /* Push the digits in the display mode's base from the least significant, then pop them in order */
+            let base = asm.options.display_mode.radix();
+            asm.emit(&[
+                0x45, 0x31, 0xC9,               /* xor r9d, r9d */
+                0xB9                            /* mov ecx, base */
+                ]);
+            asm.emit(&base.to_le_bytes());
+
+            let divide = asm.pos();
+            asm.emit(&[
+                0x31, 0xD2,                     /* .divide: xor edx, edx */
+                0xF7, 0xF1                      /* div ecx */
+                ]);
+            if base > 10 {
+                asm.emit(&[
+                    0x80, 0xFA, 0x0A,           /* cmp dl, 10 */
+                    0x72, 0x03,                 /* jb .decimal */
+                    0x80, 0xC2, 0x07            /* add dl, 'A' - '0' - 10 */
+                    ]);
+                }
+            asm.emit(&[
+                0x80, 0xC2, b'0',               /* .decimal: add dl, '0' */
+                0x52,                           /* push rdx */
+                0x41, 0xFF, 0xC1,               /* inc r9d */
+                0x85, 0xC0                      /* test eax, eax */
+                ]);
+            asm.jump8_back(0x75, divide);       /* jnz .divide */
+
+            let digit = asm.pos();
+            asm.emit(&[0x58]);                  /* .digit: pop rax */
+            asm.call(put_byte);
+            asm.emit(&[0x41, 0xFF, 0xC9]);      /* dec r9d */
+            asm.jump8_back(0x75, digit);        /* jnz .digit */
+            asm.emit(&[0xC3]);                  /* ret */
+            },
+        DisplayMode::Raw => {
+            /* The low byte is written as it is */
+            asm.emit(&[0xE9]);                  /* jmp put_byte */
+            asm.rel32(put_byte);
+            },
+        /* Unicode display was rejected while validating */
+        DisplayMode::Unicode => ()
+        }
+
+    Runtime { flush, put_byte, get_byte, put_value }
+    }
+
+/* Function for compiling the instructions into a static x86-64 Linux executable */
+pub fn compile_x86_64(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    let (code, entry) = generate(instr, options, elf::LAYOUT)?;
+
+    Ok(elf::executable(elf::MACHINE_X86_64, &code, entry as u64, options.bss_size()))
+    }
+
+/* Function for compiling the instructions into an x86-64 macOS executable */
+pub fn compile_x86_64_macho(instr: &InstructionSet, options: CodegenOptions) -> Result<Vec<u8>, CompileError> {
+    let (code, entry) = generate(instr, options, macho::LAYOUT)?;
+
+    Ok(macho::executable(macho::CPU_X86_64, &code, entry as u64, options.bss_size()))
+    }
+
+/* Function for generating the machine code, placed according to the layout - returns the code, and the entry point's offset */
+fn generate(instr: &InstructionSet, options: CodegenOptions, layout: Layout) -> Result<(Vec<u8>, usize), CompileError> {
+    options.validate_native()?;
+
+    let ir = lower_checked(instr, options.opt_level)?;
+    let mut asm = Asm {
+        code: Vec::with_capacity(256 + 16 * ir.len()),
+        options,
+        layout,
+        index: RBX
+        };
+
+    let runtime = emit_runtime(&mut asm);
+
+    /* Entry point - the tape comes right after the output buffer */
+    let entry = asm.pos();
+    asm.lea_bss(&[0x4C, 0x8D, 0x25], BUFFER_SIZE);  /* lea r12, tape */
+    asm.lea_bss(&[0x4C, 0x8D, 0x35], 0);            /* lea r14, buffer */
+    asm.emit(&[
+        0x31, 0xDB,                     /* xor ebx, ebx */
+        0x45, 0x31, 0xED                /* xor r13d, r13d */
+        ]);
+
+    /* Locations of the displacements of the open loops' forward jumps */
+    let mut loop_stack = Vec::new();
+
+    for &op in ir.ops() {
+        match op {
+            IrOp::Add(value) =>
+                asm.add_cell(value.into()),
+            IrOp::Move(value) =>
+                asm.move_pointer(value as i64),
+            IrOp::Set(value) =>
+                asm.set_cell(value),
+            IrOp::MulAdd(offset, factor) =>
+                asm.mul_add(offset as i64, factor),
+            IrOp::AddAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.add_cell(value.into())),
+            IrOp::SetAt(offset, value) =>
+                asm.at(offset as i64, |asm| asm.set_cell(value)),
+            IrOp::Output => {
+                asm.load_cell();
+                asm.call(runtime.put_value);
+                },
+            IrOp::Input => {
+                asm.call(runtime.get_byte);
+                asm.store_cell();
+                },
+            /* Jump past the loop's end, when the cell is zero */
+            IrOp::LoopStart(_) => {
+                asm.test_cell();
+                asm.emit(&[0x0F, 0x84]);
+                loop_stack.push(asm.pos());
+                asm.emit(&[0; 4]);
+                },
+            /* Jump back to the loop's body, when the cell is non-zero */
+            IrOp::LoopEnd(_) => {
+                /* Brackets are balanced, as the instruction set was sanitised during evaluation */
+                let Some(open) = loop_stack.pop() else {
+                    continue;
+                    };
+
+                asm.test_cell();
+                asm.emit(&[0x0F, 0x85]);
+                asm.rel32(open + 4);
+
+                let end = asm.pos();
+                asm.patch_rel32(open, end);
+                },
+            /* Random values, debugging instructions, procedures, and tape switches were rejected while lowering */
+            IrOp::Random | IrOp::Dump | IrOp::ProcStart(_) | IrOp::ProcEnd | IrOp::Call | IrOp::Break | IrOp::ShowPointer | IrOp::ShowTape | IrOp::Switch(_) => ()
+            }
+        }
+
+    /* Final line feed, like after the Interpreter's run, then `exit(0)` - raw output stays byte-exact */
+    if options.display_mode != DisplayMode::Raw {
+        asm.emit(&[0xB0, b'\n']);       /* mov al, '\n' */
+        asm.call(runtime.put_byte);
+        }
+    asm.call(runtime.flush);
+    asm.emit(&[0x31, 0xFF]);            /* xor edi, edi */
+    asm.syscall(Syscall::Exit);
+
+    Ok((asm.code, entry))
+    }
+
+#[cfg(test)]
+mod test {
+    use crate::x86::*;
+
+    #[test]
+    fn compile_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let binary = compile_x86_64(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&binary[.. 4], b"\x7FELF");
+        }
+
+    #[test]
+    fn compile_macho_basic() {
+        let instr = eval_instr("++[->+<]>.,")
+            .expect("Unreachable");
+
+        let binary = compile_x86_64_macho(&instr, CodegenOptions::default())
+            .expect("Unreachable");
+
+        assert_eq!(&binary[.. 4], &0xFEEDFACF_u32.to_le_bytes());
+        }
+
+    #[test]
+    fn compile_err_ascii_bit_cells() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            cell_size: CellSize::U1,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_x86_64(&instr, options), Err(CompileError::AsciiBitCells));
+        }
+
+    #[test]
+    fn compile_err_unicode() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let options = CodegenOptions {
+            cell_size: CellSize::U32,
+            display_mode: DisplayMode::Unicode,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_x86_64(&instr, options), Err(CompileError::UnicodeDisplay));
+        }
+
+    #[test]
+    fn compile_err_wide_sizes() {
+        let instr = eval_instr("+.")
+            .expect("Unreachable");
+        let wide_cells = CodegenOptions {
+            cell_size: CellSize::U64,
+            ..CodegenOptions::default()
+            };
+        let wide_pointer = CodegenOptions {
+            pointer_size: DataSize::U64,
+            ..CodegenOptions::default()
+            };
+
+        assert_eq!(compile_x86_64(&instr, wide_cells), Err(CompileError::WideCells));
+        assert_eq!(compile_x86_64(&instr, wide_pointer), Err(CompileError::TapeTooLarge));
+        }
+    }
\ No newline at end of file